@@ -0,0 +1,23 @@
+-- Migration: Create api_keys and settings tables
+-- Klucze API dla lokalnego serwera HTTP oraz ogólna tabela ustawień aplikacji
+
+-- Tabela kluczy API (przechowujemy wyłącznie hash SHA-256 klucza)
+CREATE TABLE IF NOT EXISTS api_keys (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    name TEXT NOT NULL,
+    key_hash TEXT NOT NULL UNIQUE,
+    key_prefix TEXT NOT NULL, -- Początek klucza do rozpoznania go w UI
+    scopes TEXT NOT NULL, -- Lista uprawnień oddzielona przecinkami (Read, Write, Admin)
+    created_at INTEGER NOT NULL,
+    last_used_at INTEGER, -- Unix timestamp ostatniego użycia, opcjonalny
+    revoked_at INTEGER -- Unix timestamp unieważnienia, NULL dla aktywnych kluczy
+);
+
+CREATE INDEX IF NOT EXISTS idx_api_keys_revoked_at ON api_keys(revoked_at);
+
+-- Tabela ustawień aplikacji (klucz -> wartość)
+CREATE TABLE IF NOT EXISTS settings (
+    key TEXT PRIMARY KEY NOT NULL,
+    value TEXT NOT NULL,
+    updated_at INTEGER NOT NULL
+);
@@ -0,0 +1,31 @@
+-- Migration: Create webhooks and webhook_deliveries tables
+-- Webhooki wysyłane przy zdarzeniach w grze oraz dziennik prób dostarczenia
+
+-- Tabela zarejestrowanych webhooków
+CREATE TABLE IF NOT EXISTS webhooks (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    url TEXT NOT NULL,
+    events TEXT NOT NULL DEFAULT '', -- Typy zdarzeń oddzielone przecinkami, pusty = wszystkie
+    secret TEXT, -- Opcjonalny sekret do podpisu HMAC-SHA256
+    active BOOLEAN NOT NULL DEFAULT 1,
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+
+-- Dziennik dostarczeń webhooków
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    webhook_id INTEGER NOT NULL,
+    event_type TEXT NOT NULL,
+    payload TEXT NOT NULL, -- Wysłane ciało żądania (JSON)
+    status TEXT NOT NULL DEFAULT 'Pending' CHECK (status IN ('Pending', 'Delivered', 'Failed')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    response_status INTEGER, -- Kod HTTP ostatniej próby, opcjonalny
+    error TEXT, -- Opis błędu ostatniej nieudanej próby
+    created_at INTEGER NOT NULL,
+    last_attempt_at INTEGER, -- Unix timestamp ostatniej próby
+
+    FOREIGN KEY (webhook_id) REFERENCES webhooks (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_webhook_id ON webhook_deliveries(webhook_id, created_at);
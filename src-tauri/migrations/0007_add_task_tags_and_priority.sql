@@ -0,0 +1,29 @@
+-- Migration 0007: Add task priorities, attributes and tags
+-- Zastępuje zgadywanie kategorii i atrybutów na podstawie słów w tytule zadania
+
+ALTER TABLE tasks ADD COLUMN priority TEXT NOT NULL DEFAULT 'Medium'
+    CHECK (priority IN ('Low', 'Medium', 'High', 'Urgent'));
+
+-- Atrybut postaci rozwijany przez zadanie (NULL = wynikający z tagów lub żaden)
+ALTER TABLE tasks ADD COLUMN attribute TEXT
+    CHECK (attribute IN ('strength', 'intelligence', 'charisma', 'dexterity', 'wisdom', 'constitution'));
+
+CREATE TABLE IF NOT EXISTS tags (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    created_at INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS task_tags (
+    task_id INTEGER NOT NULL,
+    tag_id INTEGER NOT NULL,
+    PRIMARY KEY (task_id, tag_id),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+);
+
+-- Index dla wyszukiwania zadań po tagu
+CREATE INDEX IF NOT EXISTS idx_task_tags_tag_id ON task_tags(tag_id);
+
+-- Index dla filtrowania zadań po priorytecie
+CREATE INDEX IF NOT EXISTS idx_tasks_priority ON tasks(priority);
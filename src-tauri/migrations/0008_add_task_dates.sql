@@ -0,0 +1,12 @@
+-- Migration 0008: Add due and scheduled dates to tasks
+-- Pozwala budować widoki "na dziś", "w tym tygodniu", "zaległe" i "bez terminu" (inbox)
+
+-- Termin wykonania (Unix timestamp), NULL = brak terminu
+ALTER TABLE tasks ADD COLUMN due_at INTEGER;
+
+-- Dzień, na który zaplanowano zadanie (YYYY-MM-DD), NULL = niezaplanowane
+ALTER TABLE tasks ADD COLUMN scheduled_for TEXT;
+
+-- Indeksy dla widoków terminów
+CREATE INDEX IF NOT EXISTS idx_tasks_due_at ON tasks(due_at);
+CREATE INDEX IF NOT EXISTS idx_tasks_scheduled_for ON tasks(scheduled_for);
@@ -0,0 +1,20 @@
+-- Migration 0009: Add recurring tasks
+-- Seria powtarzających się zadań; każde wystąpienie to osobny wiersz w tasks
+
+CREATE TABLE IF NOT EXISTS task_recurrences (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    -- Reguła w podzbiorze RFC 5545, np. FREQ=WEEKLY;BYDAY=MO,TH
+    rule TEXT NOT NULL,
+    -- 0 = seria zatrzymana, kolejne wystąpienia nie są tworzone
+    active BOOLEAN NOT NULL DEFAULT 1,
+    -- Liczba utworzonych wystąpień (dla COUNT)
+    occurrences INTEGER NOT NULL DEFAULT 1,
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+
+ALTER TABLE tasks ADD COLUMN recurrence_id INTEGER
+    REFERENCES task_recurrences(id) ON DELETE SET NULL;
+
+-- Index dla wyszukiwania wystąpień serii
+CREATE INDEX IF NOT EXISTS idx_tasks_recurrence_id ON tasks(recurrence_id);
@@ -0,0 +1,25 @@
+-- Migration 0010: Add task projects and subtasks
+-- Projekty grupują zadania, a parent_id tworzy drzewo podzadań
+
+CREATE TABLE IF NOT EXISTS projects (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    -- Kolor w formacie #RRGGBB
+    color TEXT NOT NULL DEFAULT '#6366f1',
+    -- Atrybut rozwijany przez zadania projektu, które nie mają własnego
+    attribute TEXT
+        CHECK (attribute IN ('strength', 'intelligence', 'charisma', 'dexterity', 'wisdom', 'constitution')),
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+
+-- Usunięcie projektu zostawia jego zadania bez projektu
+ALTER TABLE tasks ADD COLUMN project_id INTEGER
+    REFERENCES projects(id) ON DELETE SET NULL;
+
+-- Usunięcie zadania usuwa jego podzadania
+ALTER TABLE tasks ADD COLUMN parent_id INTEGER
+    REFERENCES tasks(id) ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS idx_tasks_project_id ON tasks(project_id);
+CREATE INDEX IF NOT EXISTS idx_tasks_parent_id ON tasks(parent_id);
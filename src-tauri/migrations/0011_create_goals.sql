@@ -0,0 +1,77 @@
+-- Migration 0011: Create goals and key results
+-- Drzewo celów Wizja → Cel Roczny → Cel Kwartalny → Cel Miesięczny (dokument projektowy 2.5)
+
+CREATE TABLE IF NOT EXISTS goals (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT,
+    level TEXT NOT NULL CHECK (level IN ('Vision', 'Yearly', 'Quarterly', 'Monthly')),
+    -- Cel nadrzędny musi mieć wyższy poziom; usunięcie celu usuwa jego podcele
+    parent_id INTEGER REFERENCES goals(id) ON DELETE CASCADE,
+    deadline INTEGER, -- Unix timestamp, opcjonalny
+    status TEXT NOT NULL DEFAULT 'Active' CHECK (status IN ('Active', 'Completed', 'Abandoned')),
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+
+-- Kluczowe rezultaty - mierzalne wartości docelowe celu
+CREATE TABLE IF NOT EXISTS key_results (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    goal_id INTEGER NOT NULL REFERENCES goals(id) ON DELETE CASCADE,
+    title TEXT NOT NULL,
+    target_value REAL NOT NULL CHECK (target_value > 0),
+    current_value REAL NOT NULL DEFAULT 0,
+    unit TEXT,
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_goals_parent_id ON goals(parent_id);
+CREATE INDEX IF NOT EXISTS idx_goals_level ON goals(level);
+CREATE INDEX IF NOT EXISTS idx_key_results_goal_id ON key_results(goal_id);
+
+-- Usunięcie celu zostawia jego zadania bez celu
+ALTER TABLE tasks ADD COLUMN goal_id INTEGER
+    REFERENCES goals(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_tasks_goal_id ON tasks(goal_id);
+
+-- Questy celów: SQLite nie pozwala zmienić CHECK, więc tabela questów jest przebudowywana
+CREATE TABLE quests_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    quest_type TEXT NOT NULL CHECK (quest_type IN ('Task', 'Habit', 'Character', 'Goal')),
+    target_value INTEGER NOT NULL,
+    current_progress INTEGER NOT NULL DEFAULT 0,
+    category TEXT, -- Opcjonalna kategoria dla questów zadaniowych
+    habit_id INTEGER, -- Opcjonalny ID nawyku dla questów nawykowych
+    goal_id INTEGER, -- Opcjonalny ID celu kwartalnego dla questów celów
+    status TEXT NOT NULL DEFAULT 'Active' CHECK (status IN ('Active', 'Completed', 'Expired')),
+    reward_exp INTEGER NOT NULL,
+    deadline INTEGER, -- Unix timestamp, opcjonalny
+    week TEXT NOT NULL, -- Format YYYY-WW
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL,
+
+    FOREIGN KEY (habit_id) REFERENCES habits (id) ON DELETE CASCADE,
+    FOREIGN KEY (goal_id) REFERENCES goals (id) ON DELETE CASCADE
+);
+
+INSERT INTO quests_new (
+    id, title, description, quest_type, target_value, current_progress, category, habit_id,
+    status, reward_exp, deadline, week, created_at, updated_at
+)
+SELECT id, title, description, quest_type, target_value, current_progress, category, habit_id,
+       status, reward_exp, deadline, week, created_at, updated_at
+FROM quests;
+
+DROP TABLE quests;
+ALTER TABLE quests_new RENAME TO quests;
+
+CREATE INDEX IF NOT EXISTS idx_quests_status ON quests(status);
+CREATE INDEX IF NOT EXISTS idx_quests_week ON quests(week);
+CREATE INDEX IF NOT EXISTS idx_quests_type ON quests(quest_type);
+CREATE INDEX IF NOT EXISTS idx_quests_habit_id ON quests(habit_id);
+CREATE INDEX IF NOT EXISTS idx_quests_goal_id ON quests(goal_id);
+CREATE INDEX IF NOT EXISTS idx_quests_deadline ON quests(deadline);
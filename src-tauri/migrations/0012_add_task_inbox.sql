@@ -0,0 +1,46 @@
+-- Migration 0012: Add inbox state for quick-captured tasks
+-- Zadania ze skrzynki czekają na przegląd (projekt, termin, tagi, priorytet) w stylu GTD
+
+-- 1 = zadanie czeka w skrzynce na przegląd
+ALTER TABLE tasks ADD COLUMN inbox BOOLEAN NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_tasks_inbox ON tasks(inbox);
+
+-- Dni, w których skrzynka została opróżniona (liczone najwyżej raz dziennie)
+CREATE TABLE IF NOT EXISTS inbox_zero_days (
+    day TEXT PRIMARY KEY NOT NULL, -- Format YYYY-MM-DD
+    reached_at INTEGER NOT NULL
+);
+
+-- Odznaki za pustą skrzynkę: SQLite nie pozwala zmienić CHECK, więc tabela odznak jest przebudowywana
+CREATE TABLE achievements_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL,
+    achievement_type TEXT NOT NULL CHECK (achievement_type IN ('HabitStreak', 'TaskCount', 'CharacterLevel', 'QuestCount', 'InboxZero')),
+    required_value INTEGER NOT NULL,
+    icon TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'Locked' CHECK (status IN ('Locked', 'Available', 'Earned')),
+    earned_at INTEGER, -- Unix timestamp, tylko gdy status = 'Earned'
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+
+INSERT INTO achievements_new (
+    id, name, description, achievement_type, required_value, icon, status, earned_at, created_at, updated_at
+)
+SELECT id, name, description, achievement_type, required_value, icon, status, earned_at, created_at, updated_at
+FROM achievements;
+
+DROP TABLE achievements;
+ALTER TABLE achievements_new RENAME TO achievements;
+
+CREATE INDEX IF NOT EXISTS idx_achievements_type ON achievements(achievement_type);
+CREATE INDEX IF NOT EXISTS idx_achievements_status ON achievements(status);
+CREATE INDEX IF NOT EXISTS idx_achievements_earned_at ON achievements(earned_at);
+
+INSERT INTO achievements (
+    name, description, achievement_type, required_value, icon, status, created_at, updated_at
+) VALUES
+    ('Czysta Skrzynka', 'Opróżnij skrzynkę zadań', 'InboxZero', 1, '📥', 'Locked', strftime('%s', 'now'), strftime('%s', 'now')),
+    ('Mistrz Przeglądu', 'Opróżnij skrzynkę zadań w 10 różnych dniach', 'InboxZero', 10, '🗃️', 'Locked', strftime('%s', 'now'), strftime('%s', 'now'));
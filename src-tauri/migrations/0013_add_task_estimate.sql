@@ -0,0 +1,4 @@
+-- Migration 0013: Add time estimates to tasks
+-- Szacowany czas wykonania, np. z szybkiego dodawania ("~30m")
+
+ALTER TABLE tasks ADD COLUMN estimate_minutes INTEGER CHECK (estimate_minutes > 0);
@@ -0,0 +1,107 @@
+-- Migration 0014: Add task notes and a full-text search index
+-- Indeks FTS5 obejmuje tytuły i notatki zadań, tytuły nawyków oraz tytuły i opisy questów
+
+-- Notatki zadania (dowolny tekst)
+ALTER TABLE tasks ADD COLUMN notes TEXT;
+
+-- Przeszukiwane dokumenty z oryginalnym tekstem (źródło snippetów)
+CREATE TABLE IF NOT EXISTS search_documents (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('task', 'habit', 'quest')),
+    item_id INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    body TEXT,
+    UNIQUE (kind, item_id)
+);
+
+-- remove_diacritics zdejmuje ogonki (ą → a, ś → s), ale nie rozkłada "ł",
+-- więc do indeksu trafia tekst z "ł" zamienionym na "l" (ta sama długość tokenów,
+-- dzięki czemu snippety z oryginalnego tekstu są podświetlane poprawnie)
+CREATE VIRTUAL TABLE IF NOT EXISTS search_index USING fts5(
+    title,
+    body,
+    content = 'search_documents',
+    content_rowid = 'id',
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER IF NOT EXISTS search_documents_ai AFTER INSERT ON search_documents BEGIN
+    INSERT INTO search_index (rowid, title, body) VALUES (
+        new.id,
+        replace(replace(new.title, 'ł', 'l'), 'Ł', 'L'),
+        replace(replace(new.body, 'ł', 'l'), 'Ł', 'L')
+    );
+END;
+
+CREATE TRIGGER IF NOT EXISTS search_documents_ad AFTER DELETE ON search_documents BEGIN
+    INSERT INTO search_index (search_index, rowid, title, body) VALUES (
+        'delete',
+        old.id,
+        replace(replace(old.title, 'ł', 'l'), 'Ł', 'L'),
+        replace(replace(old.body, 'ł', 'l'), 'Ł', 'L')
+    );
+END;
+
+CREATE TRIGGER IF NOT EXISTS search_documents_au AFTER UPDATE ON search_documents BEGIN
+    INSERT INTO search_index (search_index, rowid, title, body) VALUES (
+        'delete',
+        old.id,
+        replace(replace(old.title, 'ł', 'l'), 'Ł', 'L'),
+        replace(replace(old.body, 'ł', 'l'), 'Ł', 'L')
+    );
+    INSERT INTO search_index (rowid, title, body) VALUES (
+        new.id,
+        replace(replace(new.title, 'ł', 'l'), 'Ł', 'L'),
+        replace(replace(new.body, 'ł', 'l'), 'Ł', 'L')
+    );
+END;
+
+-- Synchronizacja dokumentów z zadaniami, nawykami i questami
+CREATE TRIGGER IF NOT EXISTS tasks_search_ai AFTER INSERT ON tasks BEGIN
+    INSERT INTO search_documents (kind, item_id, title, body) VALUES ('task', new.id, new.title, new.notes);
+END;
+
+CREATE TRIGGER IF NOT EXISTS tasks_search_au AFTER UPDATE OF title, notes ON tasks BEGIN
+    UPDATE search_documents SET title = new.title, body = new.notes
+    WHERE kind = 'task' AND item_id = new.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS tasks_search_ad AFTER DELETE ON tasks BEGIN
+    DELETE FROM search_documents WHERE kind = 'task' AND item_id = old.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS habits_search_ai AFTER INSERT ON habits BEGIN
+    INSERT INTO search_documents (kind, item_id, title, body) VALUES ('habit', new.id, new.title, NULL);
+END;
+
+CREATE TRIGGER IF NOT EXISTS habits_search_au AFTER UPDATE OF title ON habits BEGIN
+    UPDATE search_documents SET title = new.title
+    WHERE kind = 'habit' AND item_id = new.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS habits_search_ad AFTER DELETE ON habits BEGIN
+    DELETE FROM search_documents WHERE kind = 'habit' AND item_id = old.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS quests_search_ai AFTER INSERT ON quests BEGIN
+    INSERT INTO search_documents (kind, item_id, title, body) VALUES ('quest', new.id, new.title, new.description);
+END;
+
+CREATE TRIGGER IF NOT EXISTS quests_search_au AFTER UPDATE OF title, description ON quests BEGIN
+    UPDATE search_documents SET title = new.title, body = new.description
+    WHERE kind = 'quest' AND item_id = new.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS quests_search_ad AFTER DELETE ON quests BEGIN
+    DELETE FROM search_documents WHERE kind = 'quest' AND item_id = old.id;
+END;
+
+-- Indeksowanie istniejących danych
+INSERT INTO search_documents (kind, item_id, title, body)
+SELECT 'task', id, title, notes FROM tasks;
+
+INSERT INTO search_documents (kind, item_id, title, body)
+SELECT 'habit', id, title, NULL FROM habits;
+
+INSERT INTO search_documents (kind, item_id, title, body)
+SELECT 'quest', id, title, description FROM quests;
@@ -0,0 +1,30 @@
+-- Migration 0015: Soft delete (trash) for tasks and habits
+-- Usunięte zadania i nawyki trafiają do kosza; trwale usuwane są dopiero po okresie przechowywania
+
+-- Timestamp przeniesienia do kosza (NULL = element aktywny)
+ALTER TABLE tasks ADD COLUMN deleted_at INTEGER;
+ALTER TABLE habits ADD COLUMN deleted_at INTEGER;
+
+CREATE INDEX IF NOT EXISTS idx_tasks_deleted_at ON tasks(deleted_at);
+CREATE INDEX IF NOT EXISTS idx_habits_deleted_at ON habits(deleted_at);
+
+-- Elementy w koszu nie pojawiają się w wyszukiwaniu, a po przywróceniu wracają do indeksu
+CREATE TRIGGER IF NOT EXISTS tasks_search_trash AFTER UPDATE OF deleted_at ON tasks
+WHEN old.deleted_at IS NULL AND new.deleted_at IS NOT NULL BEGIN
+    DELETE FROM search_documents WHERE kind = 'task' AND item_id = new.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS tasks_search_restore AFTER UPDATE OF deleted_at ON tasks
+WHEN old.deleted_at IS NOT NULL AND new.deleted_at IS NULL BEGIN
+    INSERT INTO search_documents (kind, item_id, title, body) VALUES ('task', new.id, new.title, new.notes);
+END;
+
+CREATE TRIGGER IF NOT EXISTS habits_search_trash AFTER UPDATE OF deleted_at ON habits
+WHEN old.deleted_at IS NULL AND new.deleted_at IS NOT NULL BEGIN
+    DELETE FROM search_documents WHERE kind = 'habit' AND item_id = new.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS habits_search_restore AFTER UPDATE OF deleted_at ON habits
+WHEN old.deleted_at IS NOT NULL AND new.deleted_at IS NULL BEGIN
+    INSERT INTO search_documents (kind, item_id, title, body) VALUES ('habit', new.id, new.title, NULL);
+END;
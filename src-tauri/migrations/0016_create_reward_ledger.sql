@@ -0,0 +1,18 @@
+-- Migration 0016: Create the reward ledger
+-- Każda nagroda (EXP i punkt atrybutu) jest przypisana do źródła, więc można ją
+-- przyznać tylko raz i cofnąć, gdy zadanie lub wpis nawyku przestaje być ukończony
+
+CREATE TABLE IF NOT EXISTS rewards (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    source_type TEXT NOT NULL CHECK (source_type IN ('task', 'habit')),
+    source_id INTEGER NOT NULL,
+    -- Dzień wpisu nawyku (YYYY-MM-DD); zadania mają pusty tekst, bo NULL-e nie kolidują w UNIQUE
+    source_date TEXT NOT NULL DEFAULT '',
+    exp INTEGER NOT NULL,
+    attribute TEXT,
+    created_at INTEGER NOT NULL,
+    UNIQUE (source_type, source_id, source_date)
+);
+
+-- Rejestr nie jest wypełniany wstecz: nagrody przyznane wcześniej nie są znane,
+-- więc ukończenia sprzed migracji po odznaczeniu nie odbierają EXP ani atrybutu
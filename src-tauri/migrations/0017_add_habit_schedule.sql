@@ -0,0 +1,4 @@
+-- Migration 0017: Add habit schedules
+-- Harmonogram jest zapisany jako JSON zgodny z HabitSchedule, np. {"type":"Weekdays","days":[1,3,5]}
+
+ALTER TABLE habits ADD COLUMN schedule TEXT NOT NULL DEFAULT '{"type":"Daily"}';
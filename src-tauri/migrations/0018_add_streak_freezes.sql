@@ -0,0 +1,17 @@
+-- Migration 0018: Add streak freezes
+-- Postać zbiera żetony zamrożenia streaka, a dni pokryte żetonem trafiają do historii wpisów
+
+-- Żetony do wykorzystania i najwyższy osiągnięty poziom (żeton jest przyznawany tylko za nowy poziom)
+ALTER TABLE characters ADD COLUMN streak_freezes INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE characters ADD COLUMN highest_level INTEGER NOT NULL DEFAULT 1;
+UPDATE characters SET highest_level = level;
+
+-- Dzień pokryty żetonem zamrożenia (nie liczy się jako wykonanie, ale nie przerywa streaka)
+ALTER TABLE habit_entries ADD COLUMN frozen INTEGER NOT NULL DEFAULT 0;
+
+-- Jeden wpis na nawyk i dzień (INSERT OR REPLACE nadpisuje wpis zamiast go dublować);
+-- z istniejących duplikatów zostaje najnowszy
+DELETE FROM habit_entries
+WHERE id NOT IN (SELECT MAX(id) FROM habit_entries GROUP BY habit_id, date);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_habit_entries_habit_date ON habit_entries(habit_id, date);
@@ -0,0 +1,17 @@
+-- Migration 0019: Add best streak and total completions to habits
+-- Najlepszy streak startuje od aktualnego (wcześniejsze serie odtwarza historia streaków w statystykach)
+
+ALTER TABLE habits ADD COLUMN best_streak INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE habits ADD COLUMN total_completions INTEGER NOT NULL DEFAULT 0;
+
+UPDATE habits SET best_streak = current_streak;
+
+-- Wykonanie: zaznaczony nawyk Boolean albo licznik z osiągniętym celem (lub dodatni bez celu)
+UPDATE habits SET total_completions = (
+    SELECT COUNT(*) FROM habit_entries
+    WHERE habit_entries.habit_id = habits.id
+      AND CASE habits.habit_type
+              WHEN 'Counter' THEN habit_entries.value >= COALESCE(habits.target_value, 1)
+              ELSE habit_entries.completed = 1
+          END
+);
@@ -0,0 +1,59 @@
+-- Migration 0020: Add the Overdue quest type
+-- Quest zaległych zadań był oznaczany kategorią "#overdue"; dostaje własny typ,
+-- a kategoria zostaje tylko dla nazw tagów
+
+-- SQLite nie pozwala zmienić CHECK, więc tabela questów jest przebudowywana
+CREATE TABLE quests_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    quest_type TEXT NOT NULL CHECK (quest_type IN ('Task', 'Habit', 'Character', 'Goal', 'Overdue')),
+    target_value INTEGER NOT NULL,
+    current_progress INTEGER NOT NULL DEFAULT 0,
+    category TEXT, -- Opcjonalna kategoria (tag) dla questów zadaniowych
+    habit_id INTEGER, -- Opcjonalny ID nawyku dla questów nawykowych
+    goal_id INTEGER, -- Opcjonalny ID celu kwartalnego dla questów celów
+    status TEXT NOT NULL DEFAULT 'Active' CHECK (status IN ('Active', 'Completed', 'Expired')),
+    reward_exp INTEGER NOT NULL,
+    deadline INTEGER, -- Unix timestamp, opcjonalny
+    week TEXT NOT NULL, -- Format YYYY-WW
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL,
+
+    FOREIGN KEY (habit_id) REFERENCES habits (id) ON DELETE CASCADE,
+    FOREIGN KEY (goal_id) REFERENCES goals (id) ON DELETE CASCADE
+);
+
+INSERT INTO quests_new (
+    id, title, description, quest_type, target_value, current_progress, category, habit_id,
+    goal_id, status, reward_exp, deadline, week, created_at, updated_at
+)
+SELECT id, title, description,
+       CASE WHEN category = '#overdue' THEN 'Overdue' ELSE quest_type END,
+       target_value, current_progress, NULLIF(category, '#overdue'), habit_id,
+       goal_id, status, reward_exp, deadline, week, created_at, updated_at
+FROM quests;
+
+DROP TABLE quests;
+ALTER TABLE quests_new RENAME TO quests;
+
+CREATE INDEX IF NOT EXISTS idx_quests_status ON quests(status);
+CREATE INDEX IF NOT EXISTS idx_quests_week ON quests(week);
+CREATE INDEX IF NOT EXISTS idx_quests_type ON quests(quest_type);
+CREATE INDEX IF NOT EXISTS idx_quests_habit_id ON quests(habit_id);
+CREATE INDEX IF NOT EXISTS idx_quests_goal_id ON quests(goal_id);
+CREATE INDEX IF NOT EXISTS idx_quests_deadline ON quests(deadline);
+
+-- Wyzwalacze indeksu wyszukiwania znikają razem ze starą tabelą
+CREATE TRIGGER IF NOT EXISTS quests_search_ai AFTER INSERT ON quests BEGIN
+    INSERT INTO search_documents (kind, item_id, title, body) VALUES ('quest', new.id, new.title, new.description);
+END;
+
+CREATE TRIGGER IF NOT EXISTS quests_search_au AFTER UPDATE OF title, description ON quests BEGIN
+    UPDATE search_documents SET title = new.title, body = new.description
+    WHERE kind = 'quest' AND item_id = new.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS quests_search_ad AFTER DELETE ON quests BEGIN
+    DELETE FROM search_documents WHERE kind = 'quest' AND item_id = old.id;
+END;
@@ -1,185 +1,1936 @@
-use axum::{extract::State, http::Method, response::Json, routing::get, Router};
-use serde_json::Value;
-use std::sync::{Arc, Mutex};
+mod openapi;
+mod responses;
+
+use axum::{
+    extract::{
+        rejection::{JsonRejection, PathRejection, QueryRejection},
+        Extension, MatchedPath, Path, Query, Request, State,
+    },
+    http::{header, HeaderValue, Method, StatusCode},
+    middleware::{self, Next},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Json, Response,
+    },
+    routing::{delete, get, patch, post},
+    Router,
+};
+use futures_util::stream::{self, Stream};
+use serde::Deserialize;
+use std::convert::Infallible;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, MutexGuard};
+use tokio::sync::{broadcast, watch};
+use tokio::task::JoinHandle;
 use tower::ServiceBuilder;
-use tower_http::cors::{Any, CorsLayer};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use utoipa::{IntoParams, OpenApi, ToSchema};
+
+use crate::database::{Database, SharedDatabase};
+use crate::mcp::McpServer;
+use crate::models::{
+    Achievement, AchievementStatus, ApiKey, ApiScope, ApiServerStatus, CreateGoalRequest,
+    CreateHabitEntryRequest, CreateHabitRequest, CreateKeyResultRequest, CreateProjectRequest,
+    CreateTaskRequest, DomainEvent, Goal, GoalLevel, GoalNode, Habit, HabitEntry, HabitQuery,
+    HabitStats, HeatmapDay, HeatmapQuery, InboxStatus, KeyResult, Project, Quest, QuestStatus,
+    QuickAddRequest, QuickAddResult, SearchQuery, SearchResult, Tag, Task, TaskNode, TaskQuery,
+    TrashItem, TriageTaskRequest, UpdateGoalRequest, UpdateHabitRequest, UpdateKeyResultRequest,
+    UpdateProjectRequest, UpdateTaskRequest,
+};
+use crate::services::error::ServiceError;
+use responses::{
+    AchievementStats, ApiListResponse, ApiPageResponse, ApiResponse, CharacterOverview,
+    ErrorResponse, HabitListResponse, HabitWithToday, HealthResponse, LevelProgress,
+};
 
-use crate::database::Database;
-use crate::services::{character_service, habit_service, task_service};
+use crate::services::{
+    achievement_service, api_key_service, character_service, events, goal_service, habit_service,
+    habit_stats_service, inbox_service, project_service, quest_service, quick_add, search_service,
+    settings_service, tag_service, task_service, trash_service,
+};
 
 /// Shared application state for API endpoints
 #[derive(Clone)]
 pub struct ApiState {
-    pub database: Arc<Mutex<Database>>,
+    pub database: SharedDatabase,
+    /// Number of requests handled since the server started
+    pub request_count: Arc<AtomicU64>,
+    /// Flips to `true` when the server shuts down, closing long-lived event streams
+    pub shutdown: watch::Receiver<bool>,
 }
 
-/// Starts the HTTP API server on a background thread
-/// Returns the server handle and port number
-pub async fn start_api_server(
-    database: Arc<Mutex<Database>>,
+/// Handle to a running API server, used to query its status and shut it down
+pub struct ApiServerHandle {
     port: u16,
-) -> Result<tokio::task::JoinHandle<()>, anyhow::Error> {
-    let state = ApiState { database };
+    started_at: chrono::DateTime<chrono::Utc>,
+    request_count: Arc<AtomicU64>,
+    shutdown_tx: watch::Sender<bool>,
+    task: JoinHandle<()>,
+}
 
-    let cors = CorsLayer::new()
-        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
-        .allow_headers(Any)
-        .allow_origin(Any);
+impl ApiServerHandle {
+    /// Port the server is listening on
+    pub fn port(&self) -> u16 {
+        self.port
+    }
 
-    let app = Router::new()
-        .route("/api/health", get(health_check))
-        .route("/api/tasks", get(get_tasks))
-        .route("/api/habits", get(get_habits))
+    /// Whether the server task is still running (it stops on its own only after an I/O error)
+    pub fn is_running(&self) -> bool {
+        !self.task.is_finished()
+    }
+
+    /// Snapshot of the server state for the UI
+    pub fn status(&self, auto_start: bool) -> ApiServerStatus {
+        let running = self.is_running();
+        let uptime_seconds = if running {
+            (chrono::Utc::now() - self.started_at).num_seconds()
+        } else {
+            0
+        };
+
+        ApiServerStatus {
+            running,
+            port: self.port,
+            started_at: running.then(|| self.started_at.timestamp()),
+            uptime_seconds,
+            request_count: self.request_count.load(Ordering::Relaxed),
+            auto_start,
+        }
+    }
+
+    /// Stops accepting connections and waits for in-flight requests to finish
+    pub async fn shutdown(self) -> Result<(), anyhow::Error> {
+        // Receivers are gone only if the server already stopped, which is fine
+        let _ = self.shutdown_tx.send(true);
+        self.task
+            .await
+            .map_err(|e| anyhow::anyhow!("API server task failed: {}", e))?;
+
+        println!("🛑 Local API Server on port {} stopped", self.port);
+        Ok(())
+    }
+}
+
+/// Status reported when no API server is running
+pub fn stopped_status(port: u16, auto_start: bool) -> ApiServerStatus {
+    ApiServerStatus {
+        running: false,
+        port,
+        started_at: None,
+        uptime_seconds: 0,
+        request_count: 0,
+        auto_start,
+    }
+}
+
+/// Error returned by API endpoints, mapped to an HTTP status code
+#[derive(Debug)]
+pub enum ApiError {
+    /// 401 - missing, unknown or revoked API key
+    Unauthorized(String),
+    /// 403 - the API key lacks the scope required by the endpoint
+    Forbidden(String),
+    /// 404 - the requested resource does not exist
+    NotFound(String),
+    /// 409 - the operation is not allowed in the resource's current state
+    Conflict(String),
+    /// 422 - the request (body, query or path) is malformed or failed validation
+    Unprocessable(String),
+    /// 500 - unexpected server-side failure
+    Internal(String),
+}
+
+impl ApiError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            ApiError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden(_) => StatusCode::FORBIDDEN,
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::Conflict(_) => StatusCode::CONFLICT,
+            ApiError::Unprocessable(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = self.status_code();
+        let message = match self {
+            ApiError::Unauthorized(message)
+            | ApiError::Forbidden(message)
+            | ApiError::NotFound(message)
+            | ApiError::Conflict(message)
+            | ApiError::Unprocessable(message)
+            | ApiError::Internal(message) => message,
+        };
+
+        (
+            status,
+            Json(ErrorResponse {
+                success: false,
+                error: message,
+            }),
+        )
+            .into_response()
+    }
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(err: anyhow::Error) -> Self {
+        if let Some(service_error) = err.downcast_ref::<ServiceError>() {
+            return match service_error {
+                ServiceError::NotFound(message) => ApiError::NotFound(message.clone()),
+                ServiceError::Validation(message) => ApiError::Unprocessable(message.clone()),
+                ServiceError::Conflict(message) => ApiError::Conflict(message.clone()),
+            };
+        }
+
+        if let Some(rusqlite::Error::QueryReturnedNoRows) = err.downcast_ref::<rusqlite::Error>() {
+            return ApiError::NotFound("Resource not found".to_string());
+        }
+
+        ApiError::Internal(err.to_string())
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        ApiError::Unprocessable(rejection.body_text())
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        ApiError::Unprocessable(rejection.body_text())
+    }
+}
+
+impl From<PathRejection> for ApiError {
+    fn from(rejection: PathRejection) -> Self {
+        ApiError::Unprocessable(rejection.body_text())
+    }
+}
+
+type ApiResult<T> = Result<T, ApiError>;
+
+/// Locks the shared database, mapping a poisoned lock to a 500 error
+fn lock_database(state: &ApiState) -> ApiResult<MutexGuard<'_, Database>> {
+    state
+        .database
+        .lock()
+        .map_err(|e| ApiError::Internal(format!("Database lock error: {}", e)))
+}
+
+/// Wraps a payload in the standard success envelope
+fn success<T>(data: T) -> Json<ApiResponse<T>> {
+    Json(ApiResponse::new(data))
+}
+
+/// Routes whose DELETE permanently removes data and needs the `Admin` scope
+const ADMIN_DELETE_ROUTES: [&str; 3] = [
+    "/api/trash",
+    "/api/trash/tasks/:id",
+    "/api/trash/habits/:id",
+];
+
+/// Scope an API key needs to call an endpoint
+///
+/// The MCP endpoint is always POST, so it only needs `Read` here and
+/// checks each tool's scope itself. Emptying or purging the trash cannot be
+/// undone, so it needs `Admin`.
+fn required_scope(request: &Request) -> ApiScope {
+    let method = request.method();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or(request.uri().path(), MatchedPath::as_str);
+
+    if method == Method::GET || method == Method::HEAD || route == MCP_PATH {
+        ApiScope::Read
+    } else if method == Method::DELETE && ADMIN_DELETE_ROUTES.contains(&route) {
+        ApiScope::Admin
+    } else {
+        ApiScope::Write
+    }
+}
+
+/// Middleware validating the `Authorization: Bearer <key>` header
+///
+/// GET requests need the `Read` scope, every other method needs `Write`
+/// (except `/api/mcp`, which checks the scope per tool, and the trash purges,
+/// which need `Admin`).
+/// The authenticated key is stored in the request extensions.
+async fn require_api_key(
+    State(state): State<ApiState>,
+    mut request: Request,
+    next: Next,
+) -> ApiResult<Response> {
+    let header_token = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+        .map(str::to_string);
+    // Browser EventSource cannot send headers, so the event stream also accepts `?access_token=`
+    let token = header_token
+        .or_else(|| {
+            (request.uri().path() == EVENTS_PATH)
+                .then(|| query_access_token(request.uri().query()?))
+                .flatten()
+        })
+        .ok_or_else(|| ApiError::Unauthorized("Missing bearer token".to_string()))?;
+
+    let api_key = {
+        let db = lock_database(&state)?;
+        api_key_service::authenticate(db.connection(), &token)?
+    }
+    .ok_or_else(|| ApiError::Unauthorized("Invalid or revoked API key".to_string()))?;
+
+    let required = required_scope(&request);
+    if !api_key.grants(required) {
+        return Err(ApiError::Forbidden(format!(
+            "API key '{}' lacks the {} scope",
+            api_key.name,
+            required.as_str()
+        )));
+    }
+
+    request.extensions_mut().insert(api_key);
+
+    Ok(next.run(request).await)
+}
+
+/// Extracts the `access_token` query parameter (API keys never need URL decoding)
+fn query_access_token(query: &str) -> Option<String> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.strip_prefix("access_token="))
+        .find(|token| !token.is_empty())
+        .map(str::to_string)
+}
+
+/// Builds the CORS layer from the origins saved in settings
+///
+/// Without configured origins no browser page is allowed to call the API.
+fn build_cors_layer(origins: &[String]) -> CorsLayer {
+    let origins: Vec<HeaderValue> = origins
+        .iter()
+        .filter_map(|origin| match origin.parse() {
+            Ok(value) => Some(value),
+            Err(_) => {
+                eprintln!("⚠️ Ignoring invalid CORS origin: {}", origin);
+                None
+            }
+        })
+        .collect();
+
+    CorsLayer::new()
+        .allow_methods([
+            Method::GET,
+            Method::POST,
+            Method::PUT,
+            Method::PATCH,
+            Method::DELETE,
+        ])
+        .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE])
+        .allow_origin(AllowOrigin::list(origins))
+}
+
+/// Counts every request reaching the router (including rejected ones)
+async fn count_requests(State(state): State<ApiState>, request: Request, next: Next) -> Response {
+    state.request_count.fetch_add(1, Ordering::Relaxed);
+    next.run(request).await
+}
+
+/// Builds the router with every endpoint, authentication and CORS
+fn build_router(state: ApiState, cors: CorsLayer) -> Router {
+    let protected = Router::new()
+        .route("/api/tasks", get(get_tasks).post(create_task))
+        .route("/api/tasks/quick", post(quick_add_task))
+        .route("/api/tasks/today", get(get_tasks_today))
+        .route("/api/tasks/week", get(get_tasks_week))
+        .route("/api/tasks/overdue", get(get_tasks_overdue))
+        .route("/api/tasks/unscheduled", get(get_tasks_unscheduled))
+        .route("/api/tasks/inbox", get(get_tasks_inbox))
+        .route("/api/tasks/inbox/status", get(get_inbox_status))
+        .route("/api/tasks/:id", patch(update_task).delete(delete_task))
+        .route("/api/tags", get(get_tags))
+        .route("/api/search", get(search))
+        .route("/api/tasks/:id/toggle", patch(toggle_task))
+        .route("/api/tasks/:id/tree", get(get_task_tree))
+        .route("/api/tasks/:id/triage", post(triage_task))
+        .route("/api/projects", get(get_projects).post(create_project))
+        .route(
+            "/api/projects/:id",
+            patch(update_project).delete(delete_project),
+        )
+        .route("/api/projects/:id/tasks", get(get_project_tasks))
+        .route("/api/goals", get(get_goals).post(create_goal))
+        .route("/api/goals/tree", get(get_goal_tree))
+        .route(
+            "/api/goals/:id",
+            get(get_goal).patch(update_goal).delete(delete_goal),
+        )
+        .route("/api/goals/:id/key-results", post(create_key_result))
+        .route(
+            "/api/key-results/:id",
+            patch(update_key_result).delete(delete_key_result),
+        )
+        .route("/api/habits", get(get_habits).post(create_habit))
+        .route("/api/habits/:id", patch(update_habit).delete(delete_habit))
+        .route("/api/habits/:id/entries", post(create_habit_entry))
+        .route("/api/habits/:id/stats", get(get_habit_stats))
+        .route("/api/habits/heatmap", get(get_habit_heatmap))
+        .route("/api/trash", get(get_trash).delete(empty_trash))
+        .route("/api/trash/tasks/:id", delete(purge_task))
+        .route("/api/trash/tasks/:id/restore", post(restore_task))
+        .route("/api/trash/habits/:id", delete(purge_habit))
+        .route("/api/trash/habits/:id/restore", post(restore_habit))
         .route("/api/character", get(get_character))
-        .layer(ServiceBuilder::new().layer(cors))
-        .with_state(state);
+        .route("/api/quests", get(get_quests))
+        .route("/api/quests/:id/complete", post(complete_quest))
+        .route("/api/achievements", get(get_achievements))
+        .route("/api/achievements/stats", get(get_achievement_stats))
+        .route("/api/achievements/:id/earn", post(earn_achievement))
+        .route(EVENTS_PATH, get(stream_events))
+        .route(MCP_PATH, post(mcp_endpoint))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_api_key,
+        ));
+
+    Router::new()
+        .route("/api/health", get(health_check))
+        .route("/api/openapi.json", get(openapi_spec))
+        .merge(protected)
+        .layer(
+            ServiceBuilder::new()
+                .layer(cors)
+                .layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    count_requests,
+                )),
+        )
+        .with_state(state)
+}
+
+/// Starts the HTTP API server on a background task
+/// Returns a handle used to query the server status and shut it down gracefully
+pub async fn start_api_server(
+    database: SharedDatabase,
+    port: u16,
+) -> Result<ApiServerHandle, anyhow::Error> {
+    let cors_origins = {
+        let db = database
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+        settings_service::get_cors_origins(db.connection())?
+    };
+    let cors = build_cors_layer(&cors_origins);
+
+    let request_count = Arc::new(AtomicU64::new(0));
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let state = ApiState {
+        database,
+        request_count: request_count.clone(),
+        shutdown: shutdown_rx.clone(),
+    };
+
+    let app = build_router(state, cors);
 
     let listener = tokio::net::TcpListener::bind(format!("127.0.0.1:{}", port))
         .await
         .map_err(|e| anyhow::anyhow!("Failed to bind to port {}: {}", port, e))?;
 
     println!("🌐 Local API Server started on http://127.0.0.1:{}", port);
-    println!("📋 Available endpoints:");
-    println!("  GET /api/health    - Server health check");
-    println!("  GET /api/tasks     - Get all tasks");
-    println!("  GET /api/habits    - Get all habits with today's entries");
-    println!("  GET /api/character - Get character data");
-
-    let handle = tokio::spawn(async move {
-        if let Err(e) = axum::serve(listener, app).await {
+    if cors_origins.is_empty() {
+        println!("🔒 CORS: no browser origins allowed");
+    } else {
+        println!("🔒 CORS origins: {}", cors_origins.join(", "));
+    }
+    println!(
+        "📋 Endpoints (Authorization: Bearer <API key>): http://127.0.0.1:{}/api/openapi.json",
+        port
+    );
+
+    let task = tokio::spawn(async move {
+        let mut shutdown_rx = shutdown_rx;
+        let shutdown = async move {
+            let _ = shutdown_rx.wait_for(|stopped| *stopped).await;
+        };
+
+        if let Err(e) = axum::serve(listener, app)
+            .with_graceful_shutdown(shutdown)
+            .await
+        {
             eprintln!("❌ API Server error: {}", e);
         }
     });
 
-    Ok(handle)
+    Ok(ApiServerHandle {
+        port,
+        started_at: chrono::Utc::now(),
+        request_count,
+        shutdown_tx,
+        task,
+    })
+}
+
+/// Path of the Server-Sent Events stream
+const EVENTS_PATH: &str = "/api/events";
+/// Path of the Model Context Protocol endpoint
+const MCP_PATH: &str = "/api/mcp";
+
+/// Server-Sent Events endpoint streaming domain events as they happen
+///
+/// Each SSE event is named after the domain event type and carries its JSON payload.
+/// A `lagged` event reports how many events a slow client missed.
+#[utoipa::path(
+    get,
+    path = "/api/events",
+    tag = "events",
+    params(("access_token" = Option<String>, Query, description = "API key for clients that cannot send headers (EventSource)")),
+    responses(
+        (status = 200, description = "Stream of domain events", content_type = "text/event-stream", body = DomainEvent),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn stream_events(
+    State(state): State<ApiState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let receiver = events::subscribe();
+
+    let stream = stream::unfold(
+        (receiver, state.shutdown),
+        |(mut receiver, mut shutdown)| async move {
+            let event = tokio::select! {
+                _ = shutdown.wait_for(|stopped| *stopped) => return None,
+                result = receiver.recv() => match result {
+                    Ok(event) => Event::default()
+                        .event(event.name())
+                        .json_data(&event)
+                        .unwrap_or_else(|_| Event::default().comment("serialization error")),
+                    Err(broadcast::error::RecvError::Lagged(missed)) => {
+                        Event::default().event("lagged").data(missed.to_string())
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                },
+            };
+
+            Some((Ok(event), (receiver, shutdown)))
+        },
+    );
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// OpenAPI 3 specification generated from the handlers below
+async fn openapi_spec() -> Json<utoipa::openapi::OpenApi> {
+    Json(openapi::ApiDoc::openapi())
+}
+
+/// Model Context Protocol endpoint: one JSON-RPC message per request
+///
+/// Requests get a JSON-RPC response, notifications are acknowledged with 202.
+/// Tools are limited to the scopes of the caller's API key.
+/// Not part of the OpenAPI document, since the body is defined by the MCP specification.
+async fn mcp_endpoint(
+    State(state): State<ApiState>,
+    Extension(api_key): Extension<ApiKey>,
+    body: String,
+) -> Response {
+    let server = McpServer::with_api_key(state.database.clone(), api_key);
+
+    match server.handle_text(&body) {
+        Some(response) => Json(response).into_response(),
+        None => StatusCode::ACCEPTED.into_response(),
+    }
 }
 
 /// Health check endpoint
-async fn health_check() -> Json<Value> {
-    Json(serde_json::json!({
-        "status": "ok",
-        "service": "PDRPG API",
-        "version": "1.0.0",
-        "timestamp": chrono::Utc::now().to_rfc3339()
+#[utoipa::path(
+    get,
+    path = "/api/health",
+    tag = "system",
+    responses((status = 200, description = "Server is running", body = HealthResponse))
+)]
+async fn health_check() -> Json<HealthResponse> {
+    Json(HealthResponse {
+        status: "ok".to_string(),
+        service: "PDRPG API".to_string(),
+        version: "1.0.0".to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+/// List tasks endpoint (filtered, sorted and paginated)
+#[utoipa::path(
+    get,
+    path = "/api/tasks",
+    tag = "tasks",
+    params(TaskQuery),
+    responses(
+        (status = 200, description = "One page of tasks", body = ApiPageResponse<Task>),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponse),
+        (status = 422, description = "Invalid filter, limit or cursor", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn get_tasks(
+    State(state): State<ApiState>,
+    query: Result<Query<TaskQuery>, QueryRejection>,
+) -> ApiResult<Json<ApiPageResponse<Task>>> {
+    let Query(query) = query?;
+    let db = lock_database(&state)?;
+    let page = task_service::query_tasks(db.connection(), query)?;
+
+    Ok(Json(page.into()))
+}
+
+/// Tasks for today endpoint
+#[utoipa::path(
+    get,
+    path = "/api/tasks/today",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "Tasks scheduled for or due today", body = ApiListResponse<Task>),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn get_tasks_today(State(state): State<ApiState>) -> ApiResult<Json<ApiListResponse<Task>>> {
+    let db = lock_database(&state)?;
+    let tasks = task_service::get_tasks_for_today(db.connection())?;
+
+    Ok(Json(ApiListResponse::new(tasks)))
+}
+
+/// Tasks for the current week endpoint
+#[utoipa::path(
+    get,
+    path = "/api/tasks/week",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "Tasks scheduled for or due this week (Monday to Sunday, in the configured time zone)", body = ApiListResponse<Task>),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn get_tasks_week(State(state): State<ApiState>) -> ApiResult<Json<ApiListResponse<Task>>> {
+    let db = lock_database(&state)?;
+    let tasks = task_service::get_tasks_for_week(db.connection())?;
+
+    Ok(Json(ApiListResponse::new(tasks)))
+}
+
+/// Overdue tasks endpoint
+#[utoipa::path(
+    get,
+    path = "/api/tasks/overdue",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "Open tasks whose due date has passed", body = ApiListResponse<Task>),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn get_tasks_overdue(
+    State(state): State<ApiState>,
+) -> ApiResult<Json<ApiListResponse<Task>>> {
+    let db = lock_database(&state)?;
+    let tasks = task_service::get_overdue_tasks(db.connection())?;
+
+    Ok(Json(ApiListResponse::new(tasks)))
+}
+
+/// Unscheduled tasks endpoint
+#[utoipa::path(
+    get,
+    path = "/api/tasks/unscheduled",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "Open tasks with neither a due date nor a scheduled day", body = ApiListResponse<Task>),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn get_tasks_unscheduled(
+    State(state): State<ApiState>,
+) -> ApiResult<Json<ApiListResponse<Task>>> {
+    let db = lock_database(&state)?;
+    let tasks = task_service::get_unscheduled_tasks(db.connection())?;
+
+    Ok(Json(ApiListResponse::new(tasks)))
+}
+
+/// Inbox tasks endpoint
+#[utoipa::path(
+    get,
+    path = "/api/tasks/inbox",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "Open quick-captured tasks waiting for triage, oldest first", body = ApiListResponse<Task>),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn get_tasks_inbox(State(state): State<ApiState>) -> ApiResult<Json<ApiListResponse<Task>>> {
+    let db = lock_database(&state)?;
+    let tasks = task_service::get_inbox_tasks(db.connection())?;
+
+    Ok(Json(ApiListResponse::new(tasks)))
+}
+
+/// Inbox status endpoint
+#[utoipa::path(
+    get,
+    path = "/api/tasks/inbox/status",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "Number of inbox tasks and days with an empty inbox", body = ApiResponse<InboxStatus>),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn get_inbox_status(
+    State(state): State<ApiState>,
+) -> ApiResult<Json<ApiResponse<InboxStatus>>> {
+    let db = lock_database(&state)?;
+    let status = inbox_service::get_inbox_status(db.connection())?;
+
+    Ok(success(status))
+}
+
+/// Create task endpoint
+#[utoipa::path(
+    post,
+    path = "/api/tasks",
+    tag = "tasks",
+    request_body = CreateTaskRequest,
+    responses(
+        (status = 201, description = "Task created", body = ApiResponse<Task>),
+        (status = 422, description = "Invalid body or empty title", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn create_task(
+    State(state): State<ApiState>,
+    payload: Result<Json<CreateTaskRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<ApiResponse<Task>>)> {
+    let Json(request) = payload?;
+    let db = lock_database(&state)?;
+    let task = task_service::add_task(db.connection(), request)?;
+
+    Ok((StatusCode::CREATED, success(task)))
+}
+
+/// Quick-add task endpoint (parses dates, tags, priority, project and estimate from the text)
+#[utoipa::path(
+    post,
+    path = "/api/tasks/quick",
+    tag = "tasks",
+    request_body = QuickAddRequest,
+    responses(
+        (status = 201, description = "Task created, with the fields recognized in the text", body = ApiResponse<QuickAddResult>),
+        (status = 422, description = "Invalid body or nothing left for the title", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn quick_add_task(
+    State(state): State<ApiState>,
+    payload: Result<Json<QuickAddRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<ApiResponse<QuickAddResult>>)> {
+    let Json(request) = payload?;
+    let db = lock_database(&state)?;
+    let result = quick_add::quick_add_task(db.connection(), request)?;
+
+    Ok((StatusCode::CREATED, success(result)))
+}
+
+/// Toggle task completion endpoint
+#[utoipa::path(
+    patch,
+    path = "/api/tasks/{id}/toggle",
+    tag = "tasks",
+    params(("id" = i32, Path, description = "Task ID")),
+    responses(
+        (status = 200, description = "Task with toggled status", body = ApiResponse<Task>),
+        (status = 404, description = "Task not found", body = ErrorResponse),
+        (status = 422, description = "Invalid ID", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn toggle_task(
+    State(state): State<ApiState>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<Json<ApiResponse<Task>>> {
+    let Path(task_id) = path?;
+    let db = lock_database(&state)?;
+    let task = task_service::toggle_task_status(db.connection(), task_id)?;
+
+    Ok(success(task))
+}
+
+/// Update task endpoint (fields, recurrence, project, parent and goal)
+#[utoipa::path(
+    patch,
+    path = "/api/tasks/{id}",
+    tag = "tasks",
+    params(("id" = i32, Path, description = "Task ID")),
+    request_body = UpdateTaskRequest,
+    responses(
+        (status = 200, description = "Updated task", body = ApiResponse<Task>),
+        (status = 404, description = "Task not found", body = ErrorResponse),
+        (status = 422, description = "Invalid body or failed validation", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn update_task(
+    State(state): State<ApiState>,
+    path: Result<Path<i32>, PathRejection>,
+    payload: Result<Json<UpdateTaskRequest>, JsonRejection>,
+) -> ApiResult<Json<ApiResponse<Task>>> {
+    let Path(task_id) = path?;
+    let Json(request) = payload?;
+    let db = lock_database(&state)?;
+    let task = task_service::update_task(db.connection(), task_id, request)?;
+
+    Ok(success(task))
+}
+
+/// List task tags endpoint
+#[utoipa::path(
+    get,
+    path = "/api/tags",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "Tags used by tasks, with task counts", body = ApiListResponse<Tag>)
+    ),
+    security(("api_key" = []))
+)]
+async fn get_tags(State(state): State<ApiState>) -> ApiResult<Json<ApiListResponse<Tag>>> {
+    let db = lock_database(&state)?;
+    let tags = tag_service::list_tags(db.connection())?;
+
+    Ok(Json(ApiListResponse::new(tags)))
+}
+
+/// Delete task endpoint
+#[utoipa::path(
+    delete,
+    path = "/api/tasks/{id}",
+    tag = "tasks",
+    params(("id" = i32, Path, description = "Task ID")),
+    responses(
+        (status = 204, description = "Task and its subtasks moved to the trash"),
+        (status = 404, description = "Task not found", body = ErrorResponse),
+        (status = 422, description = "Invalid ID", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn delete_task(
+    State(state): State<ApiState>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<StatusCode> {
+    let Path(task_id) = path?;
+    let db = lock_database(&state)?;
+    task_service::delete_task(db.connection(), task_id)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Task tree endpoint (task with nested subtasks and rolled-up progress)
+#[utoipa::path(
+    get,
+    path = "/api/tasks/{id}/tree",
+    tag = "tasks",
+    params(("id" = i32, Path, description = "Task ID")),
+    responses(
+        (status = 200, description = "Task with its subtasks", body = ApiResponse<TaskNode>),
+        (status = 404, description = "Task not found", body = ErrorResponse),
+        (status = 422, description = "Invalid ID", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn get_task_tree(
+    State(state): State<ApiState>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<Json<ApiResponse<TaskNode>>> {
+    let Path(task_id) = path?;
+    let db = lock_database(&state)?;
+    let tree = task_service::get_task_tree(db.connection(), task_id)?;
+
+    Ok(success(tree))
+}
+
+/// Triage task endpoint (applies all fields at once and takes the task out of the inbox)
+#[utoipa::path(
+    post,
+    path = "/api/tasks/{id}/triage",
+    tag = "tasks",
+    params(("id" = i32, Path, description = "Task ID")),
+    request_body = TriageTaskRequest,
+    responses(
+        (status = 200, description = "Triaged task", body = ApiResponse<Task>),
+        (status = 404, description = "Task not found", body = ErrorResponse),
+        (status = 422, description = "Invalid body or failed validation", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn triage_task(
+    State(state): State<ApiState>,
+    path: Result<Path<i32>, PathRejection>,
+    payload: Result<Json<TriageTaskRequest>, JsonRejection>,
+) -> ApiResult<Json<ApiResponse<Task>>> {
+    let Path(task_id) = path?;
+    let Json(request) = payload?;
+    let db = lock_database(&state)?;
+    let task = inbox_service::triage_task(db.connection(), task_id, request)?;
+
+    Ok(success(task))
+}
+
+/// List projects endpoint
+#[utoipa::path(
+    get,
+    path = "/api/projects",
+    tag = "projects",
+    responses(
+        (status = 200, description = "Projects with task counts and progress", body = ApiListResponse<Project>)
+    ),
+    security(("api_key" = []))
+)]
+async fn get_projects(State(state): State<ApiState>) -> ApiResult<Json<ApiListResponse<Project>>> {
+    let db = lock_database(&state)?;
+    let projects = project_service::list_projects(db.connection())?;
+
+    Ok(Json(ApiListResponse::new(projects)))
+}
+
+/// Create project endpoint
+#[utoipa::path(
+    post,
+    path = "/api/projects",
+    tag = "projects",
+    request_body = CreateProjectRequest,
+    responses(
+        (status = 201, description = "Project created", body = ApiResponse<Project>),
+        (status = 409, description = "A project with this name already exists", body = ErrorResponse),
+        (status = 422, description = "Invalid name or color", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn create_project(
+    State(state): State<ApiState>,
+    payload: Result<Json<CreateProjectRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<ApiResponse<Project>>)> {
+    let Json(request) = payload?;
+    let db = lock_database(&state)?;
+    let project = project_service::create_project(db.connection(), request)?;
+
+    Ok((StatusCode::CREATED, success(project)))
+}
+
+/// Update project endpoint
+#[utoipa::path(
+    patch,
+    path = "/api/projects/{id}",
+    tag = "projects",
+    params(("id" = i32, Path, description = "Project ID")),
+    request_body = UpdateProjectRequest,
+    responses(
+        (status = 200, description = "Updated project", body = ApiResponse<Project>),
+        (status = 404, description = "Project not found", body = ErrorResponse),
+        (status = 409, description = "A project with this name already exists", body = ErrorResponse),
+        (status = 422, description = "Invalid name or color", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn update_project(
+    State(state): State<ApiState>,
+    path: Result<Path<i32>, PathRejection>,
+    payload: Result<Json<UpdateProjectRequest>, JsonRejection>,
+) -> ApiResult<Json<ApiResponse<Project>>> {
+    let Path(project_id) = path?;
+    let Json(request) = payload?;
+    let db = lock_database(&state)?;
+    let project = project_service::update_project(db.connection(), project_id, request)?;
+
+    Ok(success(project))
+}
+
+/// Delete project endpoint (tasks stay, without a project)
+#[utoipa::path(
+    delete,
+    path = "/api/projects/{id}",
+    tag = "projects",
+    params(("id" = i32, Path, description = "Project ID")),
+    responses(
+        (status = 204, description = "Project deleted"),
+        (status = 404, description = "Project not found", body = ErrorResponse),
+        (status = 422, description = "Invalid ID", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn delete_project(
+    State(state): State<ApiState>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<StatusCode> {
+    let Path(project_id) = path?;
+    let db = lock_database(&state)?;
+    project_service::delete_project(db.connection(), project_id)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Project task tree endpoint
+#[utoipa::path(
+    get,
+    path = "/api/projects/{id}/tasks",
+    tag = "projects",
+    params(("id" = i32, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Top-level tasks of the project with nested subtasks", body = ApiListResponse<TaskNode>),
+        (status = 404, description = "Project not found", body = ErrorResponse),
+        (status = 422, description = "Invalid ID", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn get_project_tasks(
+    State(state): State<ApiState>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<Json<ApiListResponse<TaskNode>>> {
+    let Path(project_id) = path?;
+    let db = lock_database(&state)?;
+    let tree = task_service::get_project_tree(db.connection(), project_id)?;
+
+    Ok(Json(ApiListResponse::new(tree)))
+}
+
+/// Full-text search endpoint
+#[utoipa::path(
+    get,
+    path = "/api/search",
+    tag = "search",
+    params(SearchQuery),
+    responses(
+        (status = 200, description = "Matches ranked by relevance, with highlighted titles and snippets", body = ApiListResponse<SearchResult>),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponse),
+        (status = 422, description = "Missing query or invalid limit", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn search(
+    State(state): State<ApiState>,
+    query: Result<Query<SearchQuery>, QueryRejection>,
+) -> ApiResult<Json<ApiListResponse<SearchResult>>> {
+    let Query(query) = query?;
+    let db = lock_database(&state)?;
+    let results = search_service::search(db.connection(), query)?;
+
+    Ok(Json(ApiListResponse::new(results)))
+}
+
+/// Query parameters of `GET /api/goals`
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct GoalQuery {
+    /// Only goals of this level
+    level: Option<GoalLevel>,
+}
+
+/// List goals endpoint
+#[utoipa::path(
+    get,
+    path = "/api/goals",
+    tag = "goals",
+    params(GoalQuery),
+    responses(
+        (status = 200, description = "Goals with key results and progress, nearest deadline first", body = ApiListResponse<Goal>),
+        (status = 422, description = "Invalid level", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn get_goals(
+    State(state): State<ApiState>,
+    query: Result<Query<GoalQuery>, QueryRejection>,
+) -> ApiResult<Json<ApiListResponse<Goal>>> {
+    let Query(query) = query?;
+    let db = lock_database(&state)?;
+    let goals = goal_service::list_goals(db.connection(), query.level)?;
+
+    Ok(Json(ApiListResponse::new(goals)))
+}
+
+/// Create goal endpoint
+#[utoipa::path(
+    post,
+    path = "/api/goals",
+    tag = "goals",
+    request_body = CreateGoalRequest,
+    responses(
+        (status = 201, description = "Goal created", body = ApiResponse<Goal>),
+        (status = 422, description = "Invalid title or parent goal", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn create_goal(
+    State(state): State<ApiState>,
+    payload: Result<Json<CreateGoalRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<ApiResponse<Goal>>)> {
+    let Json(request) = payload?;
+    let db = lock_database(&state)?;
+    let goal = goal_service::create_goal(db.connection(), request)?;
+
+    Ok((StatusCode::CREATED, success(goal)))
+}
+
+/// Goal tree endpoint
+#[utoipa::path(
+    get,
+    path = "/api/goals/tree",
+    tag = "goals",
+    responses(
+        (status = 200, description = "Top-level goals with nested subgoals", body = ApiListResponse<GoalNode>)
+    ),
+    security(("api_key" = []))
+)]
+async fn get_goal_tree(
+    State(state): State<ApiState>,
+) -> ApiResult<Json<ApiListResponse<GoalNode>>> {
+    let db = lock_database(&state)?;
+    let tree = goal_service::get_goal_tree(db.connection())?;
+
+    Ok(Json(ApiListResponse::new(tree)))
+}
+
+/// Get goal endpoint
+#[utoipa::path(
+    get,
+    path = "/api/goals/{id}",
+    tag = "goals",
+    params(("id" = i32, Path, description = "Goal ID")),
+    responses(
+        (status = 200, description = "Goal with key results and progress", body = ApiResponse<Goal>),
+        (status = 404, description = "Goal not found", body = ErrorResponse),
+        (status = 422, description = "Invalid ID", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn get_goal(
+    State(state): State<ApiState>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<Json<ApiResponse<Goal>>> {
+    let Path(goal_id) = path?;
+    let db = lock_database(&state)?;
+    let goal = goal_service::get_goal(db.connection(), goal_id)?;
+
+    Ok(success(goal))
+}
+
+/// Update goal endpoint
+#[utoipa::path(
+    patch,
+    path = "/api/goals/{id}",
+    tag = "goals",
+    params(("id" = i32, Path, description = "Goal ID")),
+    request_body = UpdateGoalRequest,
+    responses(
+        (status = 200, description = "Updated goal", body = ApiResponse<Goal>),
+        (status = 404, description = "Goal not found", body = ErrorResponse),
+        (status = 422, description = "Invalid title, level or parent goal", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn update_goal(
+    State(state): State<ApiState>,
+    path: Result<Path<i32>, PathRejection>,
+    payload: Result<Json<UpdateGoalRequest>, JsonRejection>,
+) -> ApiResult<Json<ApiResponse<Goal>>> {
+    let Path(goal_id) = path?;
+    let Json(request) = payload?;
+    let db = lock_database(&state)?;
+    let goal = goal_service::update_goal(db.connection(), goal_id, request)?;
+
+    Ok(success(goal))
+}
+
+/// Delete goal endpoint (subgoals are deleted too, tasks stay without a goal)
+#[utoipa::path(
+    delete,
+    path = "/api/goals/{id}",
+    tag = "goals",
+    params(("id" = i32, Path, description = "Goal ID")),
+    responses(
+        (status = 204, description = "Goal deleted"),
+        (status = 404, description = "Goal not found", body = ErrorResponse),
+        (status = 422, description = "Invalid ID", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn delete_goal(
+    State(state): State<ApiState>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<StatusCode> {
+    let Path(goal_id) = path?;
+    let db = lock_database(&state)?;
+    goal_service::delete_goal(db.connection(), goal_id)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Add key result endpoint
+#[utoipa::path(
+    post,
+    path = "/api/goals/{id}/key-results",
+    tag = "goals",
+    params(("id" = i32, Path, description = "Goal ID")),
+    request_body = CreateKeyResultRequest,
+    responses(
+        (status = 201, description = "Key result created", body = ApiResponse<KeyResult>),
+        (status = 404, description = "Goal not found", body = ErrorResponse),
+        (status = 422, description = "Invalid title or target", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn create_key_result(
+    State(state): State<ApiState>,
+    path: Result<Path<i32>, PathRejection>,
+    payload: Result<Json<CreateKeyResultRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<ApiResponse<KeyResult>>)> {
+    let Path(goal_id) = path?;
+    let Json(request) = payload?;
+    let db = lock_database(&state)?;
+    let key_result = goal_service::add_key_result(db.connection(), goal_id, request)?;
+
+    Ok((StatusCode::CREATED, success(key_result)))
+}
+
+/// Update key result endpoint (reaching 100% completes the goal)
+#[utoipa::path(
+    patch,
+    path = "/api/key-results/{id}",
+    tag = "goals",
+    params(("id" = i32, Path, description = "Key result ID")),
+    request_body = UpdateKeyResultRequest,
+    responses(
+        (status = 200, description = "Updated key result", body = ApiResponse<KeyResult>),
+        (status = 404, description = "Key result not found", body = ErrorResponse),
+        (status = 422, description = "Invalid title or target", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn update_key_result(
+    State(state): State<ApiState>,
+    path: Result<Path<i32>, PathRejection>,
+    payload: Result<Json<UpdateKeyResultRequest>, JsonRejection>,
+) -> ApiResult<Json<ApiResponse<KeyResult>>> {
+    let Path(key_result_id) = path?;
+    let Json(request) = payload?;
+    let db = lock_database(&state)?;
+    let key_result = goal_service::update_key_result(db.connection(), key_result_id, request)?;
+
+    Ok(success(key_result))
+}
+
+/// Delete key result endpoint
+#[utoipa::path(
+    delete,
+    path = "/api/key-results/{id}",
+    tag = "goals",
+    params(("id" = i32, Path, description = "Key result ID")),
+    responses(
+        (status = 204, description = "Key result deleted"),
+        (status = 404, description = "Key result not found", body = ErrorResponse),
+        (status = 422, description = "Invalid ID", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn delete_key_result(
+    State(state): State<ApiState>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<StatusCode> {
+    let Path(key_result_id) = path?;
+    let db = lock_database(&state)?;
+    goal_service::delete_key_result(db.connection(), key_result_id)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// List habits with today's entries endpoint (filtered, sorted and paginated)
+#[utoipa::path(
+    get,
+    path = "/api/habits",
+    tag = "habits",
+    params(HabitQuery),
+    responses(
+        (status = 200, description = "One page of habits with today's entries", body = HabitListResponse),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponse),
+        (status = 422, description = "Invalid filter, limit or cursor", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn get_habits(
+    State(state): State<ApiState>,
+    query: Result<Query<HabitQuery>, QueryRejection>,
+) -> ApiResult<Json<HabitListResponse>> {
+    let Query(query) = query?;
+    let db = lock_database(&state)?;
+    let today_date = settings_service::today(db.connection())?;
+    let today = today_date.format("%Y-%m-%d").to_string();
+
+    let page = habit_service::query_habits(db.connection(), query)?;
+    let today_entries =
+        habit_service::get_habit_entries_for_date(db.connection(), &today).unwrap_or_default();
+
+    // Get today's entries for each habit
+    let mut habits_with_entries = Vec::new();
+
+    for habit in page.items {
+        let entry = today_entries
+            .iter()
+            .find(|e| e.habit_id == habit.id)
+            .cloned();
+        let (due_today, today_completed) =
+            habit_service::schedule_status(db.connection(), &habit, today_date)?;
+
+        habits_with_entries.push(HabitWithToday {
+            habit,
+            today_entry: entry,
+            due_today,
+            today_completed,
+        });
+    }
+
+    Ok(Json(HabitListResponse {
+        success: true,
+        count: habits_with_entries.len(),
+        data: habits_with_entries,
+        next_cursor: page.next_cursor,
+        date: today,
     }))
 }
 
-/// Get all tasks endpoint
-async fn get_tasks(State(state): State<ApiState>) -> Json<Value> {
-    match state.database.lock() {
-        Ok(db) => match task_service::get_all_tasks(db.connection()) {
-            Ok(tasks) => Json(serde_json::json!({
-                "success": true,
-                "data": tasks,
-                "count": tasks.len()
-            })),
-            Err(e) => Json(serde_json::json!({
-                "success": false,
-                "error": format!("Failed to get tasks: {}", e)
-            })),
-        },
-        Err(e) => Json(serde_json::json!({
-            "success": false,
-            "error": format!("Database lock error: {}", e)
-        })),
-    }
-}
-
-/// Get all habits with today's entries endpoint
-async fn get_habits(State(state): State<ApiState>) -> Json<Value> {
-    match state.database.lock() {
-        Ok(db) => {
-            let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
-
-            match habit_service::get_all_habits(db.connection()) {
-                Ok(habits) => {
-                    // Get today's entries for each habit
-                    let mut habits_with_entries = Vec::new();
-
-                    for habit in habits {
-                        let entry =
-                            habit_service::get_habit_entries_for_date(db.connection(), &today)
-                                .unwrap_or_default()
-                                .into_iter()
-                                .find(|e| e.habit_id == habit.id);
-
-                        habits_with_entries.push(serde_json::json!({
-                            "habit": habit,
-                            "today_entry": entry,
-                            "today_completed": entry.as_ref()
-                                .map(|e| match habit.habit_type {
-                                    crate::models::HabitType::Boolean => e.completed,
-                                    crate::models::HabitType::Counter => {
-                                        if let Some(target) = habit.target_value {
-                                            e.value >= target
-                                        } else {
-                                            e.value > 0
-                                        }
-                                    }
-                                })
-                                .unwrap_or(false)
-                        }));
-                    }
+/// Create habit endpoint
+#[utoipa::path(
+    post,
+    path = "/api/habits",
+    tag = "habits",
+    request_body = CreateHabitRequest,
+    responses(
+        (status = 201, description = "Habit created", body = ApiResponse<Habit>),
+        (status = 422, description = "Invalid body or failed validation", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn create_habit(
+    State(state): State<ApiState>,
+    payload: Result<Json<CreateHabitRequest>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<ApiResponse<Habit>>)> {
+    let Json(request) = payload?;
+    let db = lock_database(&state)?;
+    let habit = habit_service::add_habit(db.connection(), request)?;
 
-                    Json(serde_json::json!({
-                        "success": true,
-                        "data": habits_with_entries,
-                        "count": habits_with_entries.len(),
-                        "date": today
-                    }))
-                }
-                Err(e) => Json(serde_json::json!({
-                    "success": false,
-                    "error": format!("Failed to get habits: {}", e)
-                })),
-            }
+    Ok((StatusCode::CREATED, success(habit)))
+}
+
+/// Update habit endpoint
+#[utoipa::path(
+    patch,
+    path = "/api/habits/{id}",
+    tag = "habits",
+    params(("id" = i32, Path, description = "Habit ID")),
+    request_body = UpdateHabitRequest,
+    responses(
+        (status = 200, description = "Updated habit", body = ApiResponse<Habit>),
+        (status = 404, description = "Habit not found", body = ErrorResponse),
+        (status = 422, description = "Invalid body or failed validation", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn update_habit(
+    State(state): State<ApiState>,
+    path: Result<Path<i32>, PathRejection>,
+    payload: Result<Json<UpdateHabitRequest>, JsonRejection>,
+) -> ApiResult<Json<ApiResponse<Habit>>> {
+    let Path(habit_id) = path?;
+    let Json(request) = payload?;
+    let db = lock_database(&state)?;
+    let habit = habit_service::update_habit(db.connection(), habit_id, request)?;
+
+    Ok(success(habit))
+}
+
+/// Body of `POST /api/habits/:id/entries` - the habit ID comes from the path
+#[derive(Debug, Deserialize, ToSchema)]
+struct HabitEntryPayload {
+    /// Entry date (YYYY-MM-DD), defaults to today
+    date: Option<String>,
+    completed: Option<bool>,
+    value: Option<i32>,
+}
+
+/// Log habit entry endpoint
+#[utoipa::path(
+    post,
+    path = "/api/habits/{id}/entries",
+    tag = "habits",
+    params(("id" = i32, Path, description = "Habit ID")),
+    request_body = HabitEntryPayload,
+    responses(
+        (status = 201, description = "Entry saved", body = ApiResponse<HabitEntry>),
+        (status = 404, description = "Habit not found", body = ErrorResponse),
+        (status = 422, description = "Invalid body or date", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn create_habit_entry(
+    State(state): State<ApiState>,
+    path: Result<Path<i32>, PathRejection>,
+    payload: Result<Json<HabitEntryPayload>, JsonRejection>,
+) -> ApiResult<(StatusCode, Json<ApiResponse<HabitEntry>>)> {
+    let Path(habit_id) = path?;
+    let Json(payload) = payload?;
+    let db = lock_database(&state)?;
+    let date = match payload.date {
+        Some(date) => date,
+        None => settings_service::today(db.connection())?
+            .format("%Y-%m-%d")
+            .to_string(),
+    };
+    let request = CreateHabitEntryRequest {
+        habit_id,
+        date,
+        completed: payload.completed,
+        value: payload.value,
+    };
+
+    let entry = habit_service::add_habit_entry(db.connection(), request)?;
+
+    Ok((StatusCode::CREATED, success(entry)))
+}
+
+/// Habit statistics endpoint
+#[utoipa::path(
+    get,
+    path = "/api/habits/{id}/stats",
+    tag = "habits",
+    params(("id" = i32, Path, description = "Habit ID")),
+    responses(
+        (status = 200, description = "Completion rates, weekday breakdown and streak history", body = ApiResponse<HabitStats>),
+        (status = 404, description = "Habit not found", body = ErrorResponse),
+        (status = 422, description = "Invalid ID", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn get_habit_stats(
+    State(state): State<ApiState>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<Json<ApiResponse<HabitStats>>> {
+    let Path(habit_id) = path?;
+    let db = lock_database(&state)?;
+    let stats = habit_stats_service::get_habit_stats(db.connection(), habit_id)?;
+
+    Ok(success(stats))
+}
+
+/// Habit calendar heatmap endpoint (one habit or all habits combined)
+#[utoipa::path(
+    get,
+    path = "/api/habits/heatmap",
+    tag = "habits",
+    params(HeatmapQuery),
+    responses(
+        (status = 200, description = "Every day of the range with completion intensity, oldest first", body = ApiListResponse<HeatmapDay>),
+        (status = 404, description = "Habit not found", body = ErrorResponse),
+        (status = 422, description = "Invalid dates or range longer than 366 days", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn get_habit_heatmap(
+    State(state): State<ApiState>,
+    query: Result<Query<HeatmapQuery>, QueryRejection>,
+) -> ApiResult<Json<ApiListResponse<HeatmapDay>>> {
+    let Query(query) = query?;
+    let db = lock_database(&state)?;
+    let days = habit_stats_service::get_heatmap(db.connection(), query)?;
+
+    Ok(Json(ApiListResponse::new(days)))
+}
+
+/// Delete habit endpoint (moves the habit to the trash)
+#[utoipa::path(
+    delete,
+    path = "/api/habits/{id}",
+    tag = "habits",
+    params(("id" = i32, Path, description = "Habit ID")),
+    responses(
+        (status = 204, description = "Habit moved to the trash, its entries are kept"),
+        (status = 404, description = "Habit not found", body = ErrorResponse),
+        (status = 422, description = "Invalid ID", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn delete_habit(
+    State(state): State<ApiState>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<StatusCode> {
+    let Path(habit_id) = path?;
+    let db = lock_database(&state)?;
+    habit_service::delete_habit(db.connection(), habit_id)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Trash contents endpoint
+#[utoipa::path(
+    get,
+    path = "/api/trash",
+    tag = "trash",
+    responses(
+        (status = 200, description = "Deleted tasks and habits, most recently deleted first", body = ApiListResponse<TrashItem>),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn get_trash(State(state): State<ApiState>) -> ApiResult<Json<ApiListResponse<TrashItem>>> {
+    let db = lock_database(&state)?;
+    let items = trash_service::list_trash(db.connection())?;
+
+    Ok(Json(ApiListResponse::new(items)))
+}
+
+/// Empty trash endpoint (needs the Admin scope)
+#[utoipa::path(
+    delete,
+    path = "/api/trash",
+    tag = "trash",
+    responses(
+        (status = 204, description = "Trash emptied"),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponse),
+        (status = 403, description = "API key lacks the Admin scope", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn empty_trash(State(state): State<ApiState>) -> ApiResult<StatusCode> {
+    let db = lock_database(&state)?;
+    trash_service::empty_trash(db.connection())?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Restore task endpoint
+#[utoipa::path(
+    post,
+    path = "/api/trash/tasks/{id}/restore",
+    tag = "trash",
+    params(("id" = i32, Path, description = "Task ID")),
+    responses(
+        (status = 200, description = "Restored task (with the subtasks deleted together with it)", body = ApiResponse<Task>),
+        (status = 404, description = "Task not in the trash", body = ErrorResponse),
+        (status = 409, description = "The parent task is still in the trash", body = ErrorResponse),
+        (status = 422, description = "Invalid ID", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn restore_task(
+    State(state): State<ApiState>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<Json<ApiResponse<Task>>> {
+    let Path(task_id) = path?;
+    let db = lock_database(&state)?;
+    let task = trash_service::restore_task(db.connection(), task_id)?;
+
+    Ok(success(task))
+}
+
+/// Permanently delete task endpoint (needs the Admin scope)
+#[utoipa::path(
+    delete,
+    path = "/api/trash/tasks/{id}",
+    tag = "trash",
+    params(("id" = i32, Path, description = "Task ID")),
+    responses(
+        (status = 204, description = "Task and its subtasks permanently deleted"),
+        (status = 403, description = "API key lacks the Admin scope", body = ErrorResponse),
+        (status = 404, description = "Task not in the trash", body = ErrorResponse),
+        (status = 422, description = "Invalid ID", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn purge_task(
+    State(state): State<ApiState>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<StatusCode> {
+    let Path(task_id) = path?;
+    let db = lock_database(&state)?;
+    trash_service::purge_task(db.connection(), task_id)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Restore habit endpoint
+#[utoipa::path(
+    post,
+    path = "/api/trash/habits/{id}/restore",
+    tag = "trash",
+    params(("id" = i32, Path, description = "Habit ID")),
+    responses(
+        (status = 200, description = "Restored habit with a recalculated streak", body = ApiResponse<Habit>),
+        (status = 404, description = "Habit not in the trash", body = ErrorResponse),
+        (status = 422, description = "Invalid ID", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn restore_habit(
+    State(state): State<ApiState>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<Json<ApiResponse<Habit>>> {
+    let Path(habit_id) = path?;
+    let db = lock_database(&state)?;
+    let habit = trash_service::restore_habit(db.connection(), habit_id)?;
+
+    Ok(success(habit))
+}
+
+/// Permanently delete habit endpoint (needs the Admin scope)
+#[utoipa::path(
+    delete,
+    path = "/api/trash/habits/{id}",
+    tag = "trash",
+    params(("id" = i32, Path, description = "Habit ID")),
+    responses(
+        (status = 204, description = "Habit, its entries and quests permanently deleted"),
+        (status = 403, description = "API key lacks the Admin scope", body = ErrorResponse),
+        (status = 404, description = "Habit not in the trash", body = ErrorResponse),
+        (status = 422, description = "Invalid ID", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn purge_habit(
+    State(state): State<ApiState>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<StatusCode> {
+    let Path(habit_id) = path?;
+    let db = lock_database(&state)?;
+    trash_service::purge_habit(db.connection(), habit_id)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Get character data endpoint
+#[utoipa::path(
+    get,
+    path = "/api/character",
+    tag = "character",
+    responses(
+        (status = 200, description = "Character with level progress", body = ApiResponse<CharacterOverview>),
+        (status = 404, description = "No character created yet", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn get_character(
+    State(state): State<ApiState>,
+) -> ApiResult<Json<ApiResponse<CharacterOverview>>> {
+    let db = lock_database(&state)?;
+    let character = character_service::get_character(db.connection())?;
+
+    // Calculate level progress
+    let current_level_exp = (character.level as f64 - 1.0).powi(2) * 100.0;
+    let next_level_exp = (character.level as f64).powi(2) * 100.0;
+    let progress = if next_level_exp > current_level_exp {
+        ((character.experience as f64 - current_level_exp) / (next_level_exp - current_level_exp)
+            * 100.0)
+            .min(100.0)
+            .max(0.0)
+    } else {
+        100.0
+    };
+
+    let level_progress = LevelProgress {
+        current_level_exp: current_level_exp as i64,
+        next_level_exp: next_level_exp as i64,
+        progress_percentage: progress,
+        exp_to_next_level: (next_level_exp as i64) - character.experience,
+    };
+
+    Ok(success(CharacterOverview {
+        character,
+        level_progress,
+    }))
+}
+
+/// Query parameters of `GET /api/quests`
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct QuestQuery {
+    /// Week in YYYY-WW format, defaults to the current week
+    week: Option<String>,
+    /// Active, Completed or Expired
+    status: Option<String>,
+}
+
+/// Checks that a week string has the YYYY-WW format used by quests
+fn is_valid_week(week: &str) -> bool {
+    match week.split_once('-') {
+        Some((year, number)) => {
+            year.len() == 4
+                && year.chars().all(|c| c.is_ascii_digit())
+                && number.len() == 2
+                && matches!(number.parse::<u32>(), Ok(1..=53))
         }
-        Err(e) => Json(serde_json::json!({
-            "success": false,
-            "error": format!("Database lock error: {}", e)
-        })),
+        None => false,
     }
 }
 
-/// Get character data endpoint
-async fn get_character(State(state): State<ApiState>) -> Json<Value> {
-    match state.database.lock() {
-        Ok(db) => match character_service::get_character(db.connection()) {
-            Ok(character) => {
-                // Calculate level progress
-                let current_level_exp = (character.level as f64 - 1.0).powi(2) * 100.0;
-                let next_level_exp = (character.level as f64).powi(2) * 100.0;
-                let progress = if next_level_exp > current_level_exp {
-                    ((character.experience as f64 - current_level_exp)
-                        / (next_level_exp - current_level_exp)
-                        * 100.0)
-                        .min(100.0)
-                        .max(0.0)
-                } else {
-                    100.0
-                };
-
-                Json(serde_json::json!({
-                    "success": true,
-                    "data": {
-                        "character": character,
-                        "level_progress": {
-                            "current_level_exp": current_level_exp as i64,
-                            "next_level_exp": next_level_exp as i64,
-                            "progress_percentage": progress,
-                            "exp_to_next_level": (next_level_exp as i64) - character.experience
-                        }
-                    }
-                }))
-            }
-            Err(e) => Json(serde_json::json!({
-                "success": false,
-                "error": format!("Failed to get character: {}", e)
-            })),
-        },
-        Err(e) => Json(serde_json::json!({
-            "success": false,
-            "error": format!("Database lock error: {}", e)
-        })),
+/// Parses a quest status from a query string value
+fn parse_quest_status(status: &str) -> ApiResult<QuestStatus> {
+    match status {
+        "Active" => Ok(QuestStatus::Active),
+        "Completed" => Ok(QuestStatus::Completed),
+        "Expired" => Ok(QuestStatus::Expired),
+        _ => Err(ApiError::Unprocessable(format!(
+            "Invalid quest status '{}' (expected Active, Completed or Expired)",
+            status
+        ))),
+    }
+}
+
+/// Get quests for a week endpoint
+#[utoipa::path(
+    get,
+    path = "/api/quests",
+    tag = "quests",
+    params(QuestQuery),
+    responses(
+        (status = 200, description = "Quests of the week", body = ApiListResponse<Quest>),
+        (status = 422, description = "Invalid week or status", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn get_quests(
+    State(state): State<ApiState>,
+    query: Result<Query<QuestQuery>, QueryRejection>,
+) -> ApiResult<Json<ApiListResponse<Quest>>> {
+    let Query(query) = query?;
+    if let Some(week) = &query.week {
+        if !is_valid_week(week) {
+            return Err(ApiError::Unprocessable(format!(
+                "Invalid week '{}' (expected YYYY-WW)",
+                week
+            )));
+        }
+    }
+
+    let status = query
+        .status
+        .as_deref()
+        .map(parse_quest_status)
+        .transpose()?;
+
+    let db = lock_database(&state)?;
+    let quests: Vec<_> = quest_service::get_quests_for_week(db.connection(), query.week)?
+        .into_iter()
+        .filter(|quest| status.is_none() || status.as_ref() == Some(&quest.status))
+        .collect();
+
+    Ok(Json(ApiListResponse::new(quests)))
+}
+
+/// Complete quest endpoint
+#[utoipa::path(
+    post,
+    path = "/api/quests/{id}/complete",
+    tag = "quests",
+    params(("id" = i32, Path, description = "Quest ID")),
+    responses(
+        (status = 200, description = "Completed quest", body = ApiResponse<Quest>),
+        (status = 404, description = "Quest not found", body = ErrorResponse),
+        (status = 409, description = "Quest is not active", body = ErrorResponse),
+        (status = 422, description = "Invalid ID", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn complete_quest(
+    State(state): State<ApiState>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<Json<ApiResponse<Quest>>> {
+    let Path(quest_id) = path?;
+    let db = lock_database(&state)?;
+    let quest = quest_service::complete_quest(db.connection(), quest_id)?;
+
+    Ok(success(quest))
+}
+
+/// Query parameters of `GET /api/achievements`
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct AchievementQuery {
+    /// Locked, Available or Earned
+    status: Option<String>,
+}
+
+/// Get achievements endpoint
+#[utoipa::path(
+    get,
+    path = "/api/achievements",
+    tag = "achievements",
+    params(AchievementQuery),
+    responses(
+        (status = 200, description = "Achievements", body = ApiListResponse<Achievement>),
+        (status = 422, description = "Invalid status", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn get_achievements(
+    State(state): State<ApiState>,
+    query: Result<Query<AchievementQuery>, QueryRejection>,
+) -> ApiResult<Json<ApiListResponse<Achievement>>> {
+    let Query(query) = query?;
+    let status = match query.status.as_deref() {
+        None => None,
+        Some("Locked") => Some(AchievementStatus::Locked),
+        Some("Available") => Some(AchievementStatus::Available),
+        Some("Earned") => Some(AchievementStatus::Earned),
+        Some(other) => {
+            return Err(ApiError::Unprocessable(format!(
+                "Invalid achievement status '{}' (expected Locked, Available or Earned)",
+                other
+            )))
+        }
+    };
+
+    let db = lock_database(&state)?;
+    let achievements = match status {
+        Some(status) => achievement_service::get_achievements_by_status(db.connection(), status)?,
+        None => achievement_service::get_all_achievements(db.connection())?,
+    };
+
+    Ok(Json(ApiListResponse::new(achievements)))
+}
+
+/// Get achievement statistics endpoint
+#[utoipa::path(
+    get,
+    path = "/api/achievements/stats",
+    tag = "achievements",
+    responses(
+        (status = 200, description = "Achievement counts", body = ApiResponse<AchievementStats>),
+        (status = 401, description = "Missing or invalid API key", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn get_achievement_stats(
+    State(state): State<ApiState>,
+) -> ApiResult<Json<ApiResponse<AchievementStats>>> {
+    let db = lock_database(&state)?;
+    let (earned, available, locked) = achievement_service::get_achievement_stats(db.connection())?;
+
+    Ok(success(AchievementStats {
+        earned,
+        available,
+        locked,
+        total: earned + available + locked,
+    }))
+}
+
+/// Earn achievement endpoint
+#[utoipa::path(
+    post,
+    path = "/api/achievements/{id}/earn",
+    tag = "achievements",
+    params(("id" = i32, Path, description = "Achievement ID")),
+    responses(
+        (status = 200, description = "Earned achievement", body = ApiResponse<Achievement>),
+        (status = 404, description = "Achievement not found", body = ErrorResponse),
+        (status = 409, description = "Achievement is not available", body = ErrorResponse),
+        (status = 422, description = "Invalid ID", body = ErrorResponse)
+    ),
+    security(("api_key" = []))
+)]
+async fn earn_achievement(
+    State(state): State<ApiState>,
+    path: Result<Path<i32>, PathRejection>,
+) -> ApiResult<Json<ApiResponse<Achievement>>> {
+    let Path(achievement_id) = path?;
+    let db = lock_database(&state)?;
+    let achievement = achievement_service::earn_achievement(db.connection(), achievement_id)?;
+
+    Ok(success(achievement))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CreateApiKeyRequest;
+    use axum::body::Body;
+    use std::sync::Mutex;
+    use tower::Service;
+
+    async fn status(app: &Router, method: Method, uri: &str, token: &str) -> StatusCode {
+        let request = axum::http::Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(header::AUTHORIZATION, format!("Bearer {}", token))
+            .body(Body::empty())
+            .unwrap();
+
+        // The router is always ready, so poll_ready can be skipped
+        app.clone().call(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_trash_purges_require_admin_scope() {
+        let db = Database::new(":memory:".into()).unwrap();
+        let create_key = |name: &str, scope: ApiScope| {
+            let request = CreateApiKeyRequest {
+                name: name.to_string(),
+                scopes: vec![scope],
+            };
+            api_key_service::create_api_key(db.connection(), request)
+                .unwrap()
+                .token
+        };
+        let write = create_key("Skrypt", ApiScope::Write);
+        let admin = create_key("Administrator", ApiScope::Admin);
+
+        let (_shutdown_tx, shutdown) = watch::channel(false);
+        let state = ApiState {
+            database: Arc::new(Mutex::new(db)),
+            request_count: Arc::default(),
+            shutdown,
+        };
+        let app = build_router(state, build_cors_layer(&[]));
+
+        for uri in ["/api/trash", "/api/trash/tasks/1", "/api/trash/habits/1"] {
+            assert_eq!(
+                status(&app, Method::DELETE, uri, &write).await,
+                StatusCode::FORBIDDEN,
+                "{}",
+                uri
+            );
+        }
+        // Listing and restoring the trash still only need Write
+        assert_eq!(
+            status(&app, Method::GET, "/api/trash", &write).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(&app, Method::POST, "/api/trash/tasks/1/restore", &write).await,
+            StatusCode::NOT_FOUND
+        );
+
+        assert_eq!(
+            status(&app, Method::DELETE, "/api/trash", &admin).await,
+            StatusCode::NO_CONTENT
+        );
+        assert_eq!(
+            status(&app, Method::DELETE, "/api/trash/tasks/1", &admin).await,
+            StatusCode::NOT_FOUND
+        );
     }
 }
@@ -0,0 +1,125 @@
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+
+use super::*;
+
+/// OpenAPI document of the local API, generated from the handler annotations
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "PDRPG Local API",
+        version = "1.0.0",
+        description = "Local HTTP API of the PDRPG desktop app"
+    ),
+    servers((url = "http://localhost:3000", description = "Default local server")),
+    paths(
+        health_check,
+        get_tasks,
+        get_tasks_today,
+        get_tasks_week,
+        get_tasks_overdue,
+        get_tasks_unscheduled,
+        get_tasks_inbox,
+        get_inbox_status,
+        create_task,
+        quick_add_task,
+        update_task,
+        toggle_task,
+        delete_task,
+        get_tags,
+        get_task_tree,
+        triage_task,
+        get_projects,
+        create_project,
+        update_project,
+        delete_project,
+        get_project_tasks,
+        search,
+        get_goals,
+        create_goal,
+        get_goal_tree,
+        get_goal,
+        update_goal,
+        delete_goal,
+        create_key_result,
+        update_key_result,
+        delete_key_result,
+        get_habits,
+        create_habit,
+        update_habit,
+        delete_habit,
+        create_habit_entry,
+        get_habit_stats,
+        get_habit_heatmap,
+        get_trash,
+        empty_trash,
+        restore_task,
+        purge_task,
+        restore_habit,
+        purge_habit,
+        get_character,
+        get_quests,
+        complete_quest,
+        get_achievements,
+        get_achievement_stats,
+        earn_achievement,
+        stream_events,
+    ),
+    modifiers(&ApiKeySecurity),
+    tags(
+        (name = "system", description = "Server status"),
+        (name = "tasks", description = "Tasks"),
+        (name = "projects", description = "Task projects"),
+        (name = "search", description = "Full-text search across tasks, habits and quests"),
+        (name = "goals", description = "Goals (Vision, Yearly, Quarterly, Monthly) and key results"),
+        (name = "habits", description = "Habits and habit entries"),
+        (name = "trash", description = "Deleted tasks and habits: restore or delete permanently"),
+        (name = "character", description = "Player character"),
+        (name = "quests", description = "Weekly quests"),
+        (name = "achievements", description = "Achievements"),
+        (name = "events", description = "Live domain events (Server-Sent Events)")
+    )
+)]
+pub struct ApiDoc;
+
+/// Registers the `Authorization: Bearer <API key>` scheme referenced by protected paths
+struct ApiKeySecurity;
+
+impl Modify for ApiKeySecurity {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .description(Some("API key created in the app (pdrpg_...)"))
+                    .build(),
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Committed copy of the specification; regenerate with `UPDATE_OPENAPI=1 cargo test`
+    const SPEC_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/openapi.json");
+
+    #[test]
+    fn test_openapi_spec_is_up_to_date() {
+        let generated = ApiDoc::openapi().to_pretty_json().unwrap() + "\n";
+
+        if std::env::var("UPDATE_OPENAPI").is_ok() {
+            std::fs::write(SPEC_PATH, &generated).unwrap();
+            return;
+        }
+
+        let committed = std::fs::read_to_string(SPEC_PATH).unwrap_or_default();
+        assert!(
+            committed == generated,
+            "openapi.json is out of date, run `UPDATE_OPENAPI=1 cargo test` and review the diff"
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::models::{Character, Habit, HabitEntry, Page};
+
+/// Standard success envelope: `{"success": true, "data": ...}`
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiResponse<T> {
+    pub success: bool,
+    pub data: T,
+}
+
+impl<T> ApiResponse<T> {
+    pub fn new(data: T) -> Self {
+        ApiResponse {
+            success: true,
+            data,
+        }
+    }
+}
+
+/// Success envelope for collections, with the number of returned items
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiListResponse<T> {
+    pub success: bool,
+    pub data: Vec<T>,
+    pub count: usize,
+}
+
+impl<T> ApiListResponse<T> {
+    pub fn new(data: Vec<T>) -> Self {
+        ApiListResponse {
+            success: true,
+            count: data.len(),
+            data,
+        }
+    }
+}
+
+/// Success envelope for one page of a paginated collection
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiPageResponse<T> {
+    pub success: bool,
+    pub data: Vec<T>,
+    pub count: usize,
+    /// Pass as `cursor` to fetch the next page; `null` on the last page
+    pub next_cursor: Option<String>,
+}
+
+impl<T> From<Page<T>> for ApiPageResponse<T> {
+    fn from(page: Page<T>) -> Self {
+        ApiPageResponse {
+            success: true,
+            count: page.items.len(),
+            data: page.items,
+            next_cursor: page.next_cursor,
+        }
+    }
+}
+
+/// Error envelope: `{"success": false, "error": "..."}`
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorResponse {
+    /// Always `false`
+    pub success: bool,
+    /// Human-readable error message
+    pub error: String,
+}
+
+/// Body of `GET /api/health`
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HealthResponse {
+    pub status: String,
+    pub service: String,
+    pub version: String,
+    /// RFC 3339 timestamp of the response
+    pub timestamp: String,
+}
+
+/// Habit together with its entry for the current day
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HabitWithToday {
+    pub habit: Habit,
+    pub today_entry: Option<HabitEntry>,
+    /// Whether the habit's schedule expects it today (e.g. `false` on a Tuesday for a Mon/Wed/Fri habit)
+    pub due_today: bool,
+    /// Whether today's entry meets the habit's goal; for `TimesPerWeek` habits, whether this week's target is met
+    pub today_completed: bool,
+}
+
+/// Body of `GET /api/habits` - the habit list plus the date used for `today_entry`
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HabitListResponse {
+    pub success: bool,
+    pub data: Vec<HabitWithToday>,
+    pub count: usize,
+    /// Pass as `cursor` to fetch the next page; `null` on the last page
+    pub next_cursor: Option<String>,
+    /// Day the entries belong to (YYYY-MM-DD)
+    pub date: String,
+}
+
+/// Progress towards the character's next level
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LevelProgress {
+    pub current_level_exp: i64,
+    pub next_level_exp: i64,
+    pub progress_percentage: f64,
+    pub exp_to_next_level: i64,
+}
+
+/// Character with its level progress
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CharacterOverview {
+    pub character: Character,
+    pub level_progress: LevelProgress,
+}
+
+/// Achievement counts by status
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AchievementStats {
+    pub earned: i32,
+    pub available: i32,
+    pub locked: i32,
+    pub total: i32,
+}
@@ -0,0 +1,8 @@
+//! Serwer MCP (Model Context Protocol) na stdio dla lokalnych asystentów AI
+
+fn main() {
+    if let Err(e) = pdrpg_lib::run_mcp_stdio() {
+        eprintln!("❌ MCP server error: {:#}", e);
+        std::process::exit(1);
+    }
+}
@@ -2,6 +2,17 @@ use anyhow::Result;
 use rusqlite::{Connection, OpenFlags};
 use rusqlite_migration::{Migrations, M};
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Jak długo połączenie czeka na zwolnienie blokady pliku zanim zwróci SQLITE_BUSY
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Współdzielony uchwyt bazy danych używany przez komendy Tauri i serwer API
+///
+/// Jedno połączenie chronione mutexem pełni rolę jedynego writera, więc zapisy
+/// z UI i z API są serializowane zamiast konkurować o blokadę pliku.
+pub type SharedDatabase = Arc<Mutex<Database>>;
 
 /// Struktura zarządzająca połączeniem z bazą danych
 pub struct Database {
@@ -27,6 +38,14 @@ impl Database {
         // Włączenie foreign keys
         connection.execute("PRAGMA foreign_keys = ON;", [])?;
 
+        // WAL pozwala czytać bazę (np. narzędziom zewnętrznym) w trakcie zapisu,
+        // a busy timeout chroni przed SQLITE_BUSY gdy plik jest chwilowo zablokowany
+        connection.query_row("PRAGMA journal_mode = WAL;", [], |row| {
+            row.get::<_, String>(0)
+        })?;
+        connection.execute("PRAGMA synchronous = NORMAL;", [])?;
+        connection.busy_timeout(BUSY_TIMEOUT)?;
+
         let mut db = Database { connection };
 
         // Uruchomienie migracji
@@ -44,6 +63,36 @@ impl Database {
             M::up(include_str!(
                 "../../migrations/0004_create_quests_and_achievements.sql"
             )),
+            M::up(include_str!(
+                "../../migrations/0005_create_api_keys_and_settings.sql"
+            )),
+            M::up(include_str!("../../migrations/0006_create_webhooks.sql")),
+            M::up(include_str!(
+                "../../migrations/0007_add_task_tags_and_priority.sql"
+            )),
+            M::up(include_str!("../../migrations/0008_add_task_dates.sql")),
+            M::up(include_str!(
+                "../../migrations/0009_add_task_recurrence.sql"
+            )),
+            M::up(include_str!(
+                "../../migrations/0010_add_projects_and_subtasks.sql"
+            )),
+            M::up(include_str!("../../migrations/0011_create_goals.sql")),
+            M::up(include_str!("../../migrations/0012_add_task_inbox.sql")),
+            M::up(include_str!("../../migrations/0013_add_task_estimate.sql")),
+            M::up(include_str!(
+                "../../migrations/0014_create_search_index.sql"
+            )),
+            M::up(include_str!("../../migrations/0015_add_soft_delete.sql")),
+            M::up(include_str!(
+                "../../migrations/0016_create_reward_ledger.sql"
+            )),
+            M::up(include_str!("../../migrations/0017_add_habit_schedule.sql")),
+            M::up(include_str!("../../migrations/0018_add_streak_freezes.sql")),
+            M::up(include_str!("../../migrations/0019_add_habit_totals.sql")),
+            M::up(include_str!(
+                "../../migrations/0020_add_overdue_quest_type.sql"
+            )),
         ]);
 
         migrations.to_latest(&mut self.connection)?;
@@ -58,7 +107,9 @@ impl Database {
 }
 
 /// Inicjalizuje bazę danych w standardowej lokalizacji aplikacji
-pub fn initialize_database() -> Result<Database> {
+///
+/// Wywoływana raz przy starcie aplikacji; zwrócony uchwyt należy współdzielić zamiast otwierać kolejne połączenia.
+pub fn initialize_database() -> Result<SharedDatabase> {
     let mut db_path = get_app_data_dir()?;
     db_path.push("pdrpg.db");
 
@@ -67,7 +118,7 @@ pub fn initialize_database() -> Result<Database> {
         std::fs::create_dir_all(parent)?;
     }
 
-    Database::new(db_path)
+    Ok(Arc::new(Mutex::new(Database::new(db_path)?)))
 }
 
 /// Zwraca ścieżkę do katalogu danych aplikacji
@@ -1,20 +1,29 @@
 // Moduły aplikacji
 mod api;
 mod database;
+mod mcp;
 mod models;
 mod services;
 
-use database::Database;
+use database::SharedDatabase;
 use models::{
-    Achievement, AchievementStatus, Character, CreateCharacterRequest, CreateHabitEntryRequest,
-    CreateHabitRequest, CreateQuestRequest, CreateTaskRequest, Habit, HabitEntry, Quest, Task,
-    UpdateCharacterRequest, UpdateHabitRequest,
+    Achievement, AchievementStatus, ApiKey, ApiServerConfig, ApiServerStatus, Character,
+    CreateApiKeyRequest, CreateCharacterRequest, CreateGoalRequest, CreateHabitEntryRequest,
+    CreateHabitRequest, CreateKeyResultRequest, CreateProjectRequest, CreateQuestRequest,
+    CreateTaskRequest, CreateWebhookRequest, CreatedApiKey, Goal, GoalLevel, GoalNode, Habit,
+    HabitEntry, HabitQuery, HabitStats, HeatmapDay, HeatmapQuery, InboxStatus, KeyResult, Page,
+    ParsedTask, Project, Quest, QuickAddRequest, QuickAddResult, SearchQuery, SearchResult, Tag,
+    Task, TaskNode, TaskQuery, TimeSettings, TrashItem, TriageTaskRequest, UpdateCharacterRequest,
+    UpdateGoalRequest, UpdateHabitRequest, UpdateKeyResultRequest, UpdateProjectRequest,
+    UpdateTaskRequest, Webhook, WebhookDelivery,
 };
 use services::{
-    achievement_service, character_service, habit_service, quest_service, task_service,
+    achievement_service, api_key_service, character_service, goal_service, habit_service,
+    habit_stats_service, inbox_service, project_service, quest_service, quick_add, search_service,
+    settings_service, tag_service, task_service, trash_service, webhook_dispatcher,
+    webhook_service,
 };
-use std::sync::{Arc, Mutex};
-use tauri::State;
+use tauri::{Manager, State};
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
@@ -24,156 +33,734 @@ fn greet(name: &str) -> String {
 
 /// Tauri command do dodawania nowego zadania
 #[tauri::command]
-fn add_task(title: String, state: State<AppState>) -> Result<Task, String> {
+fn add_task(request: CreateTaskRequest, state: State<AppState>) -> Result<Task, String> {
     let db = state
         .db
         .lock()
         .map_err(|e| format!("Database lock error: {}", e))?;
     let conn = db.connection();
 
-    let request = CreateTaskRequest { title };
+    task_service::add_task(conn, request).map_err(|e| format!("Failed to add task: {}", e))
+}
+
+/// Tauri command do szybkiego dodawania zadania z tekstu (`jutro 18:00 #tag !high +Projekt ~30m`)
+#[tauri::command]
+fn quick_add_task(text: String, state: State<AppState>) -> Result<QuickAddResult, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    quick_add::quick_add_task(conn, QuickAddRequest { text })
+        .map_err(|e| format!("Failed to add task: {}", e))
+}
+
+/// Tauri command do podglądu rozpoznanych pól szybkiego dodawania (bez tworzenia zadania)
+#[tauri::command]
+fn parse_quick_add(text: String, state: State<AppState>) -> Result<ParsedTask, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    quick_add::parse_text(conn, &text).map_err(|e| format!("Failed to parse task: {}", e))
+}
+
+/// Tauri command do aktualizacji zadania (tytuł, priorytet, atrybut, tagi, terminy, powtarzanie)
+#[tauri::command]
+fn update_task(
+    task_id: i32,
+    request: UpdateTaskRequest,
+    state: State<AppState>,
+) -> Result<Task, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    task_service::update_task(conn, task_id, request)
+        .map_err(|e| format!("Failed to update task: {}", e))
+}
+
+/// Tauri command do pobierania tagów zadań
+#[tauri::command]
+fn list_tags(state: State<AppState>) -> Result<Vec<Tag>, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    tag_service::list_tags(conn).map_err(|e| format!("Failed to list tags: {}", e))
+}
+
+/// Tauri command do wyszukiwania pełnotekstowego w zadaniach, nawykach i questach
+#[tauri::command]
+fn search(query: SearchQuery, state: State<AppState>) -> Result<Vec<SearchResult>, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    search_service::search(conn, query).map_err(|e| format!("Failed to search: {}", e))
+}
+
+/// Tauri command do pobierania wszystkich zadań
+#[tauri::command]
+fn get_all_tasks(state: State<AppState>) -> Result<Vec<Task>, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    task_service::get_all_tasks(conn).map_err(|e| format!("Failed to get tasks: {}", e))
+}
+
+/// Tauri command do pobierania zadań na dziś
+#[tauri::command]
+fn get_tasks_for_today(state: State<AppState>) -> Result<Vec<Task>, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    task_service::get_tasks_for_today(conn)
+        .map_err(|e| format!("Failed to get today's tasks: {}", e))
+}
+
+/// Tauri command do pobierania zadań na bieżący tydzień
+#[tauri::command]
+fn get_tasks_for_week(state: State<AppState>) -> Result<Vec<Task>, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    task_service::get_tasks_for_week(conn)
+        .map_err(|e| format!("Failed to get this week's tasks: {}", e))
+}
+
+/// Tauri command do pobierania zaległych zadań
+#[tauri::command]
+fn get_overdue_tasks(state: State<AppState>) -> Result<Vec<Task>, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    task_service::get_overdue_tasks(conn).map_err(|e| format!("Failed to get overdue tasks: {}", e))
+}
+
+/// Tauri command do pobierania niezaplanowanych zadań
+#[tauri::command]
+fn get_unscheduled_tasks(state: State<AppState>) -> Result<Vec<Task>, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    task_service::get_unscheduled_tasks(conn)
+        .map_err(|e| format!("Failed to get unscheduled tasks: {}", e))
+}
+
+/// Tauri command do pobierania zadań ze skrzynki
+#[tauri::command]
+fn get_inbox_tasks(state: State<AppState>) -> Result<Vec<Task>, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    task_service::get_inbox_tasks(conn).map_err(|e| format!("Failed to get inbox tasks: {}", e))
+}
+
+/// Tauri command do przeglądu zadania ze skrzynki (projekt, cel, terminy, tagi, priorytet)
+#[tauri::command]
+fn triage_task(
+    task_id: i32,
+    request: TriageTaskRequest,
+    state: State<AppState>,
+) -> Result<Task, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    inbox_service::triage_task(conn, task_id, request)
+        .map_err(|e| format!("Failed to triage task: {}", e))
+}
+
+/// Tauri command do pobierania stanu skrzynki (liczba zadań, licznik inbox zero)
+#[tauri::command]
+fn get_inbox_status(state: State<AppState>) -> Result<InboxStatus, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    inbox_service::get_inbox_status(conn).map_err(|e| format!("Failed to get inbox status: {}", e))
+}
+
+/// Tauri command do pobierania strony zadań z filtrami i sortowaniem
+#[tauri::command]
+fn query_tasks(query: TaskQuery, state: State<AppState>) -> Result<Page<Task>, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    task_service::query_tasks(conn, query).map_err(|e| format!("Failed to query tasks: {}", e))
+}
+
+/// Tauri command do przełączania statusu zadania
+#[tauri::command]
+fn toggle_task_status(task_id: i32, state: State<AppState>) -> Result<Task, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    task_service::toggle_task_status(conn, task_id)
+        .map_err(|e| format!("Failed to toggle task status: {}", e))
+}
+
+/// Tauri command do przenoszenia zadania (razem z podzadaniami) do kosza
+#[tauri::command]
+fn delete_task(task_id: i32, state: State<AppState>) -> Result<(), String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    task_service::delete_task(conn, task_id).map_err(|e| format!("Failed to delete task: {}", e))
+}
+
+/// Tauri command do pobierania zadania z drzewem podzadań
+#[tauri::command]
+fn get_task_tree(task_id: i32, state: State<AppState>) -> Result<TaskNode, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    task_service::get_task_tree(conn, task_id)
+        .map_err(|e| format!("Failed to get task tree: {}", e))
+}
+
+/// Tauri command do włączania automatycznego kończenia zadań nadrzędnych
+#[tauri::command]
+fn set_auto_complete_parents(enabled: bool, state: State<AppState>) -> Result<bool, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    settings_service::set_auto_complete_parents(conn, enabled)
+        .and_then(|_| settings_service::get_auto_complete_parents(conn))
+        .map_err(|e| format!("Failed to set auto-complete of parent tasks: {}", e))
+}
+
+/// Tauri command do sprawdzania czy ostatnie podzadanie kończy zadanie nadrzędne
+#[tauri::command]
+fn get_auto_complete_parents(state: State<AppState>) -> Result<bool, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    settings_service::get_auto_complete_parents(conn)
+        .map_err(|e| format!("Failed to get auto-complete of parent tasks: {}", e))
+}
+
+// ==== PROJECT COMMANDS ====
+
+/// Tauri command do tworzenia projektu
+#[tauri::command]
+fn create_project(
+    request: CreateProjectRequest,
+    state: State<AppState>,
+) -> Result<Project, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    project_service::create_project(conn, request)
+        .map_err(|e| format!("Failed to create project: {}", e))
+}
+
+/// Tauri command do pobierania projektów z postępem
+#[tauri::command]
+fn list_projects(state: State<AppState>) -> Result<Vec<Project>, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    project_service::list_projects(conn).map_err(|e| format!("Failed to list projects: {}", e))
+}
+
+/// Tauri command do aktualizacji projektu
+#[tauri::command]
+fn update_project(
+    project_id: i32,
+    request: UpdateProjectRequest,
+    state: State<AppState>,
+) -> Result<Project, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    project_service::update_project(conn, project_id, request)
+        .map_err(|e| format!("Failed to update project: {}", e))
+}
+
+/// Tauri command do usuwania projektu (zadania zostają bez projektu)
+#[tauri::command]
+fn delete_project(project_id: i32, state: State<AppState>) -> Result<(), String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    project_service::delete_project(conn, project_id)
+        .map_err(|e| format!("Failed to delete project: {}", e))
+}
+
+/// Tauri command do pobierania drzewa zadań projektu
+#[tauri::command]
+fn get_project_tree(project_id: i32, state: State<AppState>) -> Result<Vec<TaskNode>, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    task_service::get_project_tree(conn, project_id)
+        .map_err(|e| format!("Failed to get project tasks: {}", e))
+}
+
+// ==== GOAL COMMANDS ====
+
+/// Tauri command do tworzenia celu
+#[tauri::command]
+fn create_goal(request: CreateGoalRequest, state: State<AppState>) -> Result<Goal, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    goal_service::create_goal(conn, request).map_err(|e| format!("Failed to create goal: {}", e))
+}
+
+/// Tauri command do pobierania celów (opcjonalnie z jednego poziomu)
+#[tauri::command]
+fn list_goals(level: Option<GoalLevel>, state: State<AppState>) -> Result<Vec<Goal>, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    goal_service::list_goals(conn, level).map_err(|e| format!("Failed to list goals: {}", e))
+}
+
+/// Tauri command do pobierania drzewa celów
+#[tauri::command]
+fn get_goal_tree(state: State<AppState>) -> Result<Vec<GoalNode>, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    goal_service::get_goal_tree(conn).map_err(|e| format!("Failed to get goal tree: {}", e))
+}
+
+/// Tauri command do aktualizacji celu
+#[tauri::command]
+fn update_goal(
+    goal_id: i32,
+    request: UpdateGoalRequest,
+    state: State<AppState>,
+) -> Result<Goal, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    goal_service::update_goal(conn, goal_id, request)
+        .map_err(|e| format!("Failed to update goal: {}", e))
+}
+
+/// Tauri command do usuwania celu z podcelami (zadania zostają bez celu)
+#[tauri::command]
+fn delete_goal(goal_id: i32, state: State<AppState>) -> Result<(), String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    goal_service::delete_goal(conn, goal_id).map_err(|e| format!("Failed to delete goal: {}", e))
+}
+
+/// Tauri command do dodawania kluczowego rezultatu
+#[tauri::command]
+fn add_key_result(
+    goal_id: i32,
+    request: CreateKeyResultRequest,
+    state: State<AppState>,
+) -> Result<KeyResult, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    goal_service::add_key_result(conn, goal_id, request)
+        .map_err(|e| format!("Failed to add key result: {}", e))
+}
+
+/// Tauri command do aktualizacji kluczowego rezultatu
+#[tauri::command]
+fn update_key_result(
+    key_result_id: i32,
+    request: UpdateKeyResultRequest,
+    state: State<AppState>,
+) -> Result<KeyResult, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    goal_service::update_key_result(conn, key_result_id, request)
+        .map_err(|e| format!("Failed to update key result: {}", e))
+}
+
+/// Tauri command do usuwania kluczowego rezultatu
+#[tauri::command]
+fn delete_key_result(key_result_id: i32, state: State<AppState>) -> Result<(), String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    goal_service::delete_key_result(conn, key_result_id)
+        .map_err(|e| format!("Failed to delete key result: {}", e))
+}
+
+// ==== HABIT COMMANDS ====
+
+/// Tauri command do dodawania nowego nawyku
+#[tauri::command]
+fn add_habit(request: CreateHabitRequest, state: State<AppState>) -> Result<Habit, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    habit_service::add_habit(conn, request).map_err(|e| format!("Failed to add habit: {}", e))
+}
+
+/// Tauri command do pobierania wszystkich nawyków
+#[tauri::command]
+fn get_all_habits(state: State<AppState>) -> Result<Vec<Habit>, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    habit_service::get_all_habits(conn).map_err(|e| format!("Failed to get habits: {}", e))
+}
+
+/// Tauri command do pobierania strony nawyków z filtrami i sortowaniem
+#[tauri::command]
+fn query_habits(query: HabitQuery, state: State<AppState>) -> Result<Page<Habit>, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    habit_service::query_habits(conn, query).map_err(|e| format!("Failed to query habits: {}", e))
+}
+
+/// Tauri command do przenoszenia nawyku do kosza
+#[tauri::command]
+fn delete_habit(id: i32, state: State<AppState>) -> Result<(), String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    habit_service::delete_habit(conn, id).map_err(|e| format!("Failed to delete habit: {}", e))
+}
+
+/// Tauri command do aktualizacji nawyku
+#[tauri::command]
+fn update_habit(
+    habit_id: i32,
+    request: UpdateHabitRequest,
+    state: State<AppState>,
+) -> Result<Habit, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    habit_service::update_habit(conn, habit_id, request)
+        .map_err(|e| format!("Failed to update habit: {}", e))
+}
+
+/// Tauri command do dodawania wpisu nawyku
+#[tauri::command]
+fn add_habit_entry(
+    request: CreateHabitEntryRequest,
+    state: State<AppState>,
+) -> Result<HabitEntry, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
 
-    task_service::add_task(conn, request).map_err(|e| format!("Failed to add task: {}", e))
+    habit_service::add_habit_entry(conn, request)
+        .map_err(|e| format!("Failed to add habit entry: {}", e))
 }
 
-/// Tauri command do pobierania wszystkich zadań
+/// Tauri command do pobierania wpisów nawyków na konkretny dzień
 #[tauri::command]
-fn get_all_tasks(state: State<AppState>) -> Result<Vec<Task>, String> {
+fn get_habit_entries_for_date(
+    date: String,
+    state: State<AppState>,
+) -> Result<Vec<HabitEntry>, String> {
     let db = state
         .db
         .lock()
         .map_err(|e| format!("Database lock error: {}", e))?;
     let conn = db.connection();
 
-    task_service::get_all_tasks(conn).map_err(|e| format!("Failed to get tasks: {}", e))
+    habit_service::get_habit_entries_for_date(conn, &date)
+        .map_err(|e| format!("Failed to get habit entries for date: {}", e))
 }
 
-/// Tauri command do przełączania statusu zadania
+/// Tauri command do pobierania wszystkich wpisów dla konkretnego nawyku
 #[tauri::command]
-fn toggle_task_status(task_id: i32, state: State<AppState>) -> Result<Task, String> {
+fn get_habit_entries_for_habit(
+    habit_id: i32,
+    state: State<AppState>,
+) -> Result<Vec<HabitEntry>, String> {
     let db = state
         .db
         .lock()
         .map_err(|e| format!("Database lock error: {}", e))?;
     let conn = db.connection();
 
-    task_service::toggle_task_status(conn, task_id)
-        .map_err(|e| format!("Failed to toggle task status: {}", e))
+    habit_service::get_habit_entries_for_habit(conn, habit_id)
+        .map_err(|e| format!("Failed to get habit entries: {}", e))
 }
 
-/// Tauri command do usuwania zadania
+/// Tauri command do pobierania statystyk nawyku (odsetki wykonania, dni tygodnia, historia serii)
 #[tauri::command]
-fn delete_task(task_id: i32, state: State<AppState>) -> Result<(), String> {
+fn get_habit_stats(habit_id: i32, state: State<AppState>) -> Result<HabitStats, String> {
     let db = state
         .db
         .lock()
         .map_err(|e| format!("Database lock error: {}", e))?;
     let conn = db.connection();
 
-    task_service::delete_task(conn, task_id).map_err(|e| format!("Failed to delete task: {}", e))
+    habit_stats_service::get_habit_stats(conn, habit_id)
+        .map_err(|e| format!("Failed to get habit stats: {}", e))
 }
 
-// ==== HABIT COMMANDS ====
+/// Tauri command do pobierania kalendarza wypełnienia (jeden nawyk lub wszystkie razem)
+#[tauri::command]
+fn get_habit_heatmap(
+    query: HeatmapQuery,
+    state: State<AppState>,
+) -> Result<Vec<HeatmapDay>, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
 
-/// Tauri command do dodawania nowego nawyku
+    habit_stats_service::get_heatmap(conn, query)
+        .map_err(|e| format!("Failed to get habit heatmap: {}", e))
+}
+
+/// Tauri command do pobierania strefy czasowej i godziny rozpoczęcia dnia
 #[tauri::command]
-fn add_habit(request: CreateHabitRequest, state: State<AppState>) -> Result<Habit, String> {
+fn get_time_settings(state: State<AppState>) -> Result<TimeSettings, String> {
     let db = state
         .db
         .lock()
         .map_err(|e| format!("Database lock error: {}", e))?;
     let conn = db.connection();
 
-    habit_service::add_habit(conn, request).map_err(|e| format!("Failed to add habit: {}", e))
+    settings_service::get_time_settings(conn)
+        .map_err(|e| format!("Failed to get time settings: {}", e))
 }
 
-/// Tauri command do pobierania wszystkich nawyków
+/// Tauri command do ustawiania strefy czasowej (nazwa IANA, np. "Europe/Warsaw")
 #[tauri::command]
-fn get_all_habits(state: State<AppState>) -> Result<Vec<Habit>, String> {
+fn set_time_zone(time_zone: String, state: State<AppState>) -> Result<TimeSettings, String> {
     let db = state
         .db
         .lock()
         .map_err(|e| format!("Database lock error: {}", e))?;
     let conn = db.connection();
 
-    habit_service::get_all_habits(conn).map_err(|e| format!("Failed to get habits: {}", e))
+    settings_service::set_time_zone(conn, &time_zone)
+        .and_then(|_| settings_service::get_time_settings(conn))
+        .map_err(|e| format!("Failed to set time zone: {}", e))
 }
 
-/// Tauri command do usuwania nawyku
+/// Tauri command do ustawiania godziny, o której zaczyna się nowy dzień
 #[tauri::command]
-fn delete_habit(id: i32, state: State<AppState>) -> Result<(), String> {
+fn set_day_rollover_hour(hour: u32, state: State<AppState>) -> Result<TimeSettings, String> {
     let db = state
         .db
         .lock()
         .map_err(|e| format!("Database lock error: {}", e))?;
     let conn = db.connection();
 
-    habit_service::delete_habit(conn, id).map_err(|e| format!("Failed to delete habit: {}", e))
+    settings_service::set_day_rollover_hour(conn, hour)
+        .and_then(|_| settings_service::get_time_settings(conn))
+        .map_err(|e| format!("Failed to set day rollover hour: {}", e))
 }
 
-/// Tauri command do aktualizacji nawyku
+// ==== TRASH COMMANDS ====
+
+/// Tauri command do pobierania zawartości kosza (wygasłe elementy są najpierw usuwane)
 #[tauri::command]
-fn update_habit(
-    habit_id: i32,
-    request: UpdateHabitRequest,
-    state: State<AppState>,
-) -> Result<Habit, String> {
+fn get_trash(state: State<AppState>) -> Result<Vec<TrashItem>, String> {
     let db = state
         .db
         .lock()
         .map_err(|e| format!("Database lock error: {}", e))?;
     let conn = db.connection();
 
-    habit_service::update_habit(conn, habit_id, request)
-        .map_err(|e| format!("Failed to update habit: {}", e))
+    trash_service::list_trash(conn).map_err(|e| format!("Failed to get trash: {}", e))
 }
 
-/// Tauri command do dodawania wpisu nawyku
+/// Tauri command do przywracania zadania (razem z podzadaniami) z kosza
 #[tauri::command]
-fn add_habit_entry(
-    request: CreateHabitEntryRequest,
-    state: State<AppState>,
-) -> Result<HabitEntry, String> {
+fn restore_task(task_id: i32, state: State<AppState>) -> Result<Task, String> {
     let db = state
         .db
         .lock()
         .map_err(|e| format!("Database lock error: {}", e))?;
     let conn = db.connection();
 
-    habit_service::add_habit_entry(conn, request)
-        .map_err(|e| format!("Failed to add habit entry: {}", e))
+    trash_service::restore_task(conn, task_id).map_err(|e| format!("Failed to restore task: {}", e))
 }
 
-/// Tauri command do pobierania wpisów nawyków na konkretny dzień
+/// Tauri command do przywracania nawyku z kosza
 #[tauri::command]
-fn get_habit_entries_for_date(
-    date: String,
-    state: State<AppState>,
-) -> Result<Vec<HabitEntry>, String> {
+fn restore_habit(id: i32, state: State<AppState>) -> Result<Habit, String> {
     let db = state
         .db
         .lock()
         .map_err(|e| format!("Database lock error: {}", e))?;
     let conn = db.connection();
 
-    habit_service::get_habit_entries_for_date(conn, &date)
-        .map_err(|e| format!("Failed to get habit entries for date: {}", e))
+    trash_service::restore_habit(conn, id).map_err(|e| format!("Failed to restore habit: {}", e))
 }
 
-/// Tauri command do pobierania wszystkich wpisów dla konkretnego nawyku
+/// Tauri command do trwałego usuwania zadania z kosza
 #[tauri::command]
-fn get_habit_entries_for_habit(
-    habit_id: i32,
-    state: State<AppState>,
-) -> Result<Vec<HabitEntry>, String> {
+fn purge_task(task_id: i32, state: State<AppState>) -> Result<(), String> {
     let db = state
         .db
         .lock()
         .map_err(|e| format!("Database lock error: {}", e))?;
     let conn = db.connection();
 
-    habit_service::get_habit_entries_for_habit(conn, habit_id)
-        .map_err(|e| format!("Failed to get habit entries: {}", e))
+    trash_service::purge_task(conn, task_id).map_err(|e| format!("Failed to purge task: {}", e))
+}
+
+/// Tauri command do trwałego usuwania nawyku z kosza
+#[tauri::command]
+fn purge_habit(id: i32, state: State<AppState>) -> Result<(), String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    trash_service::purge_habit(conn, id).map_err(|e| format!("Failed to purge habit: {}", e))
+}
+
+/// Tauri command do opróżniania kosza (zwraca liczbę usuniętych elementów)
+#[tauri::command]
+fn empty_trash(state: State<AppState>) -> Result<usize, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    trash_service::empty_trash(conn).map_err(|e| format!("Failed to empty trash: {}", e))
+}
+
+/// Tauri command do ustawiania, po ilu dniach elementy z kosza są trwale usuwane
+#[tauri::command]
+fn set_trash_retention_days(days: u32, state: State<AppState>) -> Result<u32, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    settings_service::set_trash_retention_days(conn, days)
+        .and_then(|_| settings_service::get_trash_retention_days(conn))
+        .map_err(|e| format!("Failed to set trash retention: {}", e))
+}
+
+/// Tauri command do pobierania okresu przechowywania elementów w koszu (w dniach)
+#[tauri::command]
+fn get_trash_retention_days(state: State<AppState>) -> Result<u32, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    settings_service::get_trash_retention_days(conn)
+        .map_err(|e| format!("Failed to get trash retention: {}", e))
 }
 
 // ==== CHARACTER COMMANDS ====
@@ -252,43 +839,272 @@ fn add_attribute_points(
         .map_err(|e| format!("Failed to add attribute points: {}", e))
 }
 
+/// Pobiera zapisaną konfigurację API server z bazy danych aplikacji
+fn load_api_server_config(state: &AppState) -> Result<ApiServerConfig, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+
+    settings_service::get_api_server_config(db.connection())
+        .map_err(|e| format!("Failed to get API server config: {}", e))
+}
+
+/// Uruchamia API server na podanym porcie (wywołujący musi trzymać blokadę `api_server`)
+///
+/// API korzysta z tego samego uchwytu bazy danych co komendy Tauri.
+async fn launch_api_server(state: &AppState, port: u16) -> Result<api::ApiServerHandle, String> {
+    api::start_api_server(state.db.clone(), port)
+        .await
+        .map_err(|e| format!("Failed to start API server: {}", e))
+}
+
 /// Tauri command do uruchamiania API server
+///
+/// Port jest zapisywany w ustawieniach po udanym starcie; bez podanego portu używany jest ostatnio zapisany.
 #[tauri::command]
-async fn start_api_server(port: u16) -> Result<String, String> {
-    // Utwórz osobną instancję bazy danych dla API
-    let api_db = database::initialize_database()
-        .map_err(|e| format!("Failed to initialize API database: {}", e))?;
-    let db_arc = Arc::new(Mutex::new(api_db));
-
-    // Uruchom API server w osobnym tokio task
-    tokio::spawn(async move {
-        match api::start_api_server(db_arc, port).await {
-            Ok(server_handle) => {
-                println!("✅ API Server started on port {}", port);
-                if let Err(e) = server_handle.await {
-                    eprintln!("❌ API Server task failed: {}", e);
-                }
-            }
-            Err(e) => {
-                eprintln!("❌ Failed to start API server: {}", e);
-            }
-        }
-    });
-
-    Ok(format!(
-        "API Server starting on port {}. Check console for status.",
-        port
-    ))
-}
-
-/// Tauri command do sprawdzania statusu API server
-#[tauri::command]
-async fn check_api_status(port: u16) -> Result<bool, String> {
-    // Sprawdź czy port jest zajęty
-    match tokio::net::TcpListener::bind(format!("127.0.0.1:{}", port)).await {
-        Ok(_) => Ok(false), // Port wolny - API server nie działa
-        Err(_) => Ok(true), // Port zajęty - prawdopodobnie API server działa
+async fn start_api_server(
+    port: Option<u16>,
+    state: State<'_, AppState>,
+) -> Result<ApiServerStatus, String> {
+    let mut api_server = state.api_server.lock().await;
+    if let Some(server) = api_server.as_ref().filter(|server| server.is_running()) {
+        return Err(format!(
+            "API server is already running on port {}",
+            server.port()
+        ));
+    }
+
+    let config = load_api_server_config(&state)?;
+    let port = port.unwrap_or(config.port);
+    settings_service::validate_api_port(port)
+        .map_err(|e| format!("Failed to start API server: {}", e))?;
+
+    let server = launch_api_server(&state, port).await?;
+    let status = server.status(config.auto_start);
+    *api_server = Some(server);
+
+    // Port zapisujemy dopiero po udanym starcie, żeby nieudana próba nie nadpisała działającej konfiguracji
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    settings_service::set_api_port(db.connection(), port)
+        .map_err(|e| format!("Failed to save API port: {}", e))?;
+
+    Ok(status)
+}
+
+/// Tauri command do zatrzymywania API server (czeka na zakończenie trwających zapytań)
+#[tauri::command]
+async fn stop_api_server(state: State<'_, AppState>) -> Result<ApiServerStatus, String> {
+    let mut api_server = state.api_server.lock().await;
+    let server = api_server
+        .take()
+        .ok_or_else(|| "API server is not running".to_string())?;
+
+    server
+        .shutdown()
+        .await
+        .map_err(|e| format!("Failed to stop API server: {}", e))?;
+
+    let config = load_api_server_config(&state)?;
+    Ok(api::stopped_status(config.port, config.auto_start))
+}
+
+/// Tauri command do restartu API server (np. po zmianie originów CORS)
+#[tauri::command]
+async fn restart_api_server(state: State<'_, AppState>) -> Result<ApiServerStatus, String> {
+    let mut api_server = state.api_server.lock().await;
+    if let Some(server) = api_server.take() {
+        server
+            .shutdown()
+            .await
+            .map_err(|e| format!("Failed to stop API server: {}", e))?;
     }
+
+    let config = load_api_server_config(&state)?;
+    let server = launch_api_server(&state, config.port).await?;
+    let status = server.status(config.auto_start);
+    *api_server = Some(server);
+
+    Ok(status)
+}
+
+/// Tauri command do pobierania statusu API server
+#[tauri::command]
+async fn get_api_server_status(state: State<'_, AppState>) -> Result<ApiServerStatus, String> {
+    let api_server = state.api_server.lock().await;
+    let config = load_api_server_config(&state)?;
+
+    Ok(match api_server.as_ref() {
+        Some(server) => server.status(config.auto_start),
+        None => api::stopped_status(config.port, config.auto_start),
+    })
+}
+
+/// Tauri command do włączania/wyłączania automatycznego startu API server
+#[tauri::command]
+fn set_api_auto_start(enabled: bool, state: State<AppState>) -> Result<ApiServerConfig, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    settings_service::set_api_auto_start(conn, enabled)
+        .and_then(|_| settings_service::get_api_server_config(conn))
+        .map_err(|e| format!("Failed to set API auto-start: {}", e))
+}
+
+// ==== API KEY COMMANDS ====
+
+/// Tauri command do tworzenia nowego klucza API
+#[tauri::command]
+fn create_api_key(
+    request: CreateApiKeyRequest,
+    state: State<AppState>,
+) -> Result<CreatedApiKey, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    api_key_service::create_api_key(conn, request)
+        .map_err(|e| format!("Failed to create API key: {}", e))
+}
+
+/// Tauri command do pobierania listy kluczy API
+#[tauri::command]
+fn list_api_keys(state: State<AppState>) -> Result<Vec<ApiKey>, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    api_key_service::list_api_keys(conn).map_err(|e| format!("Failed to list API keys: {}", e))
+}
+
+/// Tauri command do unieważniania klucza API
+#[tauri::command]
+fn revoke_api_key(key_id: i32, state: State<AppState>) -> Result<(), String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    api_key_service::revoke_api_key(conn, key_id)
+        .map_err(|e| format!("Failed to revoke API key: {}", e))
+}
+
+/// Tauri command do pobierania originów dopuszczonych przez CORS
+#[tauri::command]
+fn get_api_cors_origins(state: State<AppState>) -> Result<Vec<String>, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    settings_service::get_cors_origins(conn)
+        .map_err(|e| format!("Failed to get CORS origins: {}", e))
+}
+
+/// Tauri command do ustawiania originów dopuszczonych przez CORS
+/// (zmiana obowiązuje po ponownym uruchomieniu API server)
+#[tauri::command]
+fn set_api_cors_origins(
+    origins: Vec<String>,
+    state: State<AppState>,
+) -> Result<Vec<String>, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    settings_service::set_cors_origins(conn, origins)
+        .map_err(|e| format!("Failed to set CORS origins: {}", e))
+}
+
+// ==== WEBHOOK COMMANDS ====
+
+/// Tauri command do rejestrowania webhooka
+#[tauri::command]
+fn create_webhook(
+    request: CreateWebhookRequest,
+    state: State<AppState>,
+) -> Result<Webhook, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    webhook_service::create_webhook(conn, request)
+        .map_err(|e| format!("Failed to create webhook: {}", e))
+}
+
+/// Tauri command do pobierania wszystkich webhooków
+#[tauri::command]
+fn list_webhooks(state: State<AppState>) -> Result<Vec<Webhook>, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    webhook_service::list_webhooks(conn).map_err(|e| format!("Failed to list webhooks: {}", e))
+}
+
+/// Tauri command do włączania/wyłączania webhooka
+#[tauri::command]
+fn set_webhook_active(
+    webhook_id: i32,
+    active: bool,
+    state: State<AppState>,
+) -> Result<Webhook, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    webhook_service::set_webhook_active(conn, webhook_id, active)
+        .map_err(|e| format!("Failed to update webhook: {}", e))
+}
+
+/// Tauri command do usuwania webhooka
+#[tauri::command]
+fn delete_webhook(webhook_id: i32, state: State<AppState>) -> Result<(), String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    webhook_service::delete_webhook(conn, webhook_id)
+        .map_err(|e| format!("Failed to delete webhook: {}", e))
+}
+
+/// Tauri command do pobierania dziennika dostarczeń webhooków
+#[tauri::command]
+fn list_webhook_deliveries(
+    webhook_id: Option<i32>,
+    limit: Option<u32>,
+    state: State<AppState>,
+) -> Result<Vec<WebhookDelivery>, String> {
+    let db = state
+        .db
+        .lock()
+        .map_err(|e| format!("Database lock error: {}", e))?;
+    let conn = db.connection();
+
+    webhook_service::list_deliveries(conn, webhook_id, limit)
+        .map_err(|e| format!("Failed to list webhook deliveries: {}", e))
 }
 
 // ==== QUEST COMMANDS ====
@@ -450,8 +1266,17 @@ fn get_achievement_stats(state: State<AppState>) -> Result<(i32, i32, i32), Stri
 
 /// Stan aplikacji zawierający połączenie z bazą danych
 struct AppState {
-    db: Mutex<Database>,
-    api_server_handle: Option<tokio::task::JoinHandle<()>>,
+    db: SharedDatabase,
+    /// Działający API server (None gdy zatrzymany)
+    api_server: tokio::sync::Mutex<Option<api::ApiServerHandle>>,
+}
+
+/// Uruchamia serwer MCP na stdin/stdout bez interfejsu graficznego (binarka `pdrpg-mcp`)
+///
+/// Korzysta z tej samej bazy danych co aplikacja, więc może działać równolegle z nią.
+pub fn run_mcp_stdio() -> anyhow::Result<()> {
+    let db = database::initialize_database()?;
+    mcp::serve_stdio(db)
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -461,35 +1286,131 @@ pub fn run() {
 
     println!("Database initialized successfully");
 
+    // Trwale usuń elementy, które leżą w koszu dłużej niż okres przechowywania
+    match db.lock() {
+        Ok(db) => match trash_service::purge_expired(db.connection()) {
+            Ok(0) => {}
+            Ok(purged) => println!("Purged {} expired item(s) from the trash", purged),
+            Err(e) => eprintln!("❌ Failed to purge the trash: {}", e),
+        },
+        Err(e) => eprintln!("❌ Database lock error: {}", e),
+    }
+
     // Tworzenie stanu aplikacji (API server zostanie uruchomiony na żądanie)
     let app_state = AppState {
-        db: Mutex::new(db),
-        api_server_handle: None,
+        db,
+        api_server: tokio::sync::Mutex::new(None),
     };
 
     tauri::Builder::default()
         .manage(app_state)
         .plugin(tauri_plugin_opener::init())
+        .setup(|app| {
+            // Wysyłka zdarzeń do zarejestrowanych webhooków
+            tauri::async_runtime::spawn(webhook_dispatcher::run(
+                app.state::<AppState>().db.clone(),
+            ));
+
+            // Przywróć API server, jeśli użytkownik włączył auto-start
+            let handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let state = handle.state::<AppState>();
+                let config = match load_api_server_config(&state) {
+                    Ok(config) => config,
+                    Err(e) => {
+                        eprintln!("❌ {}", e);
+                        return;
+                    }
+                };
+                if !config.auto_start {
+                    return;
+                }
+
+                let mut api_server = state.api_server.lock().await;
+                match launch_api_server(&state, config.port).await {
+                    Ok(server) => *api_server = Some(server),
+                    Err(e) => eprintln!("❌ API server auto-start failed: {}", e),
+                }
+            });
+
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             greet,
             add_task,
+            quick_add_task,
+            parse_quick_add,
             get_all_tasks,
+            query_tasks,
+            get_tasks_for_today,
+            get_tasks_for_week,
+            get_overdue_tasks,
+            get_unscheduled_tasks,
+            get_inbox_tasks,
+            triage_task,
+            get_inbox_status,
+            update_task,
+            list_tags,
+            search,
             toggle_task_status,
             delete_task,
+            get_task_tree,
+            get_auto_complete_parents,
+            set_auto_complete_parents,
+            create_project,
+            list_projects,
+            update_project,
+            delete_project,
+            get_project_tree,
+            create_goal,
+            list_goals,
+            get_goal_tree,
+            update_goal,
+            delete_goal,
+            add_key_result,
+            update_key_result,
+            delete_key_result,
             add_habit,
             get_all_habits,
+            query_habits,
             delete_habit,
             update_habit,
             add_habit_entry,
             get_habit_entries_for_date,
             get_habit_entries_for_habit,
+            get_habit_stats,
+            get_habit_heatmap,
+            get_trash,
+            restore_task,
+            restore_habit,
+            purge_task,
+            purge_habit,
+            empty_trash,
+            get_trash_retention_days,
+            get_time_settings,
+            set_time_zone,
+            set_day_rollover_hour,
+            set_trash_retention_days,
             get_character,
             create_character,
             update_character,
             add_experience,
             add_attribute_points,
             start_api_server,
-            check_api_status,
+            stop_api_server,
+            restart_api_server,
+            get_api_server_status,
+            set_api_auto_start,
+            create_api_key,
+            list_api_keys,
+            revoke_api_key,
+            get_api_cors_origins,
+            set_api_cors_origins,
+            create_webhook,
+            list_webhooks,
+            set_webhook_active,
+            delete_webhook,
+            list_webhook_deliveries,
             generate_weekly_quests,
             get_quests_for_week,
             get_active_quests,
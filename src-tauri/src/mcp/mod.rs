@@ -0,0 +1,362 @@
+mod tools;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+
+use crate::database::SharedDatabase;
+use crate::models::ApiKey;
+
+/// Protocol revision answered when the client asks for one we do not know
+pub const PROTOCOL_VERSION: &str = "2025-06-18";
+/// Protocol revisions this server can speak
+const SUPPORTED_PROTOCOL_VERSIONS: [&str; 3] = ["2024-11-05", "2025-03-26", PROTOCOL_VERSION];
+
+// JSON-RPC 2.0 error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+/// Server-defined error: the API key lacks the scope required by the tool
+const FORBIDDEN: i64 = -32001;
+
+/// Error returned to the client in the JSON-RPC `error` member
+#[derive(Debug)]
+pub struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        RpcError {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn invalid_params(message: impl Into<String>) -> Self {
+        RpcError::new(INVALID_PARAMS, message)
+    }
+}
+
+/// Incoming JSON-RPC message (request or notification)
+#[derive(Debug, Deserialize)]
+struct RpcMessage {
+    jsonrpc: String,
+    /// Missing for notifications, which never get a response
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+/// Model Context Protocol server exposing PDRPG data as tools
+///
+/// The server is transport-agnostic: [`McpServer::handle_message`] takes one
+/// JSON-RPC message and returns the response, which is then written to stdout
+/// ([`serve_stdio`]) or returned from `POST /api/mcp` by the API server.
+#[derive(Clone)]
+pub struct McpServer {
+    database: SharedDatabase,
+    /// Key of the HTTP client; `None` over stdio, which has full local access
+    api_key: Option<ApiKey>,
+}
+
+impl McpServer {
+    pub fn new(database: SharedDatabase) -> Self {
+        McpServer {
+            database,
+            api_key: None,
+        }
+    }
+
+    /// Server for one HTTP client, limited to the tools its API key's scopes allow
+    pub fn with_api_key(database: SharedDatabase, api_key: ApiKey) -> Self {
+        McpServer {
+            database,
+            api_key: Some(api_key),
+        }
+    }
+
+    /// Handles one JSON-RPC message; returns `None` for notifications
+    pub fn handle_message(&self, message: Value) -> Option<Value> {
+        let message: RpcMessage = match serde_json::from_value(message) {
+            Ok(message) => message,
+            Err(e) => {
+                return Some(error_response(
+                    Value::Null,
+                    RpcError::new(INVALID_REQUEST, format!("Invalid request: {}", e)),
+                ))
+            }
+        };
+
+        let Some(id) = message.id else {
+            // Notifications (e.g. `notifications/initialized`) need no answer
+            return None;
+        };
+        if message.jsonrpc != "2.0" {
+            return Some(error_response(
+                id,
+                RpcError::new(INVALID_REQUEST, "jsonrpc must be \"2.0\""),
+            ));
+        }
+
+        let result = match message.method.as_str() {
+            "initialize" => Ok(initialize(&message.params)),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tools::definitions() })),
+            "tools/call" => self.call_tool(message.params),
+            method => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Method not found: {}", method),
+            )),
+        };
+
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(error) => error_response(id, error),
+        })
+    }
+
+    /// Handles one line of raw JSON, answering malformed input with a parse error
+    pub fn handle_text(&self, text: &str) -> Option<Value> {
+        match serde_json::from_str(text) {
+            Ok(message) => self.handle_message(message),
+            Err(e) => Some(error_response(
+                Value::Null,
+                RpcError::new(PARSE_ERROR, format!("Parse error: {}", e)),
+            )),
+        }
+    }
+
+    /// Runs a tool; failures of the action itself are reported as `isError` results
+    fn call_tool(&self, params: Value) -> Result<Value, RpcError> {
+        #[derive(Deserialize)]
+        struct CallParams {
+            name: String,
+            #[serde(default)]
+            arguments: Value,
+        }
+
+        let params: CallParams = serde_json::from_value(params)
+            .map_err(|e| RpcError::invalid_params(format!("Invalid tools/call params: {}", e)))?;
+        let arguments = match params.arguments {
+            Value::Null => json!({}),
+            arguments => arguments,
+        };
+
+        if let Some(api_key) = &self.api_key {
+            let required = tools::required_scope(&params.name);
+            if !api_key.grants(required) {
+                return Err(RpcError::new(
+                    FORBIDDEN,
+                    format!(
+                        "API key '{}' lacks the {} scope required by {}",
+                        api_key.name,
+                        required.as_str(),
+                        params.name
+                    ),
+                ));
+            }
+        }
+
+        let db = self
+            .database
+            .lock()
+            .map_err(|e| RpcError::new(INTERNAL_ERROR, format!("Database lock error: {}", e)))?;
+
+        let (text, is_error) = match tools::call(db.connection(), &params.name, arguments)? {
+            Ok(output) => (
+                serde_json::to_string_pretty(&output)
+                    .map_err(|e| RpcError::new(INTERNAL_ERROR, e.to_string()))?,
+                false,
+            ),
+            Err(e) => (e.to_string(), true),
+        };
+
+        Ok(json!({
+            "content": [{ "type": "text", "text": text }],
+            "isError": is_error,
+        }))
+    }
+}
+
+/// Answers `initialize`, agreeing on the client's protocol version when we support it
+fn initialize(params: &Value) -> Value {
+    let requested = params.get("protocolVersion").and_then(Value::as_str);
+    let protocol_version = requested
+        .filter(|version| SUPPORTED_PROTOCOL_VERSIONS.contains(version))
+        .unwrap_or(PROTOCOL_VERSION);
+
+    json!({
+        "protocolVersion": protocol_version,
+        "capabilities": { "tools": {} },
+        "serverInfo": {
+            "name": "pdrpg",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "instructions": "Tools for the PDRPG gamified productivity app: tasks, habits, the player character and weekly quests.",
+    })
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message },
+    })
+}
+
+/// Serves MCP over stdio: one JSON-RPC message per line on stdin, responses on stdout
+///
+/// Returns when stdin is closed. Nothing else may be written to stdout.
+pub fn serve_stdio(database: SharedDatabase) -> anyhow::Result<()> {
+    let server = McpServer::new(database);
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout();
+
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if let Some(response) = server.handle_text(&line) {
+            writeln!(stdout, "{}", response)?;
+            stdout.flush()?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use crate::models::ApiScope;
+    use std::sync::{Arc, Mutex};
+
+    fn create_test_server() -> McpServer {
+        let db = Database::new(":memory:".into()).unwrap();
+        McpServer::new(Arc::new(Mutex::new(db)))
+    }
+
+    fn request(server: &McpServer, method: &str, params: Value) -> Value {
+        server
+            .handle_message(
+                json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_initialize_and_list_tools() {
+        let server = create_test_server();
+
+        let response = request(
+            &server,
+            "initialize",
+            json!({ "protocolVersion": "2024-11-05", "capabilities": {} }),
+        );
+        assert_eq!(response["result"]["protocolVersion"], "2024-11-05");
+        assert!(server
+            .handle_message(json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }))
+            .is_none());
+
+        let response = request(&server, "tools/list", json!({}));
+        let names: Vec<&str> = response["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|tool| tool["name"].as_str().unwrap())
+            .collect();
+        assert!(names.contains(&"add_task"));
+        assert!(names.contains(&"weekly_summary"));
+    }
+
+    #[test]
+    fn test_call_tools() {
+        let server = create_test_server();
+
+        let response = request(
+            &server,
+            "tools/call",
+            json!({ "name": "add_task", "arguments": { "title": "Napisać raport" } }),
+        );
+        assert_eq!(response["result"]["isError"], false);
+
+        let response = request(
+            &server,
+            "tools/call",
+            json!({ "name": "list_tasks", "arguments": {} }),
+        );
+        let text = response["result"]["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("Napisać raport"));
+
+        // A failing action is an isError result, not a protocol error
+        let response = request(
+            &server,
+            "tools/call",
+            json!({ "name": "complete_task", "arguments": { "task_id": 999 } }),
+        );
+        assert_eq!(response["result"]["isError"], true);
+    }
+
+    #[test]
+    fn test_tool_scopes_follow_api_key() {
+        let db = Database::new(":memory:".into()).unwrap();
+        let api_key = ApiKey {
+            id: 1,
+            name: "Odczyt".to_string(),
+            key_prefix: "pdrpg_ab".to_string(),
+            scopes: vec![ApiScope::Read],
+            created_at: 0,
+            last_used_at: None,
+            revoked_at: None,
+        };
+        let server = McpServer::with_api_key(Arc::new(Mutex::new(db)), api_key);
+
+        let response = request(&server, "tools/list", json!({}));
+        assert!(response["result"]["tools"].is_array());
+
+        for name in ["list_tasks", "get_character", "weekly_summary"] {
+            let response = request(&server, "tools/call", json!({ "name": name }));
+            assert!(
+                response["result"].is_object(),
+                "{} should be readable",
+                name
+            );
+        }
+
+        let response = request(
+            &server,
+            "tools/call",
+            json!({ "name": "add_task", "arguments": { "title": "Napisać raport" } }),
+        );
+        assert_eq!(response["error"]["code"], FORBIDDEN);
+    }
+
+    #[test]
+    fn test_protocol_errors() {
+        let server = create_test_server();
+
+        let response = request(&server, "resources/list", json!({}));
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+
+        let response = request(&server, "tools/call", json!({ "name": "nope" }));
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+
+        let response = request(
+            &server,
+            "tools/call",
+            json!({ "name": "add_task", "arguments": { "title": 5 } }),
+        );
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+
+        let response = server.handle_text("{not json").unwrap();
+        assert_eq!(response["error"]["code"], PARSE_ERROR);
+    }
+}
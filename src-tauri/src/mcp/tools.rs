@@ -0,0 +1,527 @@
+use chrono::{Datelike, Duration, Utc};
+use rusqlite::Connection;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::RpcError;
+use crate::models::{
+    AchievementStatus, ApiScope, Attribute, CreateHabitEntryRequest, CreateTaskRequest, GoalLevel,
+    QuestStatus, QuickAddRequest, SearchQuery, TaskPriority, TaskQuery, TrashKind,
+    TriageTaskRequest, UpdateKeyResultRequest,
+};
+use crate::services::{
+    achievement_service, character_service, goal_service, habit_service, inbox_service,
+    project_service, quest_service, quick_add, search_service, settings_service, task_service,
+    trash_service,
+};
+
+/// Scope an API key needs to call a tool over HTTP
+///
+/// Tools that only read data need `Read`; everything else (including unknown
+/// names) needs `Write`, so a new tool is never readable by mistake.
+pub fn required_scope(name: &str) -> ApiScope {
+    match name {
+        "list_tasks" | "get_inbox_status" | "search" | "list_trash" | "list_projects"
+        | "get_task_tree" | "list_goals" | "list_habits" | "get_character"
+        | "get_active_quests" | "weekly_summary" => ApiScope::Read,
+        _ => ApiScope::Write,
+    }
+}
+
+/// Lists the tools in the shape expected by `tools/list`
+pub fn definitions() -> Vec<Value> {
+    vec![
+        json!({
+            "name": "list_tasks",
+            "description": "List tasks, newest first. Supports filtering by completion, tag, priority and a text search; pass next_cursor back as cursor for the next page. With view set, returns that whole view instead and ignores the other filters.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "view": { "enum": ["today", "week", "overdue", "unscheduled", "inbox"], "description": "today/week: scheduled for or due in that period (days follow the configured time zone and day rollover hour, weeks start on Monday); overdue: open and past due; unscheduled: open with no dates; inbox: open quick-captured tasks waiting for triage, oldest first" },
+                    "completed": { "type": "boolean", "description": "Only completed (true) or open (false) tasks" },
+                    "project_id": { "type": "integer" },
+                    "parent_id": { "type": "integer", "description": "Only direct subtasks of this task" },
+                    "goal_id": { "type": "integer", "description": "Only tasks linked to this goal" },
+                    "inbox": { "type": "boolean", "description": "Only tasks in (true) or out of (false) the inbox" },
+                    "tag": { "type": "string" },
+                    "priority": { "enum": ["Low", "Medium", "High", "Urgent"] },
+                    "search": { "type": "string", "description": "Case-insensitive fragment of the title" },
+                    "limit": { "type": "integer", "minimum": 1, "maximum": 500 },
+                    "cursor": { "type": "string" }
+                }
+            }
+        }),
+        json!({
+            "name": "add_task",
+            "description": "Create a new open task. The attribute (or an attribute/class tag such as 'strength' or 'mage') decides which character attribute grows on completion; priority scales the EXP reward.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "title": { "type": "string" },
+                    "notes": { "type": "string" },
+                    "priority": { "enum": ["Low", "Medium", "High", "Urgent"], "description": "Defaults to Medium" },
+                    "attribute": { "enum": ["strength", "intelligence", "charisma", "dexterity", "wisdom", "constitution"] },
+                    "tags": { "type": "array", "items": { "type": "string" } },
+                    "due_at": { "type": "integer", "description": "Deadline as a Unix timestamp (seconds)" },
+                    "scheduled_for": { "type": "string", "description": "Day to work on the task, YYYY-MM-DD" },
+                    "project_id": { "type": "integer", "description": "Project to add the task to (see list_projects)" },
+                    "parent_id": { "type": "integer", "description": "Makes the task a subtask; subtasks share their parent's project" },
+                    "goal_id": { "type": "integer", "description": "Goal the task works towards (see list_goals); completing it earns more EXP and advances the goal" },
+                    "recurrence": { "type": "string", "description": "Repeat rule (RFC 5545 RRULE subset): FREQ=DAILY|WEEKLY|MONTHLY with optional INTERVAL, BYDAY (weekly), BYMONTHDAY (monthly), COUNT or UNTIL. Completing the task creates the next occurrence." },
+                    "inbox": { "type": "boolean", "description": "Put the task in the inbox for later triage. Defaults to true when no project, parent, goal, dates or recurrence are given" },
+                    "estimate_minutes": { "type": "integer", "minimum": 1, "maximum": 1440 }
+                },
+                "required": ["title"]
+            }
+        }),
+        json!({
+            "name": "quick_add_task",
+            "description": "Create a task from one line of quick-add text in Polish or English, e.g. 'Trening jutro 18:00 #sport !high +Zdrowie ~30m': #tag, !priority (low/medium/high/urgent or niski/sredni/wysoki/pilny), +Project (existing, _ for spaces), ~estimate (30m, 1h30m), a date (dziś/today, jutro/tomorrow, weekday, 'za 3 dni'/'in 3 days', 25.01, 2025-01-25) and a time (18:00). Returns the task and what was recognized; unrecognized text stays in the title.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "text": { "type": "string" }
+                },
+                "required": ["text"]
+            }
+        }),
+        json!({
+            "name": "triage_task",
+            "description": "Triage a task from the inbox: set its project, goal, dates, tags and priority in one call and take it out of the inbox. Emptying the inbox counts towards the inbox zero achievements.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "task_id": { "type": "integer" },
+                    "project_id": { "type": "integer" },
+                    "goal_id": { "type": "integer" },
+                    "scheduled_for": { "type": "string", "description": "Day to work on the task, YYYY-MM-DD" },
+                    "due_at": { "type": "integer", "description": "Deadline as a Unix timestamp (seconds)" },
+                    "tags": { "type": "array", "items": { "type": "string" }, "description": "Replaces the task's tags" },
+                    "priority": { "enum": ["Low", "Medium", "High", "Urgent"] }
+                },
+                "required": ["task_id"]
+            }
+        }),
+        json!({
+            "name": "get_inbox_status",
+            "description": "Get the number of tasks waiting in the inbox and how many days the inbox was emptied (inbox zero).",
+            "inputSchema": { "type": "object", "properties": {} }
+        }),
+        json!({
+            "name": "search",
+            "description": "Full-text search over task titles and notes, habit titles and quest titles and descriptions, best matches first. Words match as prefixes, ignoring case and Polish diacritics ('ksiazka' finds 'Książka'). Matches are wrapped in <mark> tags.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "q": { "type": "string" },
+                    "kind": { "enum": ["task", "habit", "quest"] },
+                    "limit": { "type": "integer", "minimum": 1, "maximum": 100 }
+                },
+                "required": ["q"]
+            }
+        }),
+        json!({
+            "name": "list_trash",
+            "description": "List deleted tasks and habits waiting in the trash, most recently deleted first, with the time each one will be permanently deleted (purge_at).",
+            "inputSchema": { "type": "object", "properties": {} }
+        }),
+        json!({
+            "name": "restore_from_trash",
+            "description": "Restore a deleted task (with the subtasks deleted together with it) or a habit (with all its entries) from the trash.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "kind": { "enum": ["task", "habit"] },
+                    "id": { "type": "integer" }
+                },
+                "required": ["kind", "id"]
+            }
+        }),
+        json!({
+            "name": "list_projects",
+            "description": "List projects with their colour, attribute and rolled-up completion percentage.",
+            "inputSchema": { "type": "object", "properties": {} }
+        }),
+        json!({
+            "name": "get_task_tree",
+            "description": "Get a task with its nested subtasks and the completion percentage of each.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "task_id": { "type": "integer" }
+                },
+                "required": ["task_id"]
+            }
+        }),
+        json!({
+            "name": "list_goals",
+            "description": "List goals (Vision > Yearly > Quarterly > Monthly) with their key results, linked task counts and completion percentage.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "level": { "enum": ["Vision", "Yearly", "Quarterly", "Monthly"] }
+                }
+            }
+        }),
+        json!({
+            "name": "update_key_result",
+            "description": "Record the current value of a key result. A goal whose progress reaches 100% is marked as completed.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "key_result_id": { "type": "integer" },
+                    "current_value": { "type": "number" }
+                },
+                "required": ["key_result_id", "current_value"]
+            }
+        }),
+        json!({
+            "name": "complete_task",
+            "description": "Mark a task as completed, awarding EXP (parents earn more per subtask). Completing the last open subtask also completes the parent. Does nothing if it is already completed.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "task_id": { "type": "integer" }
+                },
+                "required": ["task_id"]
+            }
+        }),
+        json!({
+            "name": "list_habits",
+            "description": "List all habits with their current streaks.",
+            "inputSchema": { "type": "object", "properties": {} }
+        }),
+        json!({
+            "name": "log_habit",
+            "description": "Log a habit for a day (today by default). Boolean habits use completed, counter habits use value.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "habit_id": { "type": "integer" },
+                    "date": { "type": "string", "description": "YYYY-MM-DD, defaults to today" },
+                    "completed": { "type": "boolean" },
+                    "value": { "type": "integer" }
+                },
+                "required": ["habit_id"]
+            }
+        }),
+        json!({
+            "name": "get_character",
+            "description": "Get the player character: level, experience, class and attributes.",
+            "inputSchema": { "type": "object", "properties": {} }
+        }),
+        json!({
+            "name": "get_active_quests",
+            "description": "List the active weekly quests with their progress.",
+            "inputSchema": { "type": "object", "properties": {} }
+        }),
+        json!({
+            "name": "weekly_summary",
+            "description": "Summarize the current week (since Monday): completed tasks, logged habits, quests, earned achievements and the character's level.",
+            "inputSchema": { "type": "object", "properties": {} }
+        }),
+    ]
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum TaskView {
+    Today,
+    Week,
+    Overdue,
+    Unscheduled,
+    Inbox,
+}
+
+#[derive(Deserialize)]
+struct ListTasksArgs {
+    view: Option<TaskView>,
+    project_id: Option<i32>,
+    parent_id: Option<i32>,
+    goal_id: Option<i32>,
+    inbox: Option<bool>,
+    completed: Option<bool>,
+    tag: Option<String>,
+    priority: Option<TaskPriority>,
+    search: Option<String>,
+    limit: Option<u32>,
+    cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct AddTaskArgs {
+    title: String,
+    notes: Option<String>,
+    #[serde(default)]
+    priority: TaskPriority,
+    attribute: Option<Attribute>,
+    #[serde(default)]
+    tags: Vec<String>,
+    due_at: Option<i64>,
+    scheduled_for: Option<String>,
+    recurrence: Option<String>,
+    project_id: Option<i32>,
+    parent_id: Option<i32>,
+    goal_id: Option<i32>,
+    inbox: Option<bool>,
+    estimate_minutes: Option<i32>,
+}
+
+#[derive(Deserialize)]
+struct TaskIdArgs {
+    task_id: i32,
+}
+
+#[derive(Deserialize)]
+struct TriageTaskArgs {
+    task_id: i32,
+    #[serde(flatten)]
+    triage: TriageTaskRequest,
+}
+
+#[derive(Deserialize)]
+struct RestoreArgs {
+    kind: TrashKind,
+    id: i32,
+}
+
+#[derive(Deserialize)]
+struct ListGoalsArgs {
+    level: Option<GoalLevel>,
+}
+
+#[derive(Deserialize)]
+struct UpdateKeyResultArgs {
+    key_result_id: i32,
+    current_value: f64,
+}
+
+#[derive(Deserialize)]
+struct LogHabitArgs {
+    habit_id: i32,
+    date: Option<String>,
+    completed: Option<bool>,
+    value: Option<i32>,
+}
+
+/// Result of the `weekly_summary` tool
+#[derive(Serialize)]
+struct WeeklySummary {
+    /// First day of the week (Monday, YYYY-MM-DD)
+    week_start: String,
+    tasks_completed: usize,
+    tasks_open: usize,
+    habit_entries_completed: usize,
+    quests_completed: usize,
+    quests_total: usize,
+    achievements_earned: Vec<String>,
+    level: Option<i32>,
+    experience: Option<i64>,
+}
+
+fn parse_args<T: DeserializeOwned>(name: &str, arguments: Value) -> Result<T, RpcError> {
+    serde_json::from_value(arguments)
+        .map_err(|e| RpcError::invalid_params(format!("Invalid arguments for {}: {}", name, e)))
+}
+
+fn to_value(output: impl Serialize) -> anyhow::Result<Value> {
+    Ok(serde_json::to_value(output)?)
+}
+
+/// Runs a tool by name
+///
+/// The outer error is a protocol error (unknown tool, malformed arguments);
+/// the inner one is a failure of the action, reported back to the model as text.
+pub fn call(
+    conn: &Connection,
+    name: &str,
+    arguments: Value,
+) -> Result<anyhow::Result<Value>, RpcError> {
+    Ok(match name {
+        "list_tasks" => {
+            let args: ListTasksArgs = parse_args(name, arguments)?;
+            if let Some(view) = args.view {
+                let tasks = match view {
+                    TaskView::Today => task_service::get_tasks_for_today(conn),
+                    TaskView::Week => task_service::get_tasks_for_week(conn),
+                    TaskView::Overdue => task_service::get_overdue_tasks(conn),
+                    TaskView::Unscheduled => task_service::get_unscheduled_tasks(conn),
+                    TaskView::Inbox => task_service::get_inbox_tasks(conn),
+                };
+                return Ok(tasks.map(|tasks| json!({ "tasks": tasks, "next_cursor": null })));
+            }
+            let query = TaskQuery {
+                project_id: args.project_id,
+                parent_id: args.parent_id,
+                goal_id: args.goal_id,
+                inbox: args.inbox,
+                completed: args.completed,
+                tag: args.tag,
+                priority: args.priority,
+                search: args.search,
+                limit: args.limit,
+                cursor: args.cursor,
+                ..Default::default()
+            };
+            task_service::query_tasks(conn, query)
+                .map(|page| json!({ "tasks": page.items, "next_cursor": page.next_cursor }))
+        }
+        "add_task" => {
+            let args: AddTaskArgs = parse_args(name, arguments)?;
+            task_service::add_task(
+                conn,
+                CreateTaskRequest {
+                    title: args.title,
+                    notes: args.notes,
+                    priority: args.priority,
+                    attribute: args.attribute,
+                    tags: args.tags,
+                    due_at: args.due_at,
+                    scheduled_for: args.scheduled_for,
+                    recurrence: args.recurrence,
+                    project_id: args.project_id,
+                    parent_id: args.parent_id,
+                    goal_id: args.goal_id,
+                    inbox: args.inbox,
+                    estimate_minutes: args.estimate_minutes,
+                    quick_add: false,
+                },
+            )
+            .and_then(to_value)
+        }
+        "complete_task" => {
+            let args: TaskIdArgs = parse_args(name, arguments)?;
+            complete_task(conn, args.task_id).and_then(to_value)
+        }
+        "quick_add_task" => {
+            let request: QuickAddRequest = parse_args(name, arguments)?;
+            quick_add::quick_add_task(conn, request).and_then(to_value)
+        }
+        "triage_task" => {
+            let args: TriageTaskArgs = parse_args(name, arguments)?;
+            inbox_service::triage_task(conn, args.task_id, args.triage).and_then(to_value)
+        }
+        "get_inbox_status" => inbox_service::get_inbox_status(conn).and_then(to_value),
+        "search" => {
+            let query: SearchQuery = parse_args(name, arguments)?;
+            search_service::search(conn, query).and_then(to_value)
+        }
+        "list_trash" => trash_service::list_trash(conn).and_then(to_value),
+        "restore_from_trash" => {
+            let args: RestoreArgs = parse_args(name, arguments)?;
+            match args.kind {
+                TrashKind::Task => trash_service::restore_task(conn, args.id).and_then(to_value),
+                TrashKind::Habit => trash_service::restore_habit(conn, args.id).and_then(to_value),
+            }
+        }
+        "list_projects" => project_service::list_projects(conn).and_then(to_value),
+        "get_task_tree" => {
+            let args: TaskIdArgs = parse_args(name, arguments)?;
+            task_service::get_task_tree(conn, args.task_id).and_then(to_value)
+        }
+        "list_goals" => {
+            let args: ListGoalsArgs = parse_args(name, arguments)?;
+            goal_service::list_goals(conn, args.level).and_then(to_value)
+        }
+        "update_key_result" => {
+            let args: UpdateKeyResultArgs = parse_args(name, arguments)?;
+            let request = UpdateKeyResultRequest {
+                current_value: Some(args.current_value),
+                ..Default::default()
+            };
+            goal_service::update_key_result(conn, args.key_result_id, request).and_then(to_value)
+        }
+        "list_habits" => habit_service::get_all_habits(conn).and_then(to_value),
+        "log_habit" => {
+            let args: LogHabitArgs = parse_args(name, arguments)?;
+            let date = match args.date {
+                Some(date) => Ok(date),
+                None => {
+                    settings_service::today(conn).map(|today| today.format("%Y-%m-%d").to_string())
+                }
+            };
+            date.and_then(|date| {
+                let request = CreateHabitEntryRequest {
+                    habit_id: args.habit_id,
+                    date,
+                    completed: args.completed,
+                    value: args.value,
+                };
+                habit_service::add_habit_entry(conn, request)
+            })
+            .and_then(to_value)
+        }
+        "get_character" => character_service::get_character(conn).and_then(to_value),
+        "get_active_quests" => quest_service::get_active_quests(conn).and_then(to_value),
+        "weekly_summary" => weekly_summary(conn).and_then(to_value),
+        name => {
+            return Err(RpcError::invalid_params(format!("Unknown tool: {}", name)));
+        }
+    })
+}
+
+/// Completes a task without reopening it when it is already done
+fn complete_task(conn: &Connection, task_id: i32) -> anyhow::Result<crate::models::Task> {
+    let task = task_service::get_task_by_id(conn, task_id)?;
+    if task.completed {
+        return Ok(task);
+    }
+
+    task_service::toggle_task_status(conn, task_id)
+}
+
+fn weekly_summary(conn: &Connection) -> anyhow::Result<WeeklySummary> {
+    let time_settings = settings_service::get_time_settings(conn)?;
+    let today = time_settings.day_of(Utc::now());
+    let week_start = today - Duration::days(today.weekday().num_days_from_monday().into());
+    let week_start_ts = time_settings.day_start_timestamp(week_start);
+
+    let tasks = task_service::get_all_tasks(conn)?;
+    let tasks_completed = tasks
+        .iter()
+        .filter(|task| task.completed && task.updated_at >= week_start_ts)
+        .count();
+    let tasks_open = tasks.iter().filter(|task| !task.completed).count();
+
+    let mut habit_entries_completed = 0;
+    for day in week_start.iter_days().take_while(|day| *day <= today) {
+        let date = day.format("%Y-%m-%d").to_string();
+        habit_entries_completed += habit_service::get_habit_entries_for_date(conn, &date)?
+            .iter()
+            .filter(|entry| entry.completed)
+            .count();
+    }
+
+    let quests = quest_service::get_quests_for_week(conn, None)?;
+    let quests_completed = quests
+        .iter()
+        .filter(|quest| matches!(quest.status, QuestStatus::Completed))
+        .count();
+
+    let achievements_earned =
+        achievement_service::get_achievements_by_status(conn, AchievementStatus::Earned)?
+            .into_iter()
+            .filter(|achievement| {
+                achievement
+                    .earned_at
+                    .is_some_and(|earned_at| earned_at >= week_start_ts)
+            })
+            .map(|achievement| achievement.name)
+            .collect();
+
+    // The character may not exist yet; the rest of the summary is still useful
+    let character = character_service::get_character(conn).ok();
+
+    Ok(WeeklySummary {
+        week_start: week_start.format("%Y-%m-%d").to_string(),
+        tasks_completed,
+        tasks_open,
+        habit_entries_completed,
+        quests_completed,
+        quests_total: quests.len(),
+        achievements_earned,
+        level: character.as_ref().map(|character| character.level),
+        experience: character.as_ref().map(|character| character.experience),
+    })
+}
@@ -1,14 +1,52 @@
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+/// Odróżnia brak pola (`None`) od jawnego `null` (`Some(None)`) w żądaniach aktualizacji
+fn deserialize_some<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
 
 /// Model reprezentujący zadanie w aplikacji
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Task {
     /// Unikalny identyfikator zadania
     pub id: i32,
     /// Tytuł/nazwa zadania
     pub title: String,
+    /// Notatki zadania
+    pub notes: Option<String>,
     /// Status ukończenia zadania
     pub completed: bool,
+    /// Priorytet zadania (wpływa na nagrodę EXP)
+    pub priority: TaskPriority,
+    /// Atrybut postaci rozwijany przez zadanie
+    pub attribute: Option<Attribute>,
+    /// Tagi zadania (małymi literami, posortowane)
+    pub tags: Vec<String>,
+    /// Termin wykonania (Unix timestamp)
+    pub due_at: Option<i64>,
+    /// Dzień, na który zaplanowano zadanie (YYYY-MM-DD)
+    pub scheduled_for: Option<String>,
+    /// ID serii, do której należy zadanie powtarzalne
+    pub recurrence_id: Option<i32>,
+    /// Reguła powtarzania aktywnej serii (RRULE, np. `FREQ=WEEKLY;BYDAY=MO`)
+    pub recurrence: Option<String>,
+    /// Projekt, do którego należy zadanie
+    pub project_id: Option<i32>,
+    /// Zadanie nadrzędne (dla podzadań)
+    pub parent_id: Option<i32>,
+    /// Cel, do którego przybliża zadanie (ukończenie daje więcej EXP)
+    pub goal_id: Option<i32>,
+    /// Zadanie czeka w skrzynce na przegląd
+    pub inbox: bool,
+    /// Szacowany czas wykonania w minutach
+    pub estimate_minutes: Option<i32>,
+    /// Liczba bezpośrednich podzadań
+    pub subtask_count: i32,
     /// Timestamp utworzenia zadania (Unix timestamp)
     pub created_at: i64,
     /// Timestamp ostatniej modyfikacji (Unix timestamp)
@@ -29,7 +67,21 @@ impl Task {
         Task {
             id: 0, // Będzie ustawione przez bazę danych
             title,
+            notes: None,
             completed: false,
+            priority: TaskPriority::default(),
+            attribute: None,
+            tags: Vec::new(),
+            due_at: None,
+            scheduled_for: None,
+            recurrence_id: None,
+            recurrence: None,
+            project_id: None,
+            parent_id: None,
+            goal_id: None,
+            inbox: false,
+            estimate_minutes: None,
+            subtask_count: 0,
             created_at: now,
             updated_at: now,
         }
@@ -66,32 +118,516 @@ impl Task {
     }
 }
 
+/// Priorytet zadania
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub enum TaskPriority {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Urgent,
+}
+
+impl TaskPriority {
+    /// Nazwa zapisywana w bazie danych
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskPriority::Low => "Low",
+            TaskPriority::Medium => "Medium",
+            TaskPriority::High => "High",
+            TaskPriority::Urgent => "Urgent",
+        }
+    }
+
+    /// Odczytuje priorytet z bazy danych (nieznane wartości traktowane jak `Medium`)
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "Low" => TaskPriority::Low,
+            "High" => TaskPriority::High,
+            "Urgent" => TaskPriority::Urgent,
+            _ => TaskPriority::Medium,
+        }
+    }
+}
+
+/// Atrybut postaci, który może rozwijać zadanie
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Attribute {
+    Strength,
+    Intelligence,
+    Charisma,
+    Dexterity,
+    Wisdom,
+    Constitution,
+}
+
+impl Attribute {
+    /// Nazwa atrybutu (zgodna z polami `CharacterAttributes`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Attribute::Strength => "strength",
+            Attribute::Intelligence => "intelligence",
+            Attribute::Charisma => "charisma",
+            Attribute::Dexterity => "dexterity",
+            Attribute::Wisdom => "wisdom",
+            Attribute::Constitution => "constitution",
+        }
+    }
+
+    /// Odczytuje atrybut z nazwy atrybutu
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "strength" => Some(Attribute::Strength),
+            "intelligence" => Some(Attribute::Intelligence),
+            "charisma" => Some(Attribute::Charisma),
+            "dexterity" => Some(Attribute::Dexterity),
+            "wisdom" => Some(Attribute::Wisdom),
+            "constitution" => Some(Attribute::Constitution),
+            _ => None,
+        }
+    }
+
+    /// Rozpoznaje tag atrybutu (`strength`) lub klasy postaci (`warrior` → siła)
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let tag = tag.to_lowercase();
+        Attribute::from_name(&tag).or(match tag.as_str() {
+            "warrior" => Some(Attribute::Strength),
+            "mage" => Some(Attribute::Intelligence),
+            "bard" => Some(Attribute::Charisma),
+            "rogue" => Some(Attribute::Dexterity),
+            _ => None,
+        })
+    }
+}
+
+/// Tag zadań wraz z liczbą oznaczonych nim zadań
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Tag {
+    pub id: i32,
+    pub name: String,
+    pub task_count: i32,
+}
+
+/// Zadanie wraz z drzewem podzadań i zagregowanym postępem
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TaskNode {
+    #[serde(flatten)]
+    pub task: Task,
+    /// Procent ukończenia (0-100); dla zadań z podzadaniami średnia ich postępu
+    pub progress: f64,
+    #[schema(no_recursion)]
+    pub subtasks: Vec<TaskNode>,
+}
+
+/// Projekt - nazwany kontener zadań
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Project {
+    pub id: i32,
+    pub name: String,
+    /// Kolor w formacie `#RRGGBB`
+    pub color: String,
+    /// Atrybut rozwijany przez zadania projektu, które nie mają własnego
+    pub attribute: Option<Attribute>,
+    /// Liczba zadań projektu (razem z podzadaniami)
+    pub task_count: i32,
+    /// Liczba ukończonych zadań projektu (razem z podzadaniami)
+    pub completed_count: i32,
+    /// Procent ukończenia (0-100) - średni postęp zadań najwyższego poziomu
+    pub progress: f64,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Dane do utworzenia projektu
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct CreateProjectRequest {
+    pub name: String,
+    /// Kolor `#RRGGBB` (domyślnie `#6366f1`)
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default)]
+    pub attribute: Option<Attribute>,
+}
+
+/// Dane do aktualizacji projektu (pominięte pola pozostają bez zmian)
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct UpdateProjectRequest {
+    pub name: Option<String>,
+    pub color: Option<String>,
+    /// `null` usuwa atrybut
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub attribute: Option<Option<Attribute>>,
+}
+
+/// Poziom celu w drzewie Wizja → Roczny → Kwartalny → Miesięczny
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub enum GoalLevel {
+    Vision,
+    Yearly,
+    Quarterly,
+    Monthly,
+}
+
+impl GoalLevel {
+    /// Nazwa zapisywana w bazie danych
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GoalLevel::Vision => "Vision",
+            GoalLevel::Yearly => "Yearly",
+            GoalLevel::Quarterly => "Quarterly",
+            GoalLevel::Monthly => "Monthly",
+        }
+    }
+
+    /// Odczytuje poziom z bazy danych (nieznane wartości traktowane jak `Monthly`)
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "Vision" => GoalLevel::Vision,
+            "Yearly" => GoalLevel::Yearly,
+            "Quarterly" => GoalLevel::Quarterly,
+            _ => GoalLevel::Monthly,
+        }
+    }
+
+    /// Głębokość w drzewie celów (0 = wizja); rodzic musi mieć mniejszą
+    pub fn rank(&self) -> u8 {
+        match self {
+            GoalLevel::Vision => 0,
+            GoalLevel::Yearly => 1,
+            GoalLevel::Quarterly => 2,
+            GoalLevel::Monthly => 3,
+        }
+    }
+}
+
+/// Status celu
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub enum GoalStatus {
+    #[default]
+    Active,
+    /// Osiągnięty ręcznie lub po dojściu postępu do 100%
+    Completed,
+    /// Porzucony - nie jest już rozwijany
+    Abandoned,
+}
+
+impl GoalStatus {
+    /// Nazwa zapisywana w bazie danych
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GoalStatus::Active => "Active",
+            GoalStatus::Completed => "Completed",
+            GoalStatus::Abandoned => "Abandoned",
+        }
+    }
+
+    /// Odczytuje status z bazy danych (nieznane wartości traktowane jak `Active`)
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "Completed" => GoalStatus::Completed,
+            "Abandoned" => GoalStatus::Abandoned,
+            _ => GoalStatus::Active,
+        }
+    }
+}
+
+/// Kluczowy rezultat - mierzalna wartość docelowa celu (OKR)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct KeyResult {
+    pub id: i32,
+    pub goal_id: i32,
+    pub title: String,
+    pub target_value: f64,
+    pub current_value: f64,
+    /// Jednostka wartości (np. `km`, `książki`)
+    pub unit: Option<String>,
+    /// Procent realizacji (0-100)
+    pub progress: f64,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Cel wraz z kluczowymi rezultatami i postępem
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Goal {
+    pub id: i32,
+    pub title: String,
+    pub description: Option<String>,
+    pub level: GoalLevel,
+    /// Cel nadrzędny (wyższego poziomu)
+    pub parent_id: Option<i32>,
+    /// Termin realizacji (Unix timestamp)
+    pub deadline: Option<i64>,
+    pub status: GoalStatus,
+    pub key_results: Vec<KeyResult>,
+    /// Liczba zadań powiązanych bezpośrednio z celem
+    pub task_count: i32,
+    /// Liczba ukończonych zadań powiązanych z celem
+    pub completed_task_count: i32,
+    /// Procent ukończenia (0-100) - średnia kluczowych rezultatów, zadań i podcelów
+    pub progress: f64,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Cel wraz z drzewem podcelów
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct GoalNode {
+    #[serde(flatten)]
+    pub goal: Goal,
+    #[schema(no_recursion)]
+    pub children: Vec<GoalNode>,
+}
+
+/// Dane do utworzenia celu
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateGoalRequest {
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub level: GoalLevel,
+    /// Cel nadrzędny - musi mieć wyższy poziom
+    #[serde(default)]
+    pub parent_id: Option<i32>,
+    /// Termin realizacji (Unix timestamp)
+    #[serde(default)]
+    pub deadline: Option<i64>,
+}
+
+/// Dane do aktualizacji celu (pominięte pola pozostają bez zmian)
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct UpdateGoalRequest {
+    pub title: Option<String>,
+    /// `null` usuwa opis
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub description: Option<Option<String>>,
+    pub level: Option<GoalLevel>,
+    /// `null` czyni cel celem najwyższego poziomu
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub parent_id: Option<Option<i32>>,
+    /// `null` usuwa termin
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub deadline: Option<Option<i64>>,
+    pub status: Option<GoalStatus>,
+}
+
+/// Dane do dodania kluczowego rezultatu
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateKeyResultRequest {
+    pub title: String,
+    /// Wartość docelowa (większa od zera)
+    pub target_value: f64,
+    /// Wartość początkowa (domyślnie 0)
+    #[serde(default)]
+    pub current_value: f64,
+    #[serde(default)]
+    pub unit: Option<String>,
+}
+
+/// Dane do aktualizacji kluczowego rezultatu (np. zapisania nowej wartości)
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct UpdateKeyResultRequest {
+    pub title: Option<String>,
+    pub target_value: Option<f64>,
+    pub current_value: Option<f64>,
+    /// `null` usuwa jednostkę
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub unit: Option<Option<String>>,
+}
+
 /// Struktura reprezentująca dane do utworzenia nowego zadania
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, ToSchema)]
 pub struct CreateTaskRequest {
     pub title: String,
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// Domyślnie `Medium`
+    #[serde(default)]
+    pub priority: TaskPriority,
+    #[serde(default)]
+    pub attribute: Option<Attribute>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Termin wykonania (Unix timestamp)
+    #[serde(default)]
+    pub due_at: Option<i64>,
+    /// Dzień, na który zaplanowano zadanie (YYYY-MM-DD)
+    #[serde(default)]
+    pub scheduled_for: Option<String>,
+    /// Reguła powtarzania (RRULE); ukończenie zadania tworzy kolejne wystąpienie
+    #[serde(default)]
+    pub recurrence: Option<String>,
+    /// Projekt zadania; podzadania dziedziczą projekt rodzica
+    #[serde(default)]
+    pub project_id: Option<i32>,
+    /// Zadanie nadrzędne - tworzy podzadanie
+    #[serde(default)]
+    pub parent_id: Option<i32>,
+    /// Cel, do którego przybliża zadanie
+    #[serde(default)]
+    pub goal_id: Option<i32>,
+    /// Czy zadanie trafia do skrzynki; domyślnie tak, gdy nie ma projektu,
+    /// rodzica, celu, terminów ani powtarzania (szybkie przechwycenie)
+    #[serde(default)]
+    pub inbox: Option<bool>,
+    /// Szacowany czas wykonania w minutach
+    #[serde(default)]
+    pub estimate_minutes: Option<i32>,
+    /// Parsuje składnię szybkiego dodawania w tytule (`jutro 18:00 #tag !high +Projekt ~30m`);
+    /// jawnie podane pola mają pierwszeństwo przed rozpoznanymi
+    #[serde(default)]
+    pub quick_add: bool,
 }
 
 /// Struktura reprezentująca dane do aktualizacji zadania
-#[derive(Debug, Deserialize)]
+///
+/// Pominięte pola pozostają bez zmian. Ukończenie zadania odbywa się przez
+/// przełączenie statusu, żeby przyznać EXP.
+#[derive(Debug, Default, Deserialize, ToSchema)]
 pub struct UpdateTaskRequest {
-    #[allow(dead_code)]
     pub title: Option<String>,
-    #[allow(dead_code)]
-    pub completed: Option<bool>,
+    /// `null` usuwa notatki
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub notes: Option<Option<String>>,
+    pub priority: Option<TaskPriority>,
+    /// `null` usuwa atrybut
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub attribute: Option<Option<Attribute>>,
+    /// Zastępuje wszystkie tagi zadania
+    pub tags: Option<Vec<String>>,
+    /// `null` usuwa termin
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub due_at: Option<Option<i64>>,
+    /// `null` usuwa zaplanowany dzień
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub scheduled_for: Option<Option<String>>,
+    /// Zmienia regułę powtarzania serii; `null` zatrzymuje serię
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub recurrence: Option<Option<String>>,
+    /// Przenosi zadanie (z podzadaniami) do projektu; `null` usuwa je z projektu
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub project_id: Option<Option<i32>>,
+    /// Przenosi zadanie pod inne zadanie; `null` czyni je zadaniem najwyższego poziomu
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub parent_id: Option<Option<i32>>,
+    /// Łączy zadanie z celem; `null` usuwa powiązanie
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub goal_id: Option<Option<i32>>,
+    /// Przenosi zadanie do skrzynki (`true`) lub z niej wyjmuje (`false`)
+    pub inbox: Option<bool>,
+    /// `null` usuwa szacowany czas
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub estimate_minutes: Option<Option<i32>>,
+}
+
+/// Tekst zadania w składni szybkiego dodawania
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct QuickAddRequest {
+    /// Np. `Trening jutro 18:00 #sport !high +Zdrowie ~30m`
+    pub text: String,
+}
+
+/// Pola rozpoznane w tekście szybkiego dodawania
+///
+/// Nierozpoznany tekst zostaje w tytule.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, ToSchema)]
+pub struct ParsedTask {
+    /// Tytuł po usunięciu rozpoznanych fragmentów
+    pub title: String,
+    /// Z `!low`, `!high`, `!pilny`, ...
+    pub priority: Option<TaskPriority>,
+    /// Z `#tag`
+    pub tags: Vec<String>,
+    /// Termin, gdy podano godzinę (`jutro 18:00`, Unix timestamp)
+    pub due_at: Option<i64>,
+    /// Dzień, gdy podano datę bez godziny (YYYY-MM-DD)
+    pub scheduled_for: Option<String>,
+    /// Projekt z `+Nazwa` (tylko istniejący)
+    pub project_id: Option<i32>,
+    /// Nazwa rozpoznanego projektu
+    pub project: Option<String>,
+    /// Szacowany czas z `~30m`, `~1h30m`
+    pub estimate_minutes: Option<i32>,
+}
+
+/// Zadanie utworzone przez szybkie dodawanie wraz z tym, co zostało rozpoznane
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct QuickAddResult {
+    pub task: Task,
+    pub parsed: ParsedTask,
+}
+
+/// Dane przeglądu zadania ze skrzynki
+///
+/// Wszystkie pola są stosowane jednym wywołaniem, a zadanie opuszcza skrzynkę
+/// (także gdy żadne pole nie zostało podane).
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct TriageTaskRequest {
+    pub project_id: Option<i32>,
+    pub goal_id: Option<i32>,
+    /// Dzień, na który zaplanowano zadanie (YYYY-MM-DD)
+    pub scheduled_for: Option<String>,
+    /// Termin wykonania (Unix timestamp)
+    pub due_at: Option<i64>,
+    /// Zastępuje tagi zadania
+    pub tags: Option<Vec<String>>,
+    pub priority: Option<TaskPriority>,
+}
+
+/// Stan skrzynki zadań
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct InboxStatus {
+    /// Liczba nieukończonych zadań w skrzynce
+    pub count: i32,
+    /// Liczba dni, w których skrzynka została opróżniona
+    pub inbox_zero_count: i32,
+    /// Ostatni dzień z pustą skrzynką (YYYY-MM-DD)
+    pub last_inbox_zero: Option<String>,
 }
 
 /// Typ nawyku określający jak jest śledzony
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
 pub enum HabitType {
     /// Nawyk typu tak/nie (np. "Czy medytowałem dzisiaj?")
+    #[default]
     Boolean,
     /// Nawyk typu licznik (np. "Ile szklanek wody wypiłem?")
     Counter,
 }
 
+/// Harmonogram nawyku - w które dni oczekiwane jest jego wykonanie
+///
+/// Streak liczy kolejne terminy z harmonogramu, więc dni spoza harmonogramu go nie przerywają.
+/// Dla `TimesPerWeek` streak jest liczony w tygodniach (od poniedziałku).
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type")]
+pub enum HabitSchedule {
+    /// Codziennie
+    #[default]
+    Daily,
+    /// W wybrane dni tygodnia (1 = poniedziałek ... 7 = niedziela)
+    Weekdays { days: Vec<u32> },
+    /// Określoną liczbę razy w tygodniu, w dowolne dni
+    TimesPerWeek { times: u32 },
+    /// Co N dni (kolejne wykonanie najpóźniej N dni po poprzednim)
+    EveryNDays { interval: u32 },
+}
+
+impl HabitSchedule {
+    /// Ile wykonań harmonogram przewiduje w ciągu tygodnia
+    pub fn occurrences_per_week(&self) -> i32 {
+        match self {
+            HabitSchedule::Daily => 7,
+            HabitSchedule::Weekdays { days } => days.len() as i32,
+            HabitSchedule::TimesPerWeek { times } => *times as i32,
+            HabitSchedule::EveryNDays { interval } => 7u32.div_ceil(*interval) as i32,
+        }
+    }
+}
+
 /// Model reprezentujący nawyk w aplikacji
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Habit {
     /// Unikalny identyfikator nawyku
     pub id: i32,
@@ -101,8 +637,14 @@ pub struct Habit {
     pub habit_type: HabitType,
     /// Wartość docelowa (dla typu counter, np. 8 szklanek wody)
     pub target_value: Option<i32>,
-    /// Aktualny ciąg dni (streak)
+    /// Harmonogram nawyku (domyślnie codziennie)
+    pub schedule: HabitSchedule,
+    /// Aktualny streak (kolejne terminy z harmonogramu)
     pub current_streak: i32,
+    /// Najdłuższy osiągnięty streak
+    pub best_streak: i32,
+    /// Łączna liczba dni z wykonanym nawykiem
+    pub total_completions: i32,
     /// Timestamp utworzenia nawyku (Unix timestamp)
     pub created_at: i64,
     /// Timestamp ostatniej modyfikacji (Unix timestamp)
@@ -116,10 +658,16 @@ impl Habit {
     /// * `title` - Nazwa nawyku
     /// * `habit_type` - Typ nawyku (Boolean lub Counter)
     /// * `target_value` - Wartość docelowa (tylko dla typu Counter)
+    /// * `schedule` - Harmonogram nawyku
     ///
     /// # Returns
     /// * `Habit` - Nowa instancja nawyku z wartościami domyślnymi
-    pub fn new(title: String, habit_type: HabitType, target_value: Option<i32>) -> Self {
+    pub fn new(
+        title: String,
+        habit_type: HabitType,
+        target_value: Option<i32>,
+        schedule: HabitSchedule,
+    ) -> Self {
         let now = chrono::Utc::now().timestamp();
 
         Habit {
@@ -127,7 +675,10 @@ impl Habit {
             title,
             habit_type,
             target_value,
+            schedule,
             current_streak: 0,
+            best_streak: 0,
+            total_completions: 0,
             created_at: now,
             updated_at: now,
         }
@@ -160,10 +711,40 @@ impl Habit {
         self.current_streak = new_streak;
         self.updated_at = chrono::Utc::now().timestamp();
     }
+
+    /// Sprawdza, czy wpis spełnia cel nawyku
+    ///
+    /// Nawyk typu Boolean wymaga zaznaczenia, a licznik - osiągnięcia wartości docelowej
+    /// (lub dowolnej wartości dodatniej, gdy cel nie jest ustawiony).
+    pub fn is_entry_completed(&self, entry: &HabitEntry) -> bool {
+        match self.habit_type {
+            HabitType::Boolean => entry.completed,
+            HabitType::Counter => match self.target_value {
+                Some(target) => entry.value >= target,
+                None => entry.value > 0,
+            },
+        }
+    }
+
+    /// Oblicza stopień wykonania wpisu (0.0 - 1.0)
+    ///
+    /// Wynosi 1.0 dokładnie wtedy, gdy wpis spełnia cel (`is_entry_completed`);
+    /// licznik poniżej celu daje część proporcjonalną do wartości.
+    pub fn entry_intensity(&self, entry: &HabitEntry) -> f64 {
+        if self.is_entry_completed(entry) {
+            return 1.0;
+        }
+        match (&self.habit_type, self.target_value) {
+            (HabitType::Counter, Some(target)) if target > 0 => {
+                f64::from(entry.value.max(0)) / f64::from(target)
+            }
+            _ => 0.0,
+        }
+    }
 }
 
 /// Model reprezentujący wpis nawyku na konkretny dzień
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct HabitEntry {
     /// Unikalny identyfikator wpisu
     pub id: i32,
@@ -175,6 +756,8 @@ pub struct HabitEntry {
     pub completed: bool,
     /// Wartość dla nawyków typu Counter
     pub value: i32,
+    /// Czy dzień został pokryty żetonem zamrożenia streaka (zamiast wykonania)
+    pub frozen: bool,
     /// Timestamp utworzenia wpisu
     pub created_at: i64,
 }
@@ -199,24 +782,30 @@ impl HabitEntry {
             date,
             completed,
             value,
+            frozen: false,
             created_at: now,
         }
     }
 }
 
 /// Struktura reprezentująca dane do utworzenia nowego nawyku
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize, ToSchema)]
 pub struct CreateHabitRequest {
     pub title: String,
     pub habit_type: HabitType,
     pub target_value: Option<i32>,
+    /// Harmonogram nawyku (domyślnie codziennie)
+    #[serde(default)]
+    pub schedule: HabitSchedule,
 }
 
 /// Struktura reprezentująca dane do aktualizacji nawyku
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateHabitRequest {
     pub title: Option<String>,
     pub target_value: Option<i32>,
+    /// Nowy harmonogram (streak jest przeliczany)
+    pub schedule: Option<HabitSchedule>,
 }
 
 /// Struktura reprezentująca dane do utworzenia wpisu nawyku
@@ -228,8 +817,95 @@ pub struct CreateHabitEntryRequest {
     pub value: Option<i32>,
 }
 
+/// Wykonanie nawyku w ostatnich N dniach
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct HabitPeriodStats {
+    /// Długość okresu w dniach (7, 30 lub 90)
+    pub days: u32,
+    /// Liczba wykonań oczekiwana według harmonogramu
+    pub expected: u32,
+    /// Liczba wykonań zaliczonych do harmonogramu
+    pub completed: u32,
+    /// Odsetek wykonania (0.0 - 1.0)
+    pub completion_rate: f64,
+    /// Średnia dzienna wartość (tylko dla typu Counter)
+    pub average_value: Option<f64>,
+}
+
+/// Wykonanie nawyku w danym dniu tygodnia
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct WeekdayStats {
+    /// Dzień tygodnia (1 = poniedziałek ... 7 = niedziela)
+    pub weekday: u32,
+    /// Liczba takich dni od początku historii nawyku
+    pub days: u32,
+    /// Liczba dni z wykonanym nawykiem
+    pub completed: u32,
+    /// Odsetek wykonania (0.0 - 1.0)
+    pub completion_rate: f64,
+}
+
+/// Jedna seria (streak) z historii nawyku
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct StreakRun {
+    /// Dzień pierwszego wykonania w serii (YYYY-MM-DD)
+    pub start: String,
+    /// Dzień ostatniego wykonania w serii (YYYY-MM-DD)
+    pub end: String,
+    /// Długość serii w jednostkach harmonogramu (dni, wykonania lub tygodnie)
+    pub length: i32,
+    /// Czy seria wciąż trwa
+    pub active: bool,
+}
+
+/// Statystyki nawyku
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct HabitStats {
+    /// ID nawyku
+    pub habit_id: i32,
+    /// Aktualny streak
+    pub current_streak: i32,
+    /// Najdłuższy streak
+    pub best_streak: i32,
+    /// Łączna liczba dni z wykonanym nawykiem
+    pub total_completions: i32,
+    /// Wykonanie w ostatnich 7, 30 i 90 dniach
+    pub periods: Vec<HabitPeriodStats>,
+    /// Wykonanie w poszczególne dni tygodnia (od poniedziałku)
+    pub weekdays: Vec<WeekdayStats>,
+    /// Historia serii od najstarszej
+    pub streak_history: Vec<StreakRun>,
+}
+
+/// Parametry kalendarza wypełnienia nawyków
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct HeatmapQuery {
+    /// Pierwszy dzień (YYYY-MM-DD), domyślnie 364 dni przed `to`
+    pub from: Option<String>,
+    /// Ostatni dzień (YYYY-MM-DD), domyślnie dzisiaj
+    pub to: Option<String>,
+    /// Tylko ten nawyk (domyślnie wszystkie nawyki razem)
+    pub habit_id: Option<i32>,
+}
+
+/// Jeden dzień kalendarza wypełnienia
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct HeatmapDay {
+    /// Dzień (YYYY-MM-DD)
+    pub date: String,
+    /// Średni stopień wykonania nawyków tego dnia (0.0 - 1.0)
+    pub intensity: f64,
+    /// Liczba nawyków wykonanych tego dnia
+    pub completed: u32,
+    /// Liczba dni pokrytych żetonem zamrożenia
+    pub frozen: u32,
+    /// Liczba nawyków branych pod uwagę (zaplanowanych lub mających wpis tego dnia)
+    pub total: u32,
+}
+
 /// Klasy postaci reprezentujące różne obszary rozwoju
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub enum CharacterClass {
     /// Wojownik - rozwój fizyczny, sport, zdrowie
     Warrior,
@@ -242,7 +918,7 @@ pub enum CharacterClass {
 }
 
 /// Atrybuty postaci dla wykresu pajęczynowego
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CharacterAttributes {
     /// Siła fizyczna (sport, trening, zdrowie)
     pub strength: i32,
@@ -285,8 +961,11 @@ impl CharacterAttributes {
     }
 }
 
+/// Najwięcej żetonów zamrożenia streaka, jakie postać może mieć naraz
+pub const MAX_STREAK_FREEZES: i32 = 3;
+
 /// Model reprezentujący postać gracza w systemie RPG
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Character {
     /// Unikalny identyfikator postaci (zawsze 1 - jedna postać na użytkownika)
     pub id: i32,
@@ -298,6 +977,8 @@ pub struct Character {
     pub character_class: CharacterClass,
     /// Atrybuty postaci
     pub attributes: CharacterAttributes,
+    /// Żetony zamrożenia streaka (za każdy nowy poziom, najwyżej `MAX_STREAK_FREEZES`)
+    pub streak_freezes: i32,
     /// Timestamp utworzenia postaci
     pub created_at: i64,
     /// Timestamp ostatniej modyfikacji
@@ -315,6 +996,7 @@ impl Character {
             experience: 0,
             character_class,
             attributes: CharacterAttributes::new(),
+            streak_freezes: 0,
             created_at: now,
             updated_at: now,
         }
@@ -386,7 +1068,7 @@ pub struct UpdateCharacterRequest {
 }
 
 /// Status questu tygodniowego
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub enum QuestStatus {
     /// Quest aktywny - można go ukończyć
     Active,
@@ -397,7 +1079,7 @@ pub enum QuestStatus {
 }
 
 /// Typ questu określający jego kategorię
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub enum QuestType {
     /// Quest związany z zadaniami (np. "Ukończ 5 zadań z kategorii 'nauka'")
     Task,
@@ -405,10 +1087,14 @@ pub enum QuestType {
     Habit,
     /// Quest związany z postacią (np. "Zdobądź 100 EXP")
     Character,
+    /// Quest związany z celem kwartalnym (np. "Doprowadź cel do 60%")
+    Goal,
+    /// Quest zaległych zadań (np. "Ukończ 3 zadania po terminie")
+    Overdue,
 }
 
 /// Model reprezentujący quest tygodniowy
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Quest {
     /// Unikalny identyfikator questu
     pub id: i32,
@@ -426,6 +1112,8 @@ pub struct Quest {
     pub category: Option<String>,
     /// ID nawyku dla questów nawykowych (opcjonalne)
     pub habit_id: Option<i32>,
+    /// ID celu kwartalnego dla questów celów (opcjonalne)
+    pub goal_id: Option<i32>,
     /// Status questu
     pub status: QuestStatus,
     /// Nagroda EXP za ukończenie
@@ -464,6 +1152,7 @@ impl Quest {
             current_progress: 0,
             category,
             habit_id,
+            goal_id: None,
             status: QuestStatus::Active,
             reward_exp,
             deadline,
@@ -495,7 +1184,7 @@ impl Quest {
 }
 
 /// Typ odznaki/achievementu
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub enum AchievementType {
     /// Odznaka za streak nawyków
     HabitStreak,
@@ -505,10 +1194,12 @@ pub enum AchievementType {
     CharacterLevel,
     /// Odznaka za ukończone questy
     QuestCount,
+    /// Odznaka za opróżnienie skrzynki zadań (liczba dni z pustą skrzynką)
+    InboxZero,
 }
 
 /// Status odznaki
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub enum AchievementStatus {
     /// Odznaka zablokowana (wymagania niespełnione)
     Locked,
@@ -519,7 +1210,7 @@ pub enum AchievementStatus {
 }
 
 /// Model reprezentujący odznakę/achievement
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct Achievement {
     /// Unikalny identyfikator odznaki
     pub id: i32,
@@ -614,3 +1305,477 @@ pub struct CreateAchievementRequest {
     pub required_value: i32,
     pub icon: String,
 }
+
+/// Uprawnienie klucza API
+///
+/// Uprawnienia są hierarchiczne: `Admin` obejmuje `Write`, a `Write` obejmuje `Read`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ApiScope {
+    /// Odczyt danych (endpointy GET)
+    Read,
+    /// Modyfikacja danych (endpointy POST/PATCH/PUT/DELETE)
+    Write,
+    /// Pełny dostęp, w tym nieodwracalne operacje (opróżnianie kosza, trwałe usuwanie)
+    Admin,
+}
+
+impl ApiScope {
+    /// Zwraca nazwę uprawnienia zapisywaną w bazie danych
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiScope::Read => "Read",
+            ApiScope::Write => "Write",
+            ApiScope::Admin => "Admin",
+        }
+    }
+
+    /// Parsuje nazwę uprawnienia zapisaną w bazie danych
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "Read" => Some(ApiScope::Read),
+            "Write" => Some(ApiScope::Write),
+            "Admin" => Some(ApiScope::Admin),
+            _ => None,
+        }
+    }
+}
+
+/// Model reprezentujący klucz API (bez samego sekretu)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKey {
+    /// Unikalny identyfikator klucza
+    pub id: i32,
+    /// Nazwa nadana przez użytkownika (np. "Skrypt do nawyków")
+    pub name: String,
+    /// Początek klucza, pozwala rozpoznać klucz bez ujawniania go
+    pub key_prefix: String,
+    /// Uprawnienia przyznane kluczowi
+    pub scopes: Vec<ApiScope>,
+    /// Timestamp utworzenia klucza
+    pub created_at: i64,
+    /// Timestamp ostatniego użycia (opcjonalny)
+    pub last_used_at: Option<i64>,
+    /// Timestamp unieważnienia (None dla aktywnych kluczy)
+    pub revoked_at: Option<i64>,
+}
+
+impl ApiKey {
+    /// Sprawdza czy klucz posiada wymagane uprawnienie (z uwzględnieniem hierarchii)
+    pub fn grants(&self, required: ApiScope) -> bool {
+        self.revoked_at.is_none() && self.scopes.iter().any(|scope| *scope >= required)
+    }
+}
+
+/// Struktura reprezentująca dane do utworzenia nowego klucza API
+#[derive(Debug, Deserialize)]
+pub struct CreateApiKeyRequest {
+    pub name: String,
+    pub scopes: Vec<ApiScope>,
+}
+
+/// Nowo utworzony klucz API wraz z sekretem (zwracany tylko raz)
+#[derive(Debug, Clone, Serialize)]
+pub struct CreatedApiKey {
+    pub api_key: ApiKey,
+    /// Pełny klucz do użycia w nagłówku `Authorization: Bearer ...`
+    pub token: String,
+}
+
+/// Ustawienia serwera API zapisywane między uruchomieniami aplikacji
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiServerConfig {
+    /// Port, na którym serwer nasłuchuje
+    pub port: u16,
+    /// Czy serwer ma startować razem z aplikacją
+    pub auto_start: bool,
+}
+
+/// Ustawienia wyznaczające "dzisiaj" dla nawyków, streaków i questów
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimeSettings {
+    /// Strefa czasowa IANA (np. "Europe/Warsaw")
+    pub time_zone: String,
+    /// Godzina (0-23), o której zaczyna się nowy dzień - wcześniejsze wpisy liczą się do poprzedniego
+    pub day_rollover_hour: u32,
+}
+
+impl TimeSettings {
+    /// Zwraca strefę czasową (UTC, gdy nazwa jest niepoprawna)
+    fn tz(&self) -> chrono_tz::Tz {
+        self.time_zone.parse().unwrap_or(chrono_tz::Tz::UTC)
+    }
+
+    /// Zwraca czas lokalny w strefie użytkownika dla podanej chwili
+    pub fn local_time(&self, instant: chrono::DateTime<chrono::Utc>) -> chrono::NaiveDateTime {
+        instant.with_timezone(&self.tz()).naive_local()
+    }
+
+    /// Zwraca dzień, do którego należy podana chwila
+    pub fn day_of(&self, instant: chrono::DateTime<chrono::Utc>) -> chrono::NaiveDate {
+        (self.local_time(instant) - chrono::Duration::hours(i64::from(self.day_rollover_hour)))
+            .date()
+    }
+
+    /// Zwraca chwilę rozpoczęcia dnia (z uwzględnieniem godziny rozpoczęcia) jako Unix timestamp
+    pub fn day_start_timestamp(&self, day: chrono::NaiveDate) -> i64 {
+        self.local_timestamp(
+            day.and_time(chrono::NaiveTime::MIN)
+                + chrono::Duration::hours(i64::from(self.day_rollover_hour)),
+        )
+    }
+
+    /// Zamienia czas lokalny w strefie użytkownika na Unix timestamp
+    pub fn local_timestamp(&self, local: chrono::NaiveDateTime) -> i64 {
+        use chrono::TimeZone;
+
+        let tz = self.tz();
+
+        // Godzina z luki przy zmianie czasu nie istnieje - wtedy przesuwamy ją o godzinę później
+        tz.from_local_datetime(&local)
+            .earliest()
+            .or_else(|| {
+                tz.from_local_datetime(&(local + chrono::Duration::hours(1)))
+                    .earliest()
+            })
+            .map_or_else(
+                || local.and_utc().timestamp(),
+                |instant| instant.timestamp(),
+            )
+    }
+}
+
+/// Bieżący stan wbudowanego serwera API
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiServerStatus {
+    /// Czy serwer jest uruchomiony
+    pub running: bool,
+    /// Port działającego serwera (lub zapisany port, gdy serwer jest zatrzymany)
+    pub port: u16,
+    /// Timestamp uruchomienia serwera (None gdy zatrzymany)
+    pub started_at: Option<i64>,
+    /// Czas działania serwera w sekundach
+    pub uptime_seconds: i64,
+    /// Liczba obsłużonych zapytań od uruchomienia serwera
+    pub request_count: u64,
+    /// Czy serwer startuje razem z aplikacją
+    pub auto_start: bool,
+}
+
+/// Zdarzenie domenowe publikowane przez serwisy (np. do strumienia `/api/events`)
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(tag = "type")]
+pub enum DomainEvent {
+    /// Zadanie zostało oznaczone jako ukończone
+    TaskCompleted { task_id: i32, title: String },
+    /// Zapisano wpis nawyku
+    HabitLogged {
+        habit_id: i32,
+        title: String,
+        date: String,
+        completed: bool,
+        value: i32,
+    },
+    /// Zmieniła się seria (streak) nawyku
+    HabitStreakChanged {
+        habit_id: i32,
+        title: String,
+        previous_streak: i32,
+        current_streak: i32,
+    },
+    /// Postać otrzymała punkty doświadczenia
+    ExperienceGained {
+        amount: i64,
+        experience: i64,
+        level: i32,
+    },
+    /// Cofnięto nagrodę (np. odznaczono zadanie) - `amount` to odjęte EXP, poziom mógł spaść
+    ExperienceRevoked {
+        amount: i64,
+        experience: i64,
+        level: i32,
+    },
+    /// Postać awansowała na wyższy poziom
+    LevelUp { level: i32 },
+    /// Quest został ukończony (automatycznie lub ręcznie)
+    QuestCompleted {
+        quest_id: i32,
+        title: String,
+        reward_exp: i64,
+    },
+    /// Odznaka została zdobyta
+    AchievementEarned {
+        achievement_id: i32,
+        name: String,
+        bonus_exp: i64,
+    },
+}
+
+impl DomainEvent {
+    /// Nazwy wszystkich typów zdarzeń (np. do walidacji filtrów webhooków)
+    pub const NAMES: [&'static str; 8] = [
+        "TaskCompleted",
+        "HabitLogged",
+        "HabitStreakChanged",
+        "ExperienceGained",
+        "ExperienceRevoked",
+        "LevelUp",
+        "QuestCompleted",
+        "AchievementEarned",
+    ];
+
+    /// Zwraca nazwę typu zdarzenia (zgodną z polem `type` w JSON)
+    pub fn name(&self) -> &'static str {
+        match self {
+            DomainEvent::TaskCompleted { .. } => "TaskCompleted",
+            DomainEvent::HabitLogged { .. } => "HabitLogged",
+            DomainEvent::HabitStreakChanged { .. } => "HabitStreakChanged",
+            DomainEvent::ExperienceGained { .. } => "ExperienceGained",
+            DomainEvent::ExperienceRevoked { .. } => "ExperienceRevoked",
+            DomainEvent::LevelUp { .. } => "LevelUp",
+            DomainEvent::QuestCompleted { .. } => "QuestCompleted",
+            DomainEvent::AchievementEarned { .. } => "AchievementEarned",
+        }
+    }
+}
+
+/// Kierunek sortowania list
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+/// Pole, według którego sortowane są zadania
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskSortField {
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+    Title,
+}
+
+/// Filtry, sortowanie i stronicowanie listy zadań
+///
+/// Wszystkie pola są opcjonalne; domyślnie zwracane jest 50 najnowszych zadań.
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TaskQuery {
+    /// Tylko zadania z tego projektu
+    pub project_id: Option<i32>,
+    /// Tylko bezpośrednie podzadania tego zadania
+    pub parent_id: Option<i32>,
+    /// Tylko zadania powiązane z tym celem
+    pub goal_id: Option<i32>,
+    /// Tylko zadania ze skrzynki (`true`) lub spoza niej (`false`)
+    pub inbox: Option<bool>,
+    /// Tylko zadania z tym tagiem
+    pub tag: Option<String>,
+    /// Tylko zadania o tym priorytecie
+    pub priority: Option<TaskPriority>,
+    /// Tylko ukończone (`true`) lub nieukończone (`false`) zadania
+    pub completed: Option<bool>,
+    /// Utworzone w tym momencie lub później (timestamp)
+    pub created_after: Option<i64>,
+    /// Utworzone przed tym momentem (timestamp)
+    pub created_before: Option<i64>,
+    /// Zaktualizowane w tym momencie lub później (timestamp)
+    pub updated_after: Option<i64>,
+    /// Zaktualizowane przed tym momentem (timestamp)
+    pub updated_before: Option<i64>,
+    /// Fragment tytułu (bez rozróżniania wielkości liter)
+    pub search: Option<String>,
+    /// Pole sortowania (domyślnie `created_at`)
+    pub sort: Option<TaskSortField>,
+    /// Kierunek sortowania (domyślnie `desc`)
+    pub order: Option<SortOrder>,
+    /// Maksymalna liczba wyników (1-500, domyślnie 50)
+    pub limit: Option<u32>,
+    /// Kursor `next_cursor` z poprzedniej strony
+    pub cursor: Option<String>,
+}
+
+/// Pole, według którego sortowane są nawyki
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HabitSortField {
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+    Title,
+    CurrentStreak,
+}
+
+/// Filtry, sortowanie i stronicowanie listy nawyków
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct HabitQuery {
+    /// Tylko nawyki danego typu
+    pub habit_type: Option<HabitType>,
+    /// Utworzone w tym momencie lub później (timestamp)
+    pub created_after: Option<i64>,
+    /// Utworzone przed tym momentem (timestamp)
+    pub created_before: Option<i64>,
+    /// Zaktualizowane w tym momencie lub później (timestamp)
+    pub updated_after: Option<i64>,
+    /// Zaktualizowane przed tym momentem (timestamp)
+    pub updated_before: Option<i64>,
+    /// Fragment tytułu (bez rozróżniania wielkości liter)
+    pub search: Option<String>,
+    /// Pole sortowania (domyślnie `created_at`)
+    pub sort: Option<HabitSortField>,
+    /// Kierunek sortowania (domyślnie `desc`)
+    pub order: Option<SortOrder>,
+    /// Maksymalna liczba wyników (1-500, domyślnie 50)
+    pub limit: Option<u32>,
+    /// Kursor `next_cursor` z poprzedniej strony
+    pub cursor: Option<String>,
+}
+
+/// Jedna strona wyników listy
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Kursor następnej strony (None gdy to ostatnia strona)
+    pub next_cursor: Option<String>,
+}
+
+/// Rodzaj elementu znalezionego przez wyszukiwanie
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchKind {
+    Task,
+    Habit,
+    Quest,
+}
+
+impl SearchKind {
+    /// Nazwa zapisywana w indeksie wyszukiwania
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SearchKind::Task => "task",
+            SearchKind::Habit => "habit",
+            SearchKind::Quest => "quest",
+        }
+    }
+
+    /// Odczytuje rodzaj z indeksu wyszukiwania
+    pub fn from_db(value: &str) -> Option<Self> {
+        match value {
+            "task" => Some(SearchKind::Task),
+            "habit" => Some(SearchKind::Habit),
+            "quest" => Some(SearchKind::Quest),
+            _ => None,
+        }
+    }
+}
+
+/// Parametry wyszukiwania pełnotekstowego
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchQuery {
+    /// Szukane słowa (prefiksy, bez rozróżniania wielkości liter i polskich znaków)
+    pub q: String,
+    /// Tylko wyniki tego rodzaju
+    pub kind: Option<SearchKind>,
+    /// Maksymalna liczba wyników (1-100, domyślnie 20)
+    pub limit: Option<u32>,
+}
+
+/// Wynik wyszukiwania pełnotekstowego
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SearchResult {
+    pub kind: SearchKind,
+    /// ID zadania, nawyku lub questu
+    pub id: i32,
+    pub title: String,
+    /// Tytuł z dopasowaniami w `<mark>...</mark>`
+    pub title_highlight: String,
+    /// Fragment notatek zadania lub opisu questu z dopasowaniami w `<mark>...</mark>`
+    pub snippet: Option<String>,
+    /// Trafność BM25 (im mniejsza, tym lepsze dopasowanie)
+    pub rank: f64,
+}
+
+/// Rodzaj elementu w koszu
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TrashKind {
+    Task,
+    Habit,
+}
+
+/// Usunięte zadanie lub nawyk czekający w koszu na przywrócenie lub trwałe usunięcie
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TrashItem {
+    pub kind: TrashKind,
+    /// ID zadania lub nawyku (do przywrócenia lub usunięcia)
+    pub id: i32,
+    pub title: String,
+    /// Liczba podzadań usuniętych razem z zadaniem (dla nawyków - liczba wpisów)
+    pub item_count: i32,
+    /// Timestamp przeniesienia do kosza (Unix timestamp)
+    pub deleted_at: i64,
+    /// Timestamp, po którym element zostanie trwale usunięty (Unix timestamp)
+    pub purge_at: i64,
+}
+
+/// Model reprezentujący zarejestrowany webhook (bez sekretu)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Webhook {
+    /// Unikalny identyfikator webhooka
+    pub id: i32,
+    /// Adres, na który wysyłane są zdarzenia (http/https)
+    pub url: String,
+    /// Typy zdarzeń, które są wysyłane (pusta lista = wszystkie)
+    pub events: Vec<String>,
+    /// Czy żądania są podpisywane sekretem HMAC
+    pub has_secret: bool,
+    /// Czy webhook jest aktywny
+    pub active: bool,
+    /// Timestamp utworzenia
+    pub created_at: i64,
+    /// Timestamp ostatniej modyfikacji
+    pub updated_at: i64,
+}
+
+/// Struktura reprezentująca dane do utworzenia nowego webhooka
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    #[serde(default)]
+    pub events: Vec<String>,
+    pub secret: Option<String>,
+}
+
+/// Status dostarczenia webhooka
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DeliveryStatus {
+    /// Oczekuje na (kolejną) próbę wysłania
+    Pending,
+    /// Odbiorca odpowiedział kodem 2xx
+    Delivered,
+    /// Wszystkie próby zakończyły się błędem
+    Failed,
+}
+
+/// Wpis w dzienniku dostarczeń webhooka
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: i32,
+    pub webhook_id: i32,
+    pub event_type: String,
+    /// Wysłane ciało żądania (JSON)
+    pub payload: String,
+    pub status: DeliveryStatus,
+    /// Liczba wykonanych prób
+    pub attempts: i32,
+    /// Kod HTTP ostatniej próby (None przy błędzie połączenia)
+    pub response_status: Option<i32>,
+    /// Opis błędu ostatniej nieudanej próby
+    pub error: Option<String>,
+    pub created_at: i64,
+    pub last_attempt_at: Option<i64>,
+}
@@ -1,5 +1,7 @@
-use crate::models::{Achievement, AchievementStatus, AchievementType, CreateAchievementRequest};
-use crate::services::character_service;
+use crate::models::{
+    Achievement, AchievementStatus, AchievementType, CreateAchievementRequest, DomainEvent,
+};
+use crate::services::{character_service, error, events};
 use anyhow::Result;
 use rusqlite::Connection;
 
@@ -21,6 +23,7 @@ pub fn get_all_achievements(conn: &Connection) -> Result<Vec<Achievement>> {
             "TaskCount" => AchievementType::TaskCount,
             "CharacterLevel" => AchievementType::CharacterLevel,
             "QuestCount" => AchievementType::QuestCount,
+            "InboxZero" => AchievementType::InboxZero,
             _ => AchievementType::HabitStreak,
         };
 
@@ -81,6 +84,7 @@ pub fn get_achievements_by_status(
             "TaskCount" => AchievementType::TaskCount,
             "CharacterLevel" => AchievementType::CharacterLevel,
             "QuestCount" => AchievementType::QuestCount,
+            "InboxZero" => AchievementType::InboxZero,
             _ => AchievementType::HabitStreak,
         };
 
@@ -150,15 +154,20 @@ pub fn check_and_update_achievements(conn: &Connection) -> Result<Vec<Achievemen
 
             // Dodaj bonus EXP za zdobycie odznaki
             let bonus_exp = calculate_achievement_bonus_exp(&updated);
+            events::publish(DomainEvent::AchievementEarned {
+                achievement_id: updated.id,
+                name: updated.name.clone(),
+                bonus_exp,
+            });
             match character_service::add_experience(conn, bonus_exp) {
                 Ok((_, level_up)) => {
                     if level_up {
-                        println!(
+                        eprintln!(
                             "Level up! Achievement '{}' caused character to level up!",
                             updated.name
                         );
                     }
-                    println!(
+                    eprintln!(
                         "Achievement earned: '{}' - {} bonus EXP!",
                         updated.name, bonus_exp
                     );
@@ -189,7 +198,7 @@ fn check_achievement_requirements(conn: &Connection, achievement: &Achievement)
             // Sprawdź najdłuższy streak nawyków
             let max_streak: i32 = conn
                 .query_row(
-                    "SELECT COALESCE(MAX(current_streak), 0) FROM habits",
+                    "SELECT COALESCE(MAX(best_streak), 0) FROM habits WHERE deleted_at IS NULL",
                     [],
                     |row| row.get(0),
                 )
@@ -201,7 +210,7 @@ fn check_achievement_requirements(conn: &Connection, achievement: &Achievement)
             // Sprawdź liczbę ukończonych zadań
             let completed_count: i32 = conn
                 .query_row(
-                    "SELECT COUNT(*) FROM tasks WHERE completed = 1",
+                    "SELECT COUNT(*) FROM tasks WHERE completed = 1 AND deleted_at IS NULL",
                     [],
                     |row| row.get(0),
                 )
@@ -231,6 +240,14 @@ fn check_achievement_requirements(conn: &Connection, achievement: &Achievement)
 
             Ok(completed_quests >= achievement.required_value)
         }
+        AchievementType::InboxZero => {
+            // Sprawdź w ilu dniach skrzynka została opróżniona
+            let inbox_zero_days: i32 = conn
+                .query_row("SELECT COUNT(*) FROM inbox_zero_days", [], |row| row.get(0))
+                .unwrap_or(0);
+
+            Ok(inbox_zero_days >= achievement.required_value)
+        }
     }
 }
 
@@ -275,6 +292,14 @@ fn calculate_achievement_bonus_exp(achievement: &Achievement) -> i64 {
             // Bonusy za questy
             achievement.required_value as i64 * 25 // 25 EXP za quest
         }
+        AchievementType::InboxZero => {
+            // Bonusy za pustą skrzynkę: pierwszy raz = 30 EXP, 10 dni = 150 EXP
+            match achievement.required_value {
+                1 => 30,
+                10 => 150,
+                _ => achievement.required_value as i64 * 15, // 15 EXP za dzień
+            }
+        }
     }
 }
 
@@ -339,6 +364,7 @@ pub fn earn_achievement(conn: &Connection, achievement_id: i32) -> Result<Achiev
             "TaskCount" => AchievementType::TaskCount,
             "CharacterLevel" => AchievementType::CharacterLevel,
             "QuestCount" => AchievementType::QuestCount,
+            "InboxZero" => AchievementType::InboxZero,
             _ => AchievementType::HabitStreak,
         };
 
@@ -364,13 +390,13 @@ pub fn earn_achievement(conn: &Connection, achievement_id: i32) -> Result<Achiev
     })?;
 
     if !matches!(achievement.status, AchievementStatus::Available) {
-        return Err(anyhow::anyhow!("Achievement is not available to earn"));
+        return Err(error::conflict("Achievement is not available to earn"));
     }
 
     // Sprawdź czy wymagania są nadal spełnione
     if !check_achievement_requirements(conn, &achievement)? {
-        return Err(anyhow::anyhow!(
-            "Achievement requirements are no longer met"
+        return Err(error::conflict(
+            "Achievement requirements are no longer met",
         ));
     }
 
@@ -382,15 +408,20 @@ pub fn earn_achievement(conn: &Connection, achievement_id: i32) -> Result<Achiev
 
     // Daj bonus EXP
     let bonus_exp = calculate_achievement_bonus_exp(&achievement);
+    events::publish(DomainEvent::AchievementEarned {
+        achievement_id: achievement.id,
+        name: achievement.name.clone(),
+        bonus_exp,
+    });
     match character_service::add_experience(conn, bonus_exp) {
         Ok((_, level_up)) => {
             if level_up {
-                println!(
+                eprintln!(
                     "Level up! Achievement '{}' caused character to level up!",
                     achievement.name
                 );
             }
-            println!(
+            eprintln!(
                 "Achievement earned: '{}' - {} bonus EXP!",
                 achievement.name, bonus_exp
             );
@@ -436,6 +467,7 @@ pub fn add_achievement(
                 AchievementType::TaskCount => "TaskCount",
                 AchievementType::CharacterLevel => "CharacterLevel",
                 AchievementType::QuestCount => "QuestCount",
+                AchievementType::InboxZero => "InboxZero",
             },
             achievement.required_value,
             &achievement.icon,
@@ -0,0 +1,273 @@
+use anyhow::Result;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use rusqlite::{Connection, OptionalExtension, Row};
+use sha2::{Digest, Sha256};
+
+use crate::models::{ApiKey, ApiScope, CreateApiKeyRequest, CreatedApiKey};
+use crate::services::error;
+
+/// Prefiks każdego klucza, ułatwia rozpoznanie go np. w plikach konfiguracyjnych
+const TOKEN_PREFIX: &str = "pdrpg_";
+/// Liczba losowych znaków klucza (alfanumeryczne, ~190 bitów entropii)
+const TOKEN_RANDOM_LENGTH: usize = 32;
+/// Liczba znaków klucza zapisywana jawnie do wyświetlania w UI
+const DISPLAY_PREFIX_LENGTH: usize = 12;
+
+/// Konwertuje wiersz bazy danych na obiekt ApiKey
+fn row_to_api_key(row: &Row) -> Result<ApiKey, rusqlite::Error> {
+    let scopes_str: String = row.get("scopes")?;
+    let scopes = scopes_str.split(',').filter_map(ApiScope::parse).collect();
+
+    Ok(ApiKey {
+        id: row.get("id")?,
+        name: row.get("name")?,
+        key_prefix: row.get("key_prefix")?,
+        scopes,
+        created_at: row.get("created_at")?,
+        last_used_at: row.get("last_used_at")?,
+        revoked_at: row.get("revoked_at")?,
+    })
+}
+
+/// Zwraca hash SHA-256 klucza w postaci szesnastkowej
+fn hash_token(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Tworzy nowy klucz API
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `request` - Nazwa i uprawnienia nowego klucza
+///
+/// # Returns
+/// * `Result<CreatedApiKey>` - Zapisany klucz wraz z sekretem (jedyny moment, gdy jest dostępny)
+pub fn create_api_key(conn: &Connection, request: CreateApiKeyRequest) -> Result<CreatedApiKey> {
+    let name = request.name.trim().to_string();
+    if name.is_empty() {
+        return Err(error::validation("API key name cannot be empty"));
+    }
+    if name.len() > 50 {
+        return Err(error::validation(
+            "API key name is too long (max 50 characters)",
+        ));
+    }
+
+    let mut scopes = request.scopes;
+    scopes.sort();
+    scopes.dedup();
+    if scopes.is_empty() {
+        return Err(error::validation("API key needs at least one scope"));
+    }
+
+    let random: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(TOKEN_RANDOM_LENGTH)
+        .map(char::from)
+        .collect();
+    let token = format!("{}{}", TOKEN_PREFIX, random);
+    let key_prefix = token[..DISPLAY_PREFIX_LENGTH].to_string();
+    let scopes_str = scopes
+        .iter()
+        .map(|scope| scope.as_str())
+        .collect::<Vec<_>>()
+        .join(",");
+    let now = chrono::Utc::now().timestamp();
+
+    conn.execute(
+        "INSERT INTO api_keys (name, key_hash, key_prefix, scopes, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        (&name, hash_token(&token), &key_prefix, &scopes_str, now),
+    )?;
+
+    let api_key = ApiKey {
+        id: conn.last_insert_rowid() as i32,
+        name,
+        key_prefix,
+        scopes,
+        created_at: now,
+        last_used_at: None,
+        revoked_at: None,
+    };
+
+    Ok(CreatedApiKey { api_key, token })
+}
+
+/// Pobiera wszystkie klucze API (również unieważnione)
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+///
+/// # Returns
+/// * `Result<Vec<ApiKey>>` - Lista kluczy lub błąd
+pub fn list_api_keys(conn: &Connection) -> Result<Vec<ApiKey>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name, key_prefix, scopes, created_at, last_used_at, revoked_at
+         FROM api_keys ORDER BY created_at DESC, id DESC",
+    )?;
+
+    let key_iter = stmt.query_map([], row_to_api_key)?;
+
+    let mut keys = Vec::new();
+    for key in key_iter {
+        keys.push(key?);
+    }
+
+    Ok(keys)
+}
+
+/// Unieważnia klucz API
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `key_id` - ID klucza do unieważnienia
+///
+/// # Returns
+/// * `Result<()>` - Sukces lub błąd
+pub fn revoke_api_key(conn: &Connection, key_id: i32) -> Result<()> {
+    let now = chrono::Utc::now().timestamp();
+    let rows_affected = conn.execute(
+        "UPDATE api_keys SET revoked_at = ?1 WHERE id = ?2 AND revoked_at IS NULL",
+        (now, key_id),
+    )?;
+
+    if rows_affected == 0 {
+        return Err(error::not_found(format!(
+            "Active API key with id {} not found",
+            key_id
+        )));
+    }
+
+    Ok(())
+}
+
+/// Weryfikuje klucz przesłany przez klienta API
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `token` - Pełny klucz z nagłówka `Authorization`
+///
+/// # Returns
+/// * `Result<Option<ApiKey>>` - Aktywny klucz lub None jeśli klucz jest nieznany/unieważniony
+pub fn authenticate(conn: &Connection, token: &str) -> Result<Option<ApiKey>> {
+    let api_key = conn
+        .query_row(
+            "SELECT id, name, key_prefix, scopes, created_at, last_used_at, revoked_at
+             FROM api_keys WHERE key_hash = ?1 AND revoked_at IS NULL",
+            [hash_token(token)],
+            row_to_api_key,
+        )
+        .optional()?;
+
+    if let Some(mut api_key) = api_key {
+        let now = chrono::Utc::now().timestamp();
+        conn.execute(
+            "UPDATE api_keys SET last_used_at = ?1 WHERE id = ?2",
+            (now, api_key.id),
+        )?;
+        api_key.last_used_at = Some(now);
+
+        return Ok(Some(api_key));
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    fn create_test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+
+        conn.execute(
+            "CREATE TABLE api_keys (
+                id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                name TEXT NOT NULL,
+                key_hash TEXT NOT NULL UNIQUE,
+                key_prefix TEXT NOT NULL,
+                scopes TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                last_used_at INTEGER,
+                revoked_at INTEGER
+            )",
+            [],
+        )
+        .unwrap();
+
+        conn
+    }
+
+    #[test]
+    fn test_create_and_authenticate_api_key() {
+        let conn = create_test_db();
+
+        let created = create_api_key(
+            &conn,
+            CreateApiKeyRequest {
+                name: "Habit script".to_string(),
+                scopes: vec![ApiScope::Write, ApiScope::Read, ApiScope::Write],
+            },
+        )
+        .unwrap();
+
+        assert!(created.token.starts_with(TOKEN_PREFIX));
+        assert_eq!(
+            created.api_key.scopes,
+            vec![ApiScope::Read, ApiScope::Write]
+        );
+        assert!(created.token.starts_with(&created.api_key.key_prefix));
+
+        let authenticated = authenticate(&conn, &created.token).unwrap().unwrap();
+        assert_eq!(authenticated.id, created.api_key.id);
+        assert!(authenticated.last_used_at.is_some());
+        assert!(authenticated.grants(ApiScope::Read));
+        assert!(authenticated.grants(ApiScope::Write));
+        assert!(!authenticated.grants(ApiScope::Admin));
+
+        assert!(authenticate(&conn, "pdrpg_wrong").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_revoke_api_key() {
+        let conn = create_test_db();
+
+        let created = create_api_key(
+            &conn,
+            CreateApiKeyRequest {
+                name: "Dashboard".to_string(),
+                scopes: vec![ApiScope::Read],
+            },
+        )
+        .unwrap();
+
+        revoke_api_key(&conn, created.api_key.id).unwrap();
+
+        assert!(authenticate(&conn, &created.token).unwrap().is_none());
+        assert!(revoke_api_key(&conn, created.api_key.id).is_err());
+
+        let keys = list_api_keys(&conn).unwrap();
+        assert_eq!(keys.len(), 1);
+        assert!(keys[0].revoked_at.is_some());
+    }
+
+    #[test]
+    fn test_create_api_key_requires_scope() {
+        let conn = create_test_db();
+
+        let result = create_api_key(
+            &conn,
+            CreateApiKeyRequest {
+                name: "No scopes".to_string(),
+                scopes: vec![],
+            },
+        );
+
+        assert!(result.is_err());
+    }
+}
@@ -1,9 +1,11 @@
 use anyhow::Result;
-use rusqlite::{params, Connection, Row};
+use rusqlite::{params, Connection, OptionalExtension, Row};
 
 use crate::models::{
-    Character, CharacterAttributes, CharacterClass, CreateCharacterRequest, UpdateCharacterRequest,
+    Attribute, Character, CharacterAttributes, CharacterClass, CreateCharacterRequest, DomainEvent,
+    Task, TaskPriority, UpdateCharacterRequest, MAX_STREAK_FREEZES,
 };
+use crate::services::{events, project_service};
 
 /// Konwertuje wiersz bazy danych na obiekt Character
 fn row_to_character(row: &Row) -> Result<Character, rusqlite::Error> {
@@ -31,6 +33,7 @@ fn row_to_character(row: &Row) -> Result<Character, rusqlite::Error> {
         experience: row.get("experience")?,
         character_class,
         attributes,
+        streak_freezes: row.get("streak_freezes")?,
         created_at: row.get("created_at")?,
         updated_at: row.get("updated_at")?,
     })
@@ -41,7 +44,7 @@ pub fn get_character(connection: &Connection) -> Result<Character> {
     let mut stmt = connection.prepare(
         "SELECT id, level, experience, character_class, 
                 strength, intelligence, charisma, dexterity, wisdom, constitution,
-                created_at, updated_at 
+                streak_freezes, created_at, updated_at 
          FROM characters WHERE id = 1",
     )?;
 
@@ -121,6 +124,26 @@ pub fn update_character(
 /// # Returns
 /// * `Result<(Character, bool)>` - Aktualna postać i informacja czy nastąpił awans poziomu
 pub fn add_experience(connection: &Connection, exp_points: i64) -> Result<(Character, bool)> {
+    let (character, level_up) = save_experience(connection, exp_points)?;
+
+    events::publish(DomainEvent::ExperienceGained {
+        amount: exp_points,
+        experience: character.experience,
+        level: character.level,
+    });
+    if level_up {
+        events::publish(DomainEvent::LevelUp {
+            level: character.level,
+        });
+    }
+
+    Ok((character, level_up))
+}
+
+/// Zmienia doświadczenie postaci bez publikowania zdarzeń
+///
+/// Wspólna część przyznawania i cofania EXP: zapis poziomu, żetony zamrożenia i progres questów.
+fn save_experience(connection: &Connection, exp_points: i64) -> Result<(Character, bool)> {
     let mut character = get_character(connection)?;
     let level_up = character.add_experience(exp_points);
 
@@ -130,10 +153,22 @@ pub fn add_experience(connection: &Connection, exp_points: i64) -> Result<(Chara
         params![character.level, character.experience, character.updated_at],
     )?;
 
-    // Automatycznie aktualizuj progress questów po zdobyciu EXP
+    if level_up {
+        // Żeton zamrożenia za każdy poziom osiągnięty po raz pierwszy (ponowny awans po cofnięciu nagrody nic nie daje)
+        connection.execute(
+            "UPDATE characters
+             SET streak_freezes = MIN(streak_freezes + MAX(?1 - highest_level, 0), ?2),
+                 highest_level = MAX(highest_level, ?1)
+             WHERE id = 1",
+            params![character.level, MAX_STREAK_FREEZES],
+        )?;
+        character.streak_freezes = get_character(connection)?.streak_freezes;
+    }
+
+    // Automatycznie aktualizuj progress questów po zmianie EXP
     if let Err(e) = crate::services::quest_service::update_all_quest_progress(connection) {
         eprintln!(
-            "Warning: Failed to update quest progress after EXP gain: {}",
+            "Warning: Failed to update quest progress after EXP change: {}",
             e
         );
     }
@@ -181,60 +216,62 @@ pub fn add_attribute_points(
     Ok(character)
 }
 
+/// Zużywa żetony zamrożenia streaka
+///
+/// # Arguments
+/// * `connection` - Połączenie z bazą danych
+/// * `count` - Liczba żetonów do zużycia
+///
+/// # Returns
+/// * `Result<bool>` - True jeśli postać miała wystarczająco żetonów (wtedy zostały zużyte)
+pub fn use_streak_freezes(connection: &Connection, count: i32) -> Result<bool> {
+    let updated = connection.execute(
+        "UPDATE characters SET streak_freezes = streak_freezes - ?1, updated_at = ?2
+         WHERE id = 1 AND streak_freezes >= ?1",
+        params![count, chrono::Utc::now().timestamp()],
+    )?;
+
+    Ok(updated == 1)
+}
+
+/// Dodatkowe EXP za każde podzadanie ukończonego zadania nadrzędnego
+const SUBTASK_BONUS_EXP: i64 = 5;
+/// Górna granica dodatku za podzadania
+const MAX_SUBTASK_BONUS_EXP: i64 = 50;
+
 /// Oblicza punkty EXP za ukończenie zadania
 ///
+/// Nagroda zależy od priorytetu (zadania nadrzędne dostają dodatek za każde
+/// podzadanie), a rozwijany atrybut pochodzi z pola `attribute`
+/// zadania lub - gdy go brak - z pierwszego tagu atrybutu albo klasy (`strength`, `mage`, ...).
+///
 /// # Arguments
-/// * `task_title` - Tytuł zadania (może zawierać tagi klas)
+/// * `task` - Ukończone zadanie
 /// * `is_goal_related` - Czy zadanie jest powiązane z celem
 ///
 /// # Returns
 /// * `(i64, Option<String>)` - Punkty EXP i opcjonalny atrybut do zwiększenia
-pub fn calculate_task_exp(task_title: &str, is_goal_related: bool) -> (i64, Option<String>) {
-    let base_exp = if is_goal_related { 25 } else { 15 };
-    let title_lower = task_title.to_lowercase();
-
-    // Analiza tagów/słów kluczowych w tytule zadania
-    let attribute = if title_lower.contains("sport")
-        || title_lower.contains("trening")
-        || title_lower.contains("ćwiczenia")
-        || title_lower.contains("fitness")
-    {
-        Some("strength".to_string())
-    } else if title_lower.contains("nauka")
-        || title_lower.contains("książka")
-        || title_lower.contains("kurs")
-        || title_lower.contains("czytanie")
-    {
-        Some("intelligence".to_string())
-    } else if title_lower.contains("prezentacja")
-        || title_lower.contains("spotkanie")
-        || title_lower.contains("kontakt")
-        || title_lower.contains("rozmowa")
-    {
-        Some("charisma".to_string())
-    } else if title_lower.contains("hobby")
-        || title_lower.contains("praktyka")
-        || title_lower.contains("umiejętność")
-        || title_lower.contains("projekt")
-    {
-        Some("dexterity".to_string())
-    } else if title_lower.contains("medytacja")
-        || title_lower.contains("refleksja")
-        || title_lower.contains("mindfulness")
-        || title_lower.contains("planowanie")
-    {
-        Some("wisdom".to_string())
-    } else if title_lower.contains("sen")
-        || title_lower.contains("dieta")
-        || title_lower.contains("zdrowie")
-        || title_lower.contains("nawyk")
-    {
-        Some("constitution".to_string())
+pub fn calculate_task_exp(task: &Task, is_goal_related: bool) -> (i64, Option<String>) {
+    let priority_exp = match task.priority {
+        TaskPriority::Low => 10,
+        TaskPriority::Medium => 15,
+        TaskPriority::High => 20,
+        TaskPriority::Urgent => 25,
+    };
+    let base_exp = if is_goal_related {
+        priority_exp + 10
     } else {
-        None
+        priority_exp
     };
+    let subtask_exp =
+        (i64::from(task.subtask_count) * SUBTASK_BONUS_EXP).min(MAX_SUBTASK_BONUS_EXP);
 
-    (base_exp, attribute)
+    let attribute = task
+        .attribute
+        .or_else(|| task.tags.iter().find_map(|tag| Attribute::from_tag(tag)))
+        .map(|attribute| attribute.as_str().to_string());
+
+    (base_exp + subtask_exp, attribute)
 }
 
 /// Oblicza punkty EXP za ukończenie nawyku
@@ -297,13 +334,51 @@ pub fn calculate_habit_exp(habit_title: &str, streak_bonus: f64) -> (i64, Option
     (final_exp, attribute)
 }
 
-/// Przetwarza ukończenie zadania - dodaje EXP i atrybuty
-pub fn process_task_completion(
+/// Źródło nagrody w rejestrze - każde może dać EXP tylko raz
+#[derive(Debug, Clone, Copy)]
+enum RewardSource<'a> {
+    /// Ukończone zadanie
+    Task(i32),
+    /// Ukończony wpis nawyku z danego dnia
+    Habit { habit_id: i32, date: &'a str },
+}
+
+impl RewardSource<'_> {
+    /// Zwraca klucz źródła w tabeli `rewards` (typ, ID, dzień)
+    fn key(&self) -> (&'static str, i32, &str) {
+        match *self {
+            RewardSource::Task(task_id) => ("task", task_id, ""),
+            RewardSource::Habit { habit_id, date } => ("habit", habit_id, date),
+        }
+    }
+}
+
+/// Przyznaje nagrodę za źródło, jeśli nie została jeszcze przyznana
+///
+/// # Returns
+/// * `Result<Option<(Character, bool)>>` - Postać i informacja o awansie, `None` gdy nagroda już była
+fn grant_reward(
     connection: &Connection,
-    task_title: &str,
-    is_goal_related: bool,
-) -> Result<(Character, bool)> {
-    let (exp_points, attribute) = calculate_task_exp(task_title, is_goal_related);
+    source: RewardSource,
+    exp_points: i64,
+    attribute: Option<String>,
+) -> Result<Option<(Character, bool)>> {
+    let (source_type, source_id, source_date) = source.key();
+    let inserted = connection.execute(
+        "INSERT OR IGNORE INTO rewards (source_type, source_id, source_date, exp, attribute, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            source_type,
+            source_id,
+            source_date,
+            exp_points,
+            attribute,
+            chrono::Utc::now().timestamp()
+        ],
+    )?;
+    if inserted == 0 {
+        return Ok(None);
+    }
 
     // Dodaj EXP
     let (mut character, level_up) = add_experience(connection, exp_points)?;
@@ -313,33 +388,123 @@ pub fn process_task_completion(
         character = add_attribute_points(connection, &attr, 1)?;
     }
 
-    Ok((character, level_up))
+    Ok(Some((character, level_up)))
+}
+
+/// Cofa nagrodę przyznaną za źródło - odejmuje EXP (poziom może spaść) i punkt atrybutu
+///
+/// # Returns
+/// * `Result<Option<Character>>` - Postać po cofnięciu, `None` gdy źródło nie miało nagrody
+fn revoke_reward(connection: &Connection, source: RewardSource) -> Result<Option<Character>> {
+    let (source_type, source_id, source_date) = source.key();
+    let reward: Option<(i64, Option<String>)> = connection
+        .query_row(
+            "SELECT exp, attribute FROM rewards
+             WHERE source_type = ?1 AND source_id = ?2 AND source_date = ?3",
+            params![source_type, source_id, source_date],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?;
+    let Some((exp_points, attribute)) = reward else {
+        return Ok(None);
+    };
+
+    connection.execute(
+        "DELETE FROM rewards WHERE source_type = ?1 AND source_id = ?2 AND source_date = ?3",
+        params![source_type, source_id, source_date],
+    )?;
+
+    // Doświadczenie nie spada poniżej zera (np. po ręcznej korekcie)
+    let revoked_exp = exp_points.min(get_character(connection)?.experience);
+    let (mut character, _) = save_experience(connection, -revoked_exp)?;
+    events::publish(DomainEvent::ExperienceRevoked {
+        amount: revoked_exp,
+        experience: character.experience,
+        level: character.level,
+    });
+
+    if let Some(attr) = attribute {
+        character = add_attribute_points(connection, &attr, -1)?;
+    }
+
+    Ok(Some(character))
+}
+
+/// Przetwarza ukończenie zadania - dodaje EXP i atrybuty
+///
+/// Zadanie bez własnego atrybutu (ani tagu atrybutu) rozwija atrybut swojego projektu.
+/// Nagroda trafia do rejestru, więc ponowne ukończenie tego samego zadania nic nie daje,
+/// dopóki nie zostanie cofnięta przez `revoke_task_completion`.
+///
+/// # Returns
+/// * `Result<Option<(Character, bool)>>` - Postać i informacja o awansie, `None` gdy zadanie już nagrodzono
+pub fn process_task_completion(
+    connection: &Connection,
+    task: &Task,
+    is_goal_related: bool,
+) -> Result<Option<(Character, bool)>> {
+    let (exp_points, mut attribute) = calculate_task_exp(task, is_goal_related);
+    if let (None, Some(project_id)) = (&attribute, task.project_id) {
+        attribute = project_service::get_project_attribute(connection, project_id)?
+            .map(|attribute| attribute.as_str().to_string());
+    }
+
+    grant_reward(
+        connection,
+        RewardSource::Task(task.id),
+        exp_points,
+        attribute,
+    )
+}
+
+/// Cofa nagrodę za zadanie, które przestało być ukończone
+pub fn revoke_task_completion(connection: &Connection, task_id: i32) -> Result<Option<Character>> {
+    revoke_reward(connection, RewardSource::Task(task_id))
 }
 
 /// Przetwarza ukończenie nawyku - dodaje EXP i atrybuty
+///
+/// Nagroda jest przypisana do nawyku i dnia wpisu, więc ponowne zapisanie
+/// tego samego dnia nie daje EXP drugi raz.
+///
+/// # Returns
+/// * `Result<Option<(Character, bool)>>` - Postać i informacja o awansie, `None` gdy dzień już nagrodzono
 pub fn process_habit_completion(
     connection: &Connection,
+    habit_id: i32,
     habit_title: &str,
+    date: &str,
     current_streak: i32,
-) -> Result<(Character, bool)> {
+) -> Result<Option<(Character, bool)>> {
     // Oblicz bonus streak (0-100% za streaki 1-30+ dni)
     let streak_bonus = (current_streak as f64 / 30.0).min(1.0);
     let (exp_points, attribute) = calculate_habit_exp(habit_title, streak_bonus);
 
-    // Dodaj EXP
-    let (mut character, level_up) = add_experience(connection, exp_points)?;
-
-    // Dodaj punkty atrybutu jeśli wykryto kategorię
-    if let Some(attr) = attribute {
-        character = add_attribute_points(connection, &attr, 1)?;
-    }
+    grant_reward(
+        connection,
+        RewardSource::Habit { habit_id, date },
+        exp_points,
+        attribute,
+    )
+}
 
-    Ok((character, level_up))
+/// Cofa nagrodę za wpis nawyku, który przestał spełniać warunek ukończenia
+pub fn revoke_habit_completion(
+    connection: &Connection,
+    habit_id: i32,
+    date: &str,
+) -> Result<Option<Character>> {
+    revoke_reward(connection, RewardSource::Habit { habit_id, date })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::database::Database;
+    use crate::models::{
+        CreateHabitEntryRequest, CreateHabitRequest, CreateTaskRequest, HabitType,
+    };
+    use crate::services::{habit_service, task_service};
     use rusqlite::Connection;
 
     fn setup_test_db() -> Connection {
@@ -358,6 +523,8 @@ mod tests {
                 dexterity INTEGER NOT NULL DEFAULT 10,
                 wisdom INTEGER NOT NULL DEFAULT 10,
                 constitution INTEGER NOT NULL DEFAULT 10,
+                streak_freezes INTEGER NOT NULL DEFAULT 0,
+                highest_level INTEGER NOT NULL DEFAULT 1,
                 created_at INTEGER NOT NULL,
                 updated_at INTEGER NOT NULL
             )",
@@ -399,17 +566,192 @@ mod tests {
         assert!(level_up);
     }
 
+    #[test]
+    fn test_streak_freezes_are_earned_once_per_level() {
+        let conn = setup_test_db();
+        create_character(
+            &conn,
+            CreateCharacterRequest {
+                character_class: CharacterClass::Rogue,
+            },
+        )
+        .unwrap();
+
+        let (character, _) = add_experience(&conn, 150).unwrap();
+        assert_eq!(character.streak_freezes, 1);
+
+        // Ponowny awans na ten sam poziom po cofnięciu EXP nie daje żetonu
+        add_experience(&conn, -150).unwrap();
+        let (character, level_up) = add_experience(&conn, 150).unwrap();
+        assert!(level_up);
+        assert_eq!(character.streak_freezes, 1);
+
+        assert!(!use_streak_freezes(&conn, 2).unwrap());
+        assert!(use_streak_freezes(&conn, 1).unwrap());
+        assert_eq!(get_character(&conn).unwrap().streak_freezes, 0);
+
+        // Liczba żetonów jest ograniczona
+        let (character, _) = add_experience(&conn, 10_000).unwrap();
+        assert_eq!(character.streak_freezes, MAX_STREAK_FREEZES);
+    }
+
     #[test]
     fn test_calculate_task_exp() {
-        let (exp, attr) = calculate_task_exp("Trening na siłowni", false);
+        let mut task = Task::new("Trening na siłowni".to_string());
+        task.tags = vec!["zdrowie".to_string(), "warrior".to_string()];
+        let (exp, attr) = calculate_task_exp(&task, false);
         assert_eq!(exp, 15);
         assert_eq!(attr, Some("strength".to_string()));
 
-        let (exp, attr) = calculate_task_exp("Nauka języka Python", true);
-        assert_eq!(exp, 25);
+        let mut task = Task::new("Nauka języka Python".to_string());
+        task.priority = TaskPriority::High;
+        task.attribute = Some(Attribute::Intelligence);
+        let (exp, attr) = calculate_task_exp(&task, true);
+        assert_eq!(exp, 30);
         assert_eq!(attr, Some("intelligence".to_string()));
     }
 
+    #[test]
+    fn test_calculate_task_exp_scales_with_subtasks() {
+        let mut task = Task::new("Przeprowadzka".to_string());
+        task.subtask_count = 4;
+        let (exp, _) = calculate_task_exp(&task, false);
+        assert_eq!(exp, 35); // 15 + 4 * 5
+
+        task.subtask_count = 30;
+        let (exp, _) = calculate_task_exp(&task, false);
+        assert_eq!(exp, 15 + MAX_SUBTASK_BONUS_EXP);
+    }
+
+    #[test]
+    fn test_calculate_task_exp_ignores_title() {
+        // "Senior review" nie jest już traktowane jak "sen" (constitution)
+        let task = Task::new("Senior review".to_string());
+        let (exp, attr) = calculate_task_exp(&task, false);
+        assert_eq!(exp, 15);
+        assert_eq!(attr, None);
+    }
+
+    #[test]
+    fn test_task_reward_is_granted_once_and_revoked_on_uncomplete() {
+        let db = Database::new(":memory:".into()).unwrap();
+        let conn = db.connection();
+        create_character(
+            conn,
+            CreateCharacterRequest {
+                character_class: CharacterClass::Warrior,
+            },
+        )
+        .unwrap();
+        let task = task_service::add_task(
+            conn,
+            CreateTaskRequest {
+                title: "Trening".to_string(),
+                attribute: Some(Attribute::Strength),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        task_service::toggle_task_status(conn, task.id).unwrap();
+        let character = get_character(conn).unwrap();
+        assert_eq!(character.experience, 15);
+        assert_eq!(character.attributes.strength, 11);
+
+        // Ponowne przetworzenie tego samego zadania nic nie daje
+        assert!(process_task_completion(conn, &task, false)
+            .unwrap()
+            .is_none());
+
+        task_service::toggle_task_status(conn, task.id).unwrap();
+        let character = get_character(conn).unwrap();
+        assert_eq!(character.experience, 0);
+        assert_eq!(character.attributes.strength, 10);
+
+        task_service::toggle_task_status(conn, task.id).unwrap();
+        assert_eq!(get_character(conn).unwrap().experience, 15);
+    }
+
+    #[test]
+    fn test_completion_without_ledger_entry_is_not_revoked() {
+        let db = Database::new(":memory:".into()).unwrap();
+        let conn = db.connection();
+        create_character(
+            conn,
+            CreateCharacterRequest {
+                character_class: CharacterClass::Warrior,
+            },
+        )
+        .unwrap();
+        add_experience(conn, 40).unwrap();
+
+        // Zadanie ukończone przed wprowadzeniem rejestru nagród nie ma w nim wpisu
+        let task = task_service::add_task(
+            conn,
+            CreateTaskRequest {
+                title: "Stare zadanie".to_string(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        conn.execute("UPDATE tasks SET completed = 1 WHERE id = ?1", [task.id])
+            .unwrap();
+
+        assert!(revoke_task_completion(conn, task.id).unwrap().is_none());
+        task_service::toggle_task_status(conn, task.id).unwrap();
+        assert_eq!(get_character(conn).unwrap().experience, 40);
+    }
+
+    #[test]
+    fn test_habit_reward_is_granted_once_per_day() {
+        let db = Database::new(":memory:".into()).unwrap();
+        let conn = db.connection();
+        create_character(
+            conn,
+            CreateCharacterRequest {
+                character_class: CharacterClass::Mage,
+            },
+        )
+        .unwrap();
+        let habit = habit_service::add_habit(
+            conn,
+            CreateHabitRequest {
+                title: "Medytacja".to_string(),
+                habit_type: HabitType::Boolean,
+                target_value: None,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let log = |date: &str, completed: bool| {
+            habit_service::add_habit_entry(
+                conn,
+                CreateHabitEntryRequest {
+                    habit_id: habit.id,
+                    date: date.to_string(),
+                    completed: Some(completed),
+                    value: None,
+                },
+            )
+            .unwrap();
+            get_character(conn).unwrap()
+        };
+
+        let first = log("2025-03-10", true);
+        assert!(first.experience > 0);
+        assert_eq!(first.attributes.wisdom, 11);
+        assert_eq!(log("2025-03-10", true).experience, first.experience);
+
+        // Obniżenie wpisu cofa nagrodę za ten dzień
+        let downgraded = log("2025-03-10", false);
+        assert_eq!(downgraded.experience, 0);
+        assert_eq!(downgraded.attributes.wisdom, 10);
+
+        log("2025-03-10", true);
+        let next_day = log("2025-03-11", true);
+        assert!(next_day.experience > first.experience);
+    }
+
     #[test]
     fn test_calculate_habit_exp() {
         let (exp, attr) = calculate_habit_exp("Codzienna medytacja", 0.5);
@@ -0,0 +1,42 @@
+use std::fmt;
+
+/// Błędy domenowe zwracane przez serwisy
+///
+/// Serwisy nadal zwracają `anyhow::Result`, ale te błędy można rozpoznać
+/// przez `downcast_ref`, dzięki czemu warstwa API może zwrócić odpowiedni kod HTTP.
+#[derive(Debug)]
+pub enum ServiceError {
+    /// Zasób o podanym ID nie istnieje
+    NotFound(String),
+    /// Dane wejściowe nie przeszły walidacji
+    Validation(String),
+    /// Operacja niedozwolona w aktualnym stanie zasobu (np. quest już ukończony)
+    Conflict(String),
+}
+
+impl fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceError::NotFound(message) => write!(f, "{}", message),
+            ServiceError::Validation(message) => write!(f, "{}", message),
+            ServiceError::Conflict(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for ServiceError {}
+
+/// Skrót do zbudowania błędu `NotFound` opakowanego w `anyhow::Error`
+pub fn not_found(message: impl Into<String>) -> anyhow::Error {
+    ServiceError::NotFound(message.into()).into()
+}
+
+/// Skrót do zbudowania błędu `Validation` opakowanego w `anyhow::Error`
+pub fn validation(message: impl Into<String>) -> anyhow::Error {
+    ServiceError::Validation(message.into()).into()
+}
+
+/// Skrót do zbudowania błędu `Conflict` opakowanego w `anyhow::Error`
+pub fn conflict(message: impl Into<String>) -> anyhow::Error {
+    ServiceError::Conflict(message.into()).into()
+}
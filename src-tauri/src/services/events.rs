@@ -0,0 +1,49 @@
+use std::sync::OnceLock;
+use tokio::sync::broadcast;
+
+use crate::models::DomainEvent;
+
+/// Ile zdarzeń może czekać na odczyt zanim wolny subskrybent zacznie je gubić
+const EVENT_BUFFER_SIZE: usize = 256;
+
+/// Globalna szyna zdarzeń domenowych
+///
+/// Serwisy przyjmują tylko `&Connection`, więc szyna jest globalna zamiast
+/// przekazywana przez argumenty - publikować można z dowolnego miejsca.
+fn bus() -> &'static broadcast::Sender<DomainEvent> {
+    static BUS: OnceLock<broadcast::Sender<DomainEvent>> = OnceLock::new();
+    BUS.get_or_init(|| broadcast::channel(EVENT_BUFFER_SIZE).0)
+}
+
+/// Publikuje zdarzenie do wszystkich subskrybentów
+///
+/// Brak subskrybentów nie jest błędem - zdarzenie zostaje wtedy pominięte.
+pub fn publish(event: DomainEvent) {
+    let _ = bus().send(event);
+}
+
+/// Tworzy nową subskrypcję, która otrzyma zdarzenia opublikowane od tego momentu
+pub fn subscribe() -> broadcast::Receiver<DomainEvent> {
+    bus().subscribe()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_publish_and_subscribe() {
+        let mut receiver = subscribe();
+
+        publish(DomainEvent::LevelUp { level: 42 });
+
+        // Inne testy mogą publikować równolegle, więc szukamy naszego zdarzenia
+        let mut received = false;
+        while let Ok(event) = receiver.try_recv() {
+            if event == (DomainEvent::LevelUp { level: 42 }) {
+                received = true;
+            }
+        }
+        assert!(received);
+    }
+}
@@ -0,0 +1,783 @@
+use anyhow::Result;
+use rusqlite::{Connection, OptionalExtension, Row};
+use std::collections::HashMap;
+
+use crate::models::{
+    CreateGoalRequest, CreateKeyResultRequest, Goal, GoalLevel, GoalNode, GoalStatus, KeyResult,
+    UpdateGoalRequest, UpdateKeyResultRequest,
+};
+use crate::services::error;
+
+/// Maksymalna długość tytułu celu lub kluczowego rezultatu
+const MAX_TITLE_LENGTH: usize = 100;
+
+/// ID celu (parametr `?`) i wszystkich jego podcelów, do użycia w `id IN (...)`
+const SUBTREE_IDS: &str = "WITH RECURSIVE subtree(id) AS (
+         SELECT ?
+         UNION ALL
+         SELECT goals.id FROM goals JOIN subtree ON goals.parent_id = subtree.id
+     )
+     SELECT id FROM subtree";
+
+/// Konwertuje wiersz bazy danych na cel (bez kluczowych rezultatów i postępu)
+fn row_to_goal(row: &Row) -> Result<Goal, rusqlite::Error> {
+    let level: String = row.get("level")?;
+    let status: String = row.get("status")?;
+
+    Ok(Goal {
+        id: row.get("id")?,
+        title: row.get("title")?,
+        description: row.get("description")?,
+        level: GoalLevel::from_db(&level),
+        parent_id: row.get("parent_id")?,
+        deadline: row.get("deadline")?,
+        status: GoalStatus::from_db(&status),
+        key_results: Vec::new(),
+        task_count: 0,
+        completed_task_count: 0,
+        progress: 0.0,
+        created_at: row.get("created_at")?,
+        updated_at: row.get("updated_at")?,
+    })
+}
+
+/// Konwertuje wiersz bazy danych na kluczowy rezultat
+fn row_to_key_result(row: &Row) -> Result<KeyResult, rusqlite::Error> {
+    let target_value: f64 = row.get("target_value")?;
+    let current_value: f64 = row.get("current_value")?;
+
+    Ok(KeyResult {
+        id: row.get("id")?,
+        goal_id: row.get("goal_id")?,
+        title: row.get("title")?,
+        target_value,
+        current_value,
+        unit: row.get("unit")?,
+        progress: percent(current_value, target_value),
+        created_at: row.get("created_at")?,
+        updated_at: row.get("updated_at")?,
+    })
+}
+
+/// Procent realizacji (0-100) zaokrąglony do 0.1
+fn percent(value: f64, target: f64) -> f64 {
+    if target <= 0.0 {
+        return 0.0;
+    }
+    ((value / target * 100.0).clamp(0.0, 100.0) * 10.0).round() / 10.0
+}
+
+/// Sprawdza i przycina tytuł celu lub kluczowego rezultatu
+fn validate_title(title: &str) -> Result<String> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err(error::validation("Goal title cannot be empty"));
+    }
+    if title.chars().count() > MAX_TITLE_LENGTH {
+        return Err(error::validation(format!(
+            "Goal title is too long (max {} characters)",
+            MAX_TITLE_LENGTH
+        )));
+    }
+
+    Ok(title.to_string())
+}
+
+/// Sprawdza wartość docelową kluczowego rezultatu
+fn validate_target(target_value: f64) -> Result<f64> {
+    if !target_value.is_finite() || target_value <= 0.0 {
+        return Err(error::validation(
+            "Key result target must be a number greater than zero",
+        ));
+    }
+
+    Ok(target_value)
+}
+
+/// Sprawdza, czy cel nadrzędny istnieje i ma wyższy poziom niż `level`
+///
+/// Poziomy ściśle rosną w dół drzewa, więc cykl nie jest możliwy.
+fn validate_parent(conn: &Connection, parent_id: Option<i32>, level: GoalLevel) -> Result<()> {
+    let Some(parent_id) = parent_id else {
+        return Ok(());
+    };
+
+    let parent_level: Option<String> = conn
+        .query_row(
+            "SELECT level FROM goals WHERE id = ?1",
+            [parent_id],
+            |row| row.get(0),
+        )
+        .optional()?;
+    let Some(parent_level) = parent_level else {
+        return Err(error::validation(format!(
+            "Parent goal {} not found",
+            parent_id
+        )));
+    };
+
+    if GoalLevel::from_db(&parent_level).rank() >= level.rank() {
+        return Err(error::validation(format!(
+            "A {} goal cannot be placed under a {} goal",
+            level.as_str(),
+            parent_level
+        )));
+    }
+
+    Ok(())
+}
+
+/// Wczytuje wszystkie cele z kluczowymi rezultatami, licznikami zadań i postępem
+///
+/// Postęp celu to średnia z postępu każdego kluczowego rezultatu, odsetka ukończonych
+/// zadań (liczonego jako jedna pozycja) i postępu podcelów (bez porzuconych).
+/// Ukończony cel ma zawsze 100%, a cel bez żadnej z tych pozycji - 0%.
+fn load_goals(conn: &Connection) -> Result<Vec<Goal>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, title, description, level, parent_id, deadline, status, created_at, updated_at
+         FROM goals ORDER BY deadline IS NULL, deadline, id",
+    )?;
+    let goal_iter = stmt.query_map([], row_to_goal)?;
+
+    let mut goals = Vec::new();
+    for goal in goal_iter {
+        goals.push(goal?);
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT id, goal_id, title, target_value, current_value, unit, created_at, updated_at
+         FROM key_results ORDER BY id",
+    )?;
+    let key_result_iter = stmt.query_map([], row_to_key_result)?;
+    let mut key_results: HashMap<i32, Vec<KeyResult>> = HashMap::new();
+    for key_result in key_result_iter {
+        let key_result = key_result?;
+        key_results
+            .entry(key_result.goal_id)
+            .or_default()
+            .push(key_result);
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT goal_id, COUNT(*), SUM(completed) FROM tasks
+         WHERE goal_id IS NOT NULL AND deleted_at IS NULL GROUP BY goal_id",
+    )?;
+    let count_iter = stmt.query_map([], |row| {
+        Ok((row.get::<_, i32>(0)?, (row.get(1)?, row.get(2)?)))
+    })?;
+    let mut task_counts: HashMap<i32, (i32, i32)> = HashMap::new();
+    for count in count_iter {
+        let (goal_id, counts) = count?;
+        task_counts.insert(goal_id, counts);
+    }
+
+    for goal in goals.iter_mut() {
+        goal.key_results = key_results.remove(&goal.id).unwrap_or_default();
+        let (task_count, completed_task_count) =
+            task_counts.get(&goal.id).copied().unwrap_or((0, 0));
+        goal.task_count = task_count;
+        goal.completed_task_count = completed_task_count;
+    }
+
+    let mut children: HashMap<i32, Vec<usize>> = HashMap::new();
+    for (index, goal) in goals.iter().enumerate() {
+        if let Some(parent_id) = goal.parent_id {
+            children.entry(parent_id).or_default().push(index);
+        }
+    }
+    let mut progress = HashMap::new();
+    for index in 0..goals.len() {
+        goal_progress(&goals, &children, index, &mut progress);
+    }
+    for goal in goals.iter_mut() {
+        goal.progress = progress[&goal.id];
+    }
+
+    Ok(goals)
+}
+
+/// Liczy postęp celu (patrz [`load_goals`]), zapamiętując wyniki podcelów
+fn goal_progress(
+    goals: &[Goal],
+    children: &HashMap<i32, Vec<usize>>,
+    index: usize,
+    progress: &mut HashMap<i32, f64>,
+) -> f64 {
+    let goal = &goals[index];
+    if let Some(&value) = progress.get(&goal.id) {
+        return value;
+    }
+
+    let mut items: Vec<f64> = goal.key_results.iter().map(|kr| kr.progress).collect();
+    if goal.task_count > 0 {
+        items.push(percent(
+            goal.completed_task_count as f64,
+            goal.task_count as f64,
+        ));
+    }
+    for &child in children.get(&goal.id).into_iter().flatten() {
+        if goals[child].status != GoalStatus::Abandoned {
+            items.push(goal_progress(goals, children, child, progress));
+        }
+    }
+
+    let value = if goal.status == GoalStatus::Completed {
+        100.0
+    } else if items.is_empty() {
+        0.0
+    } else {
+        (items.iter().sum::<f64>() / items.len() as f64 * 10.0).round() / 10.0
+    };
+    progress.insert(goal.id, value);
+
+    value
+}
+
+/// Tworzy nowy cel
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `request` - Tytuł, poziom, opcjonalny cel nadrzędny, opis i termin
+///
+/// # Returns
+/// * `Result<Goal>` - Utworzony cel lub błąd walidacji
+pub fn create_goal(conn: &Connection, request: CreateGoalRequest) -> Result<Goal> {
+    let title = validate_title(&request.title)?;
+    validate_parent(conn, request.parent_id, request.level)?;
+
+    let now = chrono::Utc::now().timestamp();
+    conn.execute(
+        "INSERT INTO goals (title, description, level, parent_id, deadline, status, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)",
+        (
+            &title,
+            &request.description,
+            request.level.as_str(),
+            request.parent_id,
+            request.deadline,
+            GoalStatus::Active.as_str(),
+            now,
+        ),
+    )?;
+
+    get_goal(conn, conn.last_insert_rowid() as i32)
+}
+
+/// Pobiera cele, opcjonalnie tylko z jednego poziomu
+///
+/// # Returns
+/// * `Result<Vec<Goal>>` - Cele posortowane według terminu
+pub fn list_goals(conn: &Connection, level: Option<GoalLevel>) -> Result<Vec<Goal>> {
+    let goals = load_goals(conn)?;
+
+    Ok(goals
+        .into_iter()
+        .filter(|goal| level.is_none_or(|level| goal.level == level))
+        .collect())
+}
+
+/// Pobiera cel po ID
+///
+/// # Returns
+/// * `Result<Goal>` - Cel z kluczowymi rezultatami i postępem lub błąd not found
+pub fn get_goal(conn: &Connection, goal_id: i32) -> Result<Goal> {
+    load_goals(conn)?
+        .into_iter()
+        .find(|goal| goal.id == goal_id)
+        .ok_or_else(|| error::not_found(format!("Goal with id {} not found", goal_id)))
+}
+
+/// Pobiera drzewo wszystkich celów (korzenie to cele bez celu nadrzędnego)
+pub fn get_goal_tree(conn: &Connection) -> Result<Vec<GoalNode>> {
+    let goals = load_goals(conn)?;
+
+    let mut children: HashMap<i32, Vec<Goal>> = HashMap::new();
+    let mut roots = Vec::new();
+    for goal in goals {
+        match goal.parent_id {
+            Some(parent_id) => children.entry(parent_id).or_default().push(goal),
+            None => roots.push(goal),
+        }
+    }
+
+    Ok(roots
+        .into_iter()
+        .map(|goal| build_node(goal, &mut children))
+        .collect())
+}
+
+fn build_node(goal: Goal, children: &mut HashMap<i32, Vec<Goal>>) -> GoalNode {
+    let child_goals = children.remove(&goal.id).unwrap_or_default();
+
+    GoalNode {
+        goal,
+        children: child_goals
+            .into_iter()
+            .map(|child| build_node(child, children))
+            .collect(),
+    }
+}
+
+/// Aktualizuje cel
+///
+/// Zmiana poziomu lub celu nadrzędnego musi zachować porządek poziomów
+/// względem rodzica i wszystkich bezpośrednich podcelów.
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `goal_id` - ID celu
+/// * `request` - Zmieniane pola
+///
+/// # Returns
+/// * `Result<Goal>` - Zaktualizowany cel lub błąd
+pub fn update_goal(conn: &Connection, goal_id: i32, request: UpdateGoalRequest) -> Result<Goal> {
+    let mut goal = get_goal(conn, goal_id)?;
+
+    if let Some(title) = request.title {
+        goal.title = validate_title(&title)?;
+    }
+    if let Some(description) = request.description {
+        goal.description = description;
+    }
+    if let Some(deadline) = request.deadline {
+        goal.deadline = deadline;
+    }
+    if let Some(status) = request.status {
+        goal.status = status;
+    }
+    if request.level.is_some() || request.parent_id.is_some() {
+        let level = request.level.unwrap_or(goal.level);
+        let parent_id = request.parent_id.unwrap_or(goal.parent_id);
+        validate_parent(conn, parent_id, level)?;
+
+        let mut stmt = conn.prepare("SELECT level FROM goals WHERE parent_id = ?1")?;
+        let child_levels = stmt.query_map([goal_id], |row| row.get::<_, String>(0))?;
+        for child_level in child_levels {
+            if GoalLevel::from_db(&child_level?).rank() <= level.rank() {
+                return Err(error::validation(format!(
+                    "Goal has subgoals that cannot be placed under a {} goal",
+                    level.as_str()
+                )));
+            }
+        }
+
+        goal.level = level;
+        goal.parent_id = parent_id;
+    }
+    goal.updated_at = chrono::Utc::now().timestamp();
+
+    conn.execute(
+        "UPDATE goals SET title = ?1, description = ?2, level = ?3, parent_id = ?4, deadline = ?5,
+                status = ?6, updated_at = ?7
+         WHERE id = ?8",
+        (
+            &goal.title,
+            &goal.description,
+            goal.level.as_str(),
+            goal.parent_id,
+            goal.deadline,
+            goal.status.as_str(),
+            goal.updated_at,
+            goal_id,
+        ),
+    )?;
+
+    get_goal(conn, goal_id)
+}
+
+/// Usuwa cel razem z podcelami i kluczowymi rezultatami; zadania zostają, ale bez celu
+pub fn delete_goal(conn: &Connection, goal_id: i32) -> Result<()> {
+    let subtree: Vec<i32> = {
+        let mut stmt = conn.prepare(SUBTREE_IDS)?;
+        let ids = stmt.query_map([goal_id], |row| row.get(0))?;
+        ids.collect::<Result<_, _>>()?
+    };
+
+    for id in subtree.iter().rev() {
+        conn.execute("UPDATE tasks SET goal_id = NULL WHERE goal_id = ?1", [id])?;
+        conn.execute("DELETE FROM key_results WHERE goal_id = ?1", [id])?;
+    }
+    // Podcele są usuwane od najgłębszych, żeby nie zależeć od kaskady kluczy obcych
+    let mut rows_affected = 0;
+    for id in subtree.iter().rev() {
+        rows_affected = conn.execute("DELETE FROM goals WHERE id = ?1", [id])?;
+    }
+
+    if rows_affected == 0 {
+        return Err(error::not_found(format!(
+            "Goal with id {} not found",
+            goal_id
+        )));
+    }
+
+    Ok(())
+}
+
+/// Dodaje kluczowy rezultat do celu
+///
+/// # Returns
+/// * `Result<KeyResult>` - Utworzony kluczowy rezultat lub błąd
+pub fn add_key_result(
+    conn: &Connection,
+    goal_id: i32,
+    request: CreateKeyResultRequest,
+) -> Result<KeyResult> {
+    get_goal(conn, goal_id)?;
+    let title = validate_title(&request.title)?;
+    let target_value = validate_target(request.target_value)?;
+
+    let now = chrono::Utc::now().timestamp();
+    conn.execute(
+        "INSERT INTO key_results (goal_id, title, target_value, current_value, unit, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)",
+        (
+            goal_id,
+            &title,
+            target_value,
+            request.current_value,
+            &request.unit,
+            now,
+        ),
+    )?;
+    let key_result = get_key_result(conn, conn.last_insert_rowid() as i32)?;
+    refresh_goal_status(conn, goal_id)?;
+
+    Ok(key_result)
+}
+
+fn get_key_result(conn: &Connection, key_result_id: i32) -> Result<KeyResult> {
+    conn.query_row(
+        "SELECT id, goal_id, title, target_value, current_value, unit, created_at, updated_at
+         FROM key_results WHERE id = ?1",
+        [key_result_id],
+        row_to_key_result,
+    )
+    .optional()?
+    .ok_or_else(|| error::not_found(format!("Key result with id {} not found", key_result_id)))
+}
+
+/// Aktualizuje kluczowy rezultat (np. zapisuje nową wartość bieżącą)
+///
+/// Osiągnięcie 100% postępu kończy cel (patrz [`refresh_goal_status`]).
+///
+/// # Returns
+/// * `Result<KeyResult>` - Zaktualizowany kluczowy rezultat lub błąd
+pub fn update_key_result(
+    conn: &Connection,
+    key_result_id: i32,
+    request: UpdateKeyResultRequest,
+) -> Result<KeyResult> {
+    let mut key_result = get_key_result(conn, key_result_id)?;
+
+    if let Some(title) = request.title {
+        key_result.title = validate_title(&title)?;
+    }
+    if let Some(target_value) = request.target_value {
+        key_result.target_value = validate_target(target_value)?;
+    }
+    if let Some(current_value) = request.current_value {
+        key_result.current_value = current_value;
+    }
+    if let Some(unit) = request.unit {
+        key_result.unit = unit;
+    }
+    key_result.updated_at = chrono::Utc::now().timestamp();
+
+    conn.execute(
+        "UPDATE key_results SET title = ?1, target_value = ?2, current_value = ?3, unit = ?4,
+                updated_at = ?5
+         WHERE id = ?6",
+        (
+            &key_result.title,
+            key_result.target_value,
+            key_result.current_value,
+            &key_result.unit,
+            key_result.updated_at,
+            key_result_id,
+        ),
+    )?;
+    refresh_goal_status(conn, key_result.goal_id)?;
+
+    get_key_result(conn, key_result_id)
+}
+
+/// Usuwa kluczowy rezultat
+pub fn delete_key_result(conn: &Connection, key_result_id: i32) -> Result<()> {
+    let rows_affected = conn.execute("DELETE FROM key_results WHERE id = ?1", [key_result_id])?;
+
+    if rows_affected == 0 {
+        return Err(error::not_found(format!(
+            "Key result with id {} not found",
+            key_result_id
+        )));
+    }
+
+    Ok(())
+}
+
+/// Kończy aktywny cel, którego postęp doszedł do 100%, i sprawdza kolejne cele w górę drzewa
+///
+/// Wywoływana po ukończeniu powiązanego zadania i po zmianie kluczowego rezultatu.
+///
+/// # Returns
+/// * `Result<Vec<i32>>` - ID celów oznaczonych jako ukończone
+pub fn refresh_goal_status(conn: &Connection, goal_id: i32) -> Result<Vec<i32>> {
+    let goals: HashMap<i32, Goal> = load_goals(conn)?
+        .into_iter()
+        .map(|goal| (goal.id, goal))
+        .collect();
+
+    let mut completed = Vec::new();
+    let mut current = goals.get(&goal_id);
+    while let Some(goal) = current {
+        if goal.status != GoalStatus::Active || goal.progress < 100.0 {
+            break;
+        }
+        completed.push(goal.id);
+        current = goal.parent_id.and_then(|parent_id| goals.get(&parent_id));
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    for id in &completed {
+        conn.execute(
+            "UPDATE goals SET status = ?1, updated_at = ?2 WHERE id = ?3",
+            (GoalStatus::Completed.as_str(), now, id),
+        )?;
+    }
+
+    Ok(completed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CreateTaskRequest, TaskPriority};
+    use crate::services::task_service;
+
+    fn create_test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+
+        conn.execute_batch(
+            "CREATE TABLE tasks (
+                id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                title TEXT NOT NULL,
+                notes TEXT,
+                completed BOOLEAN NOT NULL DEFAULT 0,
+                priority TEXT NOT NULL DEFAULT 'Medium',
+                attribute TEXT,
+                due_at INTEGER,
+                scheduled_for TEXT,
+                recurrence_id INTEGER,
+                project_id INTEGER,
+                parent_id INTEGER,
+                goal_id INTEGER,
+                inbox BOOLEAN NOT NULL DEFAULT 0,
+                estimate_minutes INTEGER,
+                deleted_at INTEGER,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            );
+            CREATE TABLE tags (
+                id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                created_at INTEGER NOT NULL
+            );
+            CREATE TABLE task_tags (
+                task_id INTEGER NOT NULL,
+                tag_id INTEGER NOT NULL,
+                PRIMARY KEY (task_id, tag_id)
+            );
+            CREATE TABLE task_recurrences (
+                id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                rule TEXT NOT NULL,
+                active BOOLEAN NOT NULL DEFAULT 1,
+                occurrences INTEGER NOT NULL DEFAULT 1,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            );
+            CREATE TABLE goals (
+                id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                title TEXT NOT NULL,
+                description TEXT,
+                level TEXT NOT NULL,
+                parent_id INTEGER,
+                deadline INTEGER,
+                status TEXT NOT NULL DEFAULT 'Active',
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            );
+            CREATE TABLE key_results (
+                id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                goal_id INTEGER NOT NULL,
+                title TEXT NOT NULL,
+                target_value REAL NOT NULL,
+                current_value REAL NOT NULL DEFAULT 0,
+                unit TEXT,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            );
+            CREATE TABLE settings (
+                key TEXT PRIMARY KEY NOT NULL,
+                value TEXT NOT NULL
+            );",
+        )
+        .unwrap();
+
+        conn
+    }
+
+    fn create(conn: &Connection, title: &str, level: GoalLevel, parent_id: Option<i32>) -> Goal {
+        create_goal(
+            conn,
+            CreateGoalRequest {
+                title: title.to_string(),
+                description: None,
+                level,
+                parent_id,
+                deadline: None,
+            },
+        )
+        .unwrap()
+    }
+
+    fn add_task(conn: &Connection, title: &str, goal_id: i32) -> i32 {
+        task_service::add_task(
+            conn,
+            CreateTaskRequest {
+                title: title.to_string(),
+                priority: TaskPriority::Low,
+                goal_id: Some(goal_id),
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .id
+    }
+
+    #[test]
+    fn test_goal_levels_must_descend() {
+        let conn = create_test_db();
+        let vision = create(&conn, "Zdrowe życie", GoalLevel::Vision, None);
+        let yearly = create(
+            &conn,
+            "Przebiec maraton",
+            GoalLevel::Yearly,
+            Some(vision.id),
+        );
+
+        let invalid = create_goal(
+            &conn,
+            CreateGoalRequest {
+                title: "Druga wizja".to_string(),
+                description: None,
+                level: GoalLevel::Vision,
+                parent_id: Some(yearly.id),
+                deadline: None,
+            },
+        );
+        assert!(invalid.is_err());
+
+        let quarterly = create(&conn, "Półmaraton", GoalLevel::Quarterly, Some(yearly.id));
+        // Cel roczny nie może zejść poniżej swojego podcelu kwartalnego
+        let demoted = update_goal(
+            &conn,
+            yearly.id,
+            UpdateGoalRequest {
+                level: Some(GoalLevel::Monthly),
+                ..Default::default()
+            },
+        );
+        assert!(demoted.is_err());
+
+        let tree = get_goal_tree(&conn).unwrap();
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree[0].children[0].children[0].goal.id, quarterly.id);
+    }
+
+    #[test]
+    fn test_goal_progress_from_key_results_tasks_and_subgoals() {
+        let conn = create_test_db();
+        let yearly = create(&conn, "Forma", GoalLevel::Yearly, None);
+        let quarterly = create(&conn, "Bieganie", GoalLevel::Quarterly, Some(yearly.id));
+
+        let key_result = add_key_result(
+            &conn,
+            quarterly.id,
+            CreateKeyResultRequest {
+                title: "Przebiegnij 100 km".to_string(),
+                target_value: 100.0,
+                current_value: 25.0,
+                unit: Some("km".to_string()),
+            },
+        )
+        .unwrap();
+        assert_eq!(key_result.progress, 25.0);
+
+        let done = add_task(&conn, "Kup buty", quarterly.id);
+        add_task(&conn, "Plan treningowy", quarterly.id);
+        task_service::toggle_task_status(&conn, done).unwrap();
+
+        // (25% rezultatu + 50% zadań) / 2
+        let goal = get_goal(&conn, quarterly.id).unwrap();
+        assert_eq!(goal.task_count, 2);
+        assert_eq!(goal.completed_task_count, 1);
+        assert_eq!(goal.progress, 37.5);
+        assert_eq!(get_goal(&conn, yearly.id).unwrap().progress, 37.5);
+    }
+
+    #[test]
+    fn test_reaching_full_progress_completes_goal_chain() {
+        let conn = create_test_db();
+        let yearly = create(&conn, "Książki", GoalLevel::Yearly, None);
+        let monthly = create(&conn, "Styczeń", GoalLevel::Monthly, Some(yearly.id));
+        let key_result = add_key_result(
+            &conn,
+            monthly.id,
+            CreateKeyResultRequest {
+                title: "Przeczytaj 2 książki".to_string(),
+                target_value: 2.0,
+                current_value: 0.0,
+                unit: None,
+            },
+        )
+        .unwrap();
+
+        update_key_result(
+            &conn,
+            key_result.id,
+            UpdateKeyResultRequest {
+                current_value: Some(2.0),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_goal(&conn, monthly.id).unwrap().status,
+            GoalStatus::Completed
+        );
+        assert_eq!(
+            get_goal(&conn, yearly.id).unwrap().status,
+            GoalStatus::Completed
+        );
+    }
+
+    #[test]
+    fn test_delete_goal_unlinks_tasks() {
+        let conn = create_test_db();
+        let yearly = create(&conn, "Nauka", GoalLevel::Yearly, None);
+        let monthly = create(&conn, "Kurs", GoalLevel::Monthly, Some(yearly.id));
+        let task_id = add_task(&conn, "Lekcja 1", monthly.id);
+
+        delete_goal(&conn, yearly.id).unwrap();
+
+        assert!(get_goal(&conn, monthly.id).is_err());
+        assert_eq!(
+            task_service::get_task_by_id(&conn, task_id)
+                .unwrap()
+                .goal_id,
+            None
+        );
+        assert!(delete_goal(&conn, yearly.id).is_err());
+    }
+}
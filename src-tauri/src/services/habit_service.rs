@@ -1,9 +1,94 @@
+use std::collections::{BTreeSet, HashSet};
+
 use crate::models::{
-    CreateHabitEntryRequest, CreateHabitRequest, Habit, HabitEntry, HabitType, UpdateHabitRequest,
+    CreateHabitEntryRequest, CreateHabitRequest, DomainEvent, Habit, HabitEntry, HabitQuery,
+    HabitSchedule, HabitSortField, HabitType, Page, UpdateHabitRequest, MAX_STREAK_FREEZES,
 };
-use crate::services::character_service;
+use crate::services::pagination::{self, Filters, PageRequest};
+use crate::services::{character_service, error, events, habit_stats_service, settings_service};
 use anyhow::Result;
-use rusqlite::Connection;
+use chrono::{Datelike, Duration, NaiveDate};
+use rusqlite::types::Value;
+use rusqlite::{Connection, Row};
+
+/// Kolumny tabeli habits odczytywane przez [`row_to_habit`]
+const HABIT_COLUMNS: &str = "id, title, habit_type, target_value, schedule, current_streak, \
+                             best_streak, total_completions, created_at, updated_at";
+
+/// Kolumny tabeli habit_entries odczytywane przez [`row_to_entry`]
+const ENTRY_COLUMNS: &str = "id, habit_id, date, completed, value, frozen, created_at";
+
+/// Najdłuższy dozwolony odstęp dla harmonogramu "co N dni"
+pub(crate) const MAX_SCHEDULE_INTERVAL: u32 = 365;
+
+/// Konwertuje wiersz bazy danych na obiekt Habit
+fn row_to_habit(row: &Row) -> Result<Habit, rusqlite::Error> {
+    let habit_type_str: String = row.get("habit_type")?;
+    let habit_type = match habit_type_str.as_str() {
+        "Counter" => HabitType::Counter,
+        _ => HabitType::Boolean, // Default fallback
+    };
+    let schedule: String = row.get("schedule")?;
+
+    Ok(Habit {
+        id: row.get("id")?,
+        title: row.get("title")?,
+        habit_type,
+        target_value: row.get("target_value")?,
+        schedule: serde_json::from_str(&schedule).unwrap_or_default(),
+        current_streak: row.get("current_streak")?,
+        best_streak: row.get("best_streak")?,
+        total_completions: row.get("total_completions")?,
+        created_at: row.get("created_at")?,
+        updated_at: row.get("updated_at")?,
+    })
+}
+
+/// Konwertuje wiersz bazy danych na obiekt HabitEntry
+fn row_to_entry(row: &Row) -> Result<HabitEntry, rusqlite::Error> {
+    Ok(HabitEntry {
+        id: row.get("id")?,
+        habit_id: row.get("habit_id")?,
+        date: row.get("date")?,
+        completed: row.get("completed")?,
+        value: row.get("value")?,
+        frozen: row.get("frozen")?,
+        created_at: row.get("created_at")?,
+    })
+}
+
+/// Sprawdza harmonogram i sprowadza go do postaci kanonicznej (posortowane dni bez powtórzeń)
+fn validate_schedule(schedule: HabitSchedule) -> Result<HabitSchedule> {
+    match schedule {
+        HabitSchedule::Weekdays { mut days } => {
+            if days.is_empty() {
+                return Err(error::validation(
+                    "Schedule must include at least one weekday",
+                ));
+            }
+            if days.iter().any(|day| !(1..=7).contains(day)) {
+                return Err(error::validation(
+                    "Weekdays must be between 1 (Monday) and 7 (Sunday)",
+                ));
+            }
+            days.sort_unstable();
+            days.dedup();
+            Ok(HabitSchedule::Weekdays { days })
+        }
+        HabitSchedule::TimesPerWeek { times } if !(1..=7).contains(&times) => {
+            Err(error::validation("times must be between 1 and 7"))
+        }
+        HabitSchedule::EveryNDays { interval }
+            if !(1..=MAX_SCHEDULE_INTERVAL).contains(&interval) =>
+        {
+            Err(error::validation(format!(
+                "interval must be between 1 and {}",
+                MAX_SCHEDULE_INTERVAL
+            )))
+        }
+        schedule => Ok(schedule),
+    }
+}
 
 /// Dodaje nowy nawyk do bazy danych
 ///
@@ -16,28 +101,35 @@ use rusqlite::Connection;
 pub fn add_habit(conn: &Connection, request: CreateHabitRequest) -> Result<Habit> {
     // Validate title length
     if request.title.trim().is_empty() {
-        return Err(anyhow::anyhow!("Habit title cannot be empty"));
+        return Err(error::validation("Habit title cannot be empty"));
     }
     if request.title.len() > 50 {
-        return Err(anyhow::anyhow!(
-            "Habit title is too long (max 50 characters)"
+        return Err(error::validation(
+            "Habit title is too long (max 50 characters)",
         ));
     }
+    let schedule = validate_schedule(request.schedule)?;
 
-    let mut habit = Habit::new(request.title, request.habit_type, request.target_value);
+    let mut habit = Habit::new(
+        request.title,
+        request.habit_type,
+        request.target_value,
+        schedule,
+    );
 
     let habit_type_str = match habit.habit_type {
         HabitType::Boolean => "Boolean",
         HabitType::Counter => "Counter",
     };
 
-    let sql = "INSERT INTO habits (title, habit_type, target_value, current_streak, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)";
+    let sql = "INSERT INTO habits (title, habit_type, target_value, schedule, current_streak, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)";
     conn.execute(
         sql,
         (
             &habit.title,
             habit_type_str,
             habit.target_value,
+            serde_json::to_string(&habit.schedule)?,
             habit.current_streak,
             habit.created_at,
             habit.updated_at,
@@ -58,27 +150,13 @@ pub fn add_habit(conn: &Connection, request: CreateHabitRequest) -> Result<Habit
 /// # Returns
 /// * `Result<Vec<Habit>>` - Lista wszystkich nawyków lub błąd
 pub fn get_all_habits(conn: &Connection) -> Result<Vec<Habit>> {
-    let sql = "SELECT id, title, habit_type, target_value, current_streak, created_at, updated_at FROM habits ORDER BY created_at DESC";
-    let mut stmt = conn.prepare(sql)?;
-
-    let habit_iter = stmt.query_map([], |row| {
-        let habit_type_str: String = row.get(2)?;
-        let habit_type = match habit_type_str.as_str() {
-            "Boolean" => HabitType::Boolean,
-            "Counter" => HabitType::Counter,
-            _ => HabitType::Boolean, // Default fallback
-        };
+    let sql = format!(
+        "SELECT {} FROM habits WHERE deleted_at IS NULL ORDER BY created_at DESC",
+        HABIT_COLUMNS
+    );
+    let mut stmt = conn.prepare(&sql)?;
 
-        Ok(Habit {
-            id: row.get(0)?,
-            title: row.get(1)?,
-            habit_type,
-            target_value: row.get(3)?,
-            current_streak: row.get(4)?,
-            created_at: row.get(5)?,
-            updated_at: row.get(6)?,
-        })
-    })?;
+    let habit_iter = stmt.query_map([], row_to_habit)?;
 
     let mut habits = Vec::new();
     for habit in habit_iter {
@@ -88,7 +166,71 @@ pub fn get_all_habits(conn: &Connection) -> Result<Vec<Habit>> {
     Ok(habits)
 }
 
-/// Pobiera nawyk po ID
+/// Pobiera stronę nawyków spełniających filtry
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `query` - Filtry, sortowanie, limit i kursor
+///
+/// # Returns
+/// * `Result<Page<Habit>>` - Strona nawyków z kursorem następnej strony lub błąd
+pub fn query_habits(conn: &Connection, query: HabitQuery) -> Result<Page<Habit>> {
+    let mut filters = Filters::default();
+    filters.add_condition("deleted_at IS NULL");
+    if let Some(habit_type) = &query.habit_type {
+        let habit_type = match habit_type {
+            HabitType::Boolean => "Boolean",
+            HabitType::Counter => "Counter",
+        };
+        filters.add("habit_type = ?", habit_type.to_string());
+    }
+    if let Some(after) = query.created_after {
+        filters.add("created_at >= ?", after);
+    }
+    if let Some(before) = query.created_before {
+        filters.add("created_at < ?", before);
+    }
+    if let Some(after) = query.updated_after {
+        filters.add("updated_at >= ?", after);
+    }
+    if let Some(before) = query.updated_before {
+        filters.add("updated_at < ?", before);
+    }
+    filters.add_search("title", query.search.as_deref());
+
+    let sort = query.sort.unwrap_or_default();
+    let (sort_name, sort_column) = match sort {
+        HabitSortField::CreatedAt => ("created_at", "created_at"),
+        HabitSortField::UpdatedAt => ("updated_at", "updated_at"),
+        HabitSortField::Title => ("title", "title"),
+        HabitSortField::CurrentStreak => ("current_streak", "current_streak"),
+    };
+
+    pagination::fetch_page(
+        conn,
+        &format!("SELECT {} FROM habits", HABIT_COLUMNS),
+        filters,
+        PageRequest {
+            sort_name,
+            sort_column,
+            order: query.order.unwrap_or_default(),
+            limit: query.limit,
+            cursor: query.cursor.as_deref(),
+        },
+        row_to_habit,
+        |habit| {
+            let value = match sort {
+                HabitSortField::CreatedAt => Value::Integer(habit.created_at),
+                HabitSortField::UpdatedAt => Value::Integer(habit.updated_at),
+                HabitSortField::Title => Value::Text(habit.title.clone()),
+                HabitSortField::CurrentStreak => Value::Integer(habit.current_streak.into()),
+            };
+            (value, habit.id)
+        },
+    )
+}
+
+/// Pobiera nawyk po ID (nawyki z kosza są traktowane jak nieistniejące)
 ///
 /// # Arguments
 /// * `conn` - Referencja do połączenia z bazą danych
@@ -97,32 +239,28 @@ pub fn get_all_habits(conn: &Connection) -> Result<Vec<Habit>> {
 /// # Returns
 /// * `Result<Habit>` - Nawyk lub błąd
 pub fn get_habit_by_id(conn: &Connection, habit_id: i32) -> Result<Habit> {
-    let sql = "SELECT id, title, habit_type, target_value, current_streak, created_at, updated_at FROM habits WHERE id = ?1";
-    let mut stmt = conn.prepare(sql)?;
-
-    let habit = stmt.query_row([habit_id], |row| {
-        let habit_type_str: String = row.get(2)?;
-        let habit_type = match habit_type_str.as_str() {
-            "Boolean" => HabitType::Boolean,
-            "Counter" => HabitType::Counter,
-            _ => HabitType::Boolean, // Default fallback
-        };
-
-        Ok(Habit {
-            id: row.get(0)?,
-            title: row.get(1)?,
-            habit_type,
-            target_value: row.get(3)?,
-            current_streak: row.get(4)?,
-            created_at: row.get(5)?,
-            updated_at: row.get(6)?,
-        })
-    })?;
+    let sql = format!(
+        "SELECT {} FROM habits WHERE id = ?1 AND deleted_at IS NULL",
+        HABIT_COLUMNS
+    );
+    let mut stmt = conn.prepare(&sql)?;
+
+    let habit = stmt
+        .query_row([habit_id], row_to_habit)
+        .map_err(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => {
+                error::not_found(format!("Habit with id {} not found", habit_id))
+            }
+            e => e.into(),
+        })?;
 
     Ok(habit)
 }
 
-/// Usuwa nawyk z bazy danych (wraz z wszystkimi wpisami)
+/// Przenosi nawyk do kosza
+///
+/// Wpisy nawyku i powiązane questy zostają zachowane do czasu trwałego usunięcia
+/// (przez `trash_service` lub automatycznie po okresie przechowywania).
 ///
 /// # Arguments
 /// * `conn` - Referencja do połączenia z bazą danych
@@ -131,16 +269,14 @@ pub fn get_habit_by_id(conn: &Connection, habit_id: i32) -> Result<Habit> {
 /// # Returns
 /// * `Result<()>` - Sukces lub błąd
 pub fn delete_habit(conn: &Connection, habit_id: i32) -> Result<()> {
-    // Najpierw usuń wszystkie wpisy powiązane z nawykiem
-    let delete_entries_sql = "DELETE FROM habit_entries WHERE habit_id = ?1";
-    conn.execute(delete_entries_sql, [habit_id])?;
-
-    // Następnie usuń sam nawyk
-    let delete_habit_sql = "DELETE FROM habits WHERE id = ?1";
-    let rows_affected = conn.execute(delete_habit_sql, [habit_id])?;
+    let sql = "UPDATE habits SET deleted_at = ?1 WHERE id = ?2 AND deleted_at IS NULL";
+    let rows_affected = conn.execute(sql, (chrono::Utc::now().timestamp(), habit_id))?;
 
     if rows_affected == 0 {
-        return Err(anyhow::anyhow!("Habit with id {} not found", habit_id));
+        return Err(error::not_found(format!(
+            "Habit with id {} not found",
+            habit_id
+        )));
     }
 
     Ok(())
@@ -165,27 +301,55 @@ pub fn update_habit(
     if let Some(title) = request.title {
         // Validate title length
         if title.trim().is_empty() {
-            return Err(anyhow::anyhow!("Habit title cannot be empty"));
+            return Err(error::validation("Habit title cannot be empty"));
         }
         if title.len() > 50 {
-            return Err(anyhow::anyhow!(
-                "Habit title is too long (max 50 characters)"
+            return Err(error::validation(
+                "Habit title is too long (max 50 characters)",
             ));
         }
         habit.update_title(title);
     }
 
-    if let Some(target_value) = request.target_value {
-        habit.update_target_value(Some(target_value));
-    }
+    let target_changed = match request.target_value {
+        Some(target_value) => {
+            let changed = habit.target_value != Some(target_value);
+            habit.update_target_value(Some(target_value));
+            changed
+        }
+        None => false,
+    };
 
-    let sql = "UPDATE habits SET title = ?1, target_value = ?2, updated_at = ?3 WHERE id = ?4";
+    let schedule_changed = match request.schedule {
+        Some(schedule) => {
+            let schedule = validate_schedule(schedule)?;
+            let changed = schedule != habit.schedule;
+            habit.schedule = schedule;
+            changed
+        }
+        None => false,
+    };
+
+    let sql = "UPDATE habits SET title = ?1, target_value = ?2, schedule = ?3, updated_at = ?4 WHERE id = ?5";
     conn.execute(
         sql,
-        (&habit.title, habit.target_value, habit.updated_at, habit.id),
+        (
+            &habit.title,
+            habit.target_value,
+            serde_json::to_string(&habit.schedule)?,
+            habit.updated_at,
+            habit.id,
+        ),
     )?;
 
-    Ok(habit)
+    // Zmiana harmonogramu lub celu zmienia to, które dni liczą się do streaka
+    if schedule_changed || target_changed {
+        habit.current_streak = calculate_streak(conn, habit.id)?;
+        update_habit_streak(conn, habit.id, habit.current_streak)?;
+        update_habit_totals(conn, &habit)?;
+    }
+
+    get_habit_by_id(conn, habit.id)
 }
 
 /// Dodaje wpis nawyku na konkretny dzień
@@ -197,6 +361,17 @@ pub fn update_habit(
 /// # Returns
 /// * `Result<HabitEntry>` - Nowo utworzony wpis lub błąd
 pub fn add_habit_entry(conn: &Connection, request: CreateHabitEntryRequest) -> Result<HabitEntry> {
+    // Upewnij się, że nawyk istnieje zanim zapiszemy wpis
+    let habit = get_habit_by_id(conn, request.habit_id)?;
+    let previous_streak = habit.current_streak;
+
+    if chrono::NaiveDate::parse_from_str(&request.date, "%Y-%m-%d").is_err() {
+        return Err(error::validation(format!(
+            "Invalid date '{}' (expected YYYY-MM-DD)",
+            request.date
+        )));
+    }
+
     let completed = request.completed.unwrap_or(false);
     let value = request.value.unwrap_or(0);
 
@@ -220,45 +395,72 @@ pub fn add_habit_entry(conn: &Connection, request: CreateHabitEntryRequest) -> R
         Ok(row.get::<_, i32>(0)?)
     })?;
 
-    // Przelicz streak dla nawyku
+    // Dzisiejsze wykonanie po przerwie ratuje streak żetonami zamrożenia
+    if habit.is_entry_completed(&entry) {
+        let today = settings_service::today(conn)?;
+        if entry.date == today.format("%Y-%m-%d").to_string() {
+            if let Err(e) = apply_streak_freezes(conn, &habit, today) {
+                eprintln!("Failed to apply streak freezes: {}", e);
+            }
+        }
+    }
+
+    // Przelicz streak i liczbę wykonań nawyku
     let new_streak = calculate_streak(conn, request.habit_id)?;
     update_habit_streak(conn, request.habit_id, new_streak)?;
+    update_habit_totals(conn, &habit)?;
 
     // Sprawdź czy wpis oznacza ukończenie nawyku na dzisiaj i dodaj EXP
     let habit = get_habit_by_id(conn, request.habit_id)?;
-    let is_completed = match habit.habit_type {
-        HabitType::Boolean => entry.completed,
-        HabitType::Counter => {
-            if let Some(target) = habit.target_value {
-                entry.value >= target
-            } else {
-                entry.value > 0
-            }
-        }
-    };
 
-    if is_completed {
-        // Przetwórz ukończenie nawyku i dodaj EXP
-        match character_service::process_habit_completion(conn, &habit.title, new_streak) {
-            Ok((_, level_up)) => {
+    events::publish(DomainEvent::HabitLogged {
+        habit_id: habit.id,
+        title: habit.title.clone(),
+        date: entry.date.clone(),
+        completed: entry.completed,
+        value: entry.value,
+    });
+    if new_streak != previous_streak {
+        events::publish(DomainEvent::HabitStreakChanged {
+            habit_id: habit.id,
+            title: habit.title.clone(),
+            previous_streak,
+            current_streak: new_streak,
+        });
+    }
+    if habit.is_entry_completed(&entry) {
+        // Przetwórz ukończenie nawyku i dodaj EXP (raz na nawyk i dzień)
+        match character_service::process_habit_completion(
+            conn,
+            habit.id,
+            &habit.title,
+            &entry.date,
+            new_streak,
+        ) {
+            Ok(Some((_, level_up))) => {
                 if level_up {
-                    println!(
+                    eprintln!(
                         "Level up! Habit '{}' caused character to level up!",
                         habit.title
                     );
                 }
             }
+            // Ten dzień był już nagrodzony
+            Ok(None) => {}
             Err(e) => {
                 // Loguj błąd ale nie przerywaj operacji - wpis został już dodany
                 eprintln!("Failed to process habit completion for EXP: {}", e);
             }
         }
+    } else if let Err(e) = character_service::revoke_habit_completion(conn, habit.id, &entry.date) {
+        // Wpis obniżony poniżej ukończenia traci nagrodę za ten dzień
+        eprintln!("Failed to revoke habit completion EXP: {}", e);
     }
 
     Ok(entry)
 }
 
-/// Pobiera wpisy nawyków na konkretny dzień
+/// Pobiera wpisy nawyków na konkretny dzień (bez nawyków z kosza)
 ///
 /// # Arguments
 /// * `conn` - Referencja do połączenia z bazą danych
@@ -267,19 +469,15 @@ pub fn add_habit_entry(conn: &Connection, request: CreateHabitEntryRequest) -> R
 /// # Returns
 /// * `Result<Vec<HabitEntry>>` - Lista wpisów na dany dzień lub błąd
 pub fn get_habit_entries_for_date(conn: &Connection, date: &str) -> Result<Vec<HabitEntry>> {
-    let sql = "SELECT id, habit_id, date, completed, value, created_at FROM habit_entries WHERE date = ?1 ORDER BY created_at ASC";
-    let mut stmt = conn.prepare(sql)?;
-
-    let entry_iter = stmt.query_map([date], |row| {
-        Ok(HabitEntry {
-            id: row.get(0)?,
-            habit_id: row.get(1)?,
-            date: row.get(2)?,
-            completed: row.get(3)?,
-            value: row.get(4)?,
-            created_at: row.get(5)?,
-        })
-    })?;
+    let sql = format!(
+        "SELECT {} FROM habit_entries
+         WHERE date = ?1 AND habit_id IN (SELECT id FROM habits WHERE deleted_at IS NULL)
+         ORDER BY created_at ASC",
+        ENTRY_COLUMNS
+    );
+    let mut stmt = conn.prepare(&sql)?;
+
+    let entry_iter = stmt.query_map([date], row_to_entry)?;
 
     let mut entries = Vec::new();
     for entry in entry_iter {
@@ -298,19 +496,13 @@ pub fn get_habit_entries_for_date(conn: &Connection, date: &str) -> Result<Vec<H
 /// # Returns
 /// * `Result<Vec<HabitEntry>>` - Lista wpisów dla nawyku lub błąd
 pub fn get_habit_entries_for_habit(conn: &Connection, habit_id: i32) -> Result<Vec<HabitEntry>> {
-    let sql = "SELECT id, habit_id, date, completed, value, created_at FROM habit_entries WHERE habit_id = ?1 ORDER BY date DESC, created_at DESC";
-    let mut stmt = conn.prepare(sql)?;
-
-    let entry_iter = stmt.query_map([habit_id], |row| {
-        Ok(HabitEntry {
-            id: row.get(0)?,
-            habit_id: row.get(1)?,
-            date: row.get(2)?,
-            completed: row.get(3)?,
-            value: row.get(4)?,
-            created_at: row.get(5)?,
-        })
-    })?;
+    let sql = format!(
+        "SELECT {} FROM habit_entries WHERE habit_id = ?1 ORDER BY date DESC, created_at DESC",
+        ENTRY_COLUMNS
+    );
+    let mut stmt = conn.prepare(&sql)?;
+
+    let entry_iter = stmt.query_map([habit_id], row_to_entry)?;
 
     let mut entries = Vec::new();
     for entry in entry_iter {
@@ -320,60 +512,385 @@ pub fn get_habit_entries_for_habit(conn: &Connection, habit_id: i32) -> Result<V
     Ok(entries)
 }
 
-/// Oblicza aktualny streak dla nawyku
+/// Pobiera wpisy nawyków z przedziału dni (bez nawyków z kosza)
 ///
 /// # Arguments
 /// * `conn` - Referencja do połączenia z bazą danych
-/// * `habit_id` - ID nawyku
+/// * `from` - Pierwszy dzień (YYYY-MM-DD)
+/// * `to` - Ostatni dzień (YYYY-MM-DD)
+/// * `habit_id` - Tylko wpisy tego nawyku (None - wszystkich nawyków)
 ///
 /// # Returns
-/// * `Result<i32>` - Aktualny streak lub błąd
-pub fn calculate_streak(conn: &Connection, habit_id: i32) -> Result<i32> {
-    let habit = get_habit_by_id(conn, habit_id)?;
-    let entries = get_habit_entries_for_habit(conn, habit_id)?;
+/// * `Result<Vec<HabitEntry>>` - Wpisy posortowane według dnia lub błąd
+pub fn get_habit_entries_in_range(
+    conn: &Connection,
+    from: &str,
+    to: &str,
+    habit_id: Option<i32>,
+) -> Result<Vec<HabitEntry>> {
+    let sql = format!(
+        "SELECT {} FROM habit_entries
+         WHERE date BETWEEN ?1 AND ?2 AND (?3 IS NULL OR habit_id = ?3)
+           AND habit_id IN (SELECT id FROM habits WHERE deleted_at IS NULL)
+         ORDER BY date ASC, created_at ASC",
+        ENTRY_COLUMNS
+    );
+    let mut stmt = conn.prepare(&sql)?;
+
+    let entry_iter = stmt.query_map((from, to, habit_id), row_to_entry)?;
 
-    if entries.is_empty() {
-        return Ok(0);
+    let mut entries = Vec::new();
+    for entry in entry_iter {
+        entries.push(entry?);
     }
 
+    Ok(entries)
+}
+
+/// Zbiera dni, w których nawyk został wykonany, oraz dni pokryte żetonami zamrożenia
+///
+/// Liczy się tylko najnowszy wpis z danego dnia (wpisy muszą być posortowane
+/// od najnowszych, jak w `get_habit_entries_for_habit`).
+///
+/// # Returns
+/// * `(BTreeSet<NaiveDate>, BTreeSet<NaiveDate>)` - Dni wykonane i dni zamrożone
+pub(crate) fn entry_dates(
+    habit: &Habit,
+    entries: &[HabitEntry],
+) -> (BTreeSet<NaiveDate>, BTreeSet<NaiveDate>) {
+    let mut seen_dates = HashSet::new();
+    let mut done = BTreeSet::new();
+    let mut frozen = BTreeSet::new();
+
+    for entry in entries
+        .iter()
+        .filter(|entry| seen_dates.insert(entry.date.as_str()))
+    {
+        let Ok(date) = NaiveDate::parse_from_str(&entry.date, "%Y-%m-%d") else {
+            continue;
+        };
+        if habit.is_entry_completed(entry) {
+            done.insert(date);
+        } else if entry.frozen {
+            frozen.insert(date);
+        }
+    }
+
+    (done, frozen)
+}
+
+/// Zwraca poniedziałek tygodnia, do którego należy dzień
+pub(crate) fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(i64::from(date.weekday().num_days_from_monday()))
+}
+
+/// Liczy kolejne wykonania, z których każde wypada najpóźniej `interval` dni po poprzednim
+///
+/// Ostatnie wykonanie musi mieścić się w bieżącym terminie. Dopóki dzisiaj nie ma wpisu,
+/// termin liczy się od wczoraj, więc streak nie spada do zera w trakcie dnia.
+/// Dni zamrożone podtrzymują łańcuch, ale nie zwiększają streaka.
+fn interval_streak(
+    done: &BTreeSet<NaiveDate>,
+    frozen: &BTreeSet<NaiveDate>,
+    today: NaiveDate,
+    interval: u32,
+) -> i32 {
+    let covered: BTreeSet<NaiveDate> = done.union(frozen).copied().collect();
     let mut streak = 0;
-    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
-    let mut current_date = chrono::NaiveDate::parse_from_str(&today, "%Y-%m-%d")
-        .map_err(|e| anyhow::anyhow!("Failed to parse date: {}", e))?;
+    let mut deadline = if covered.contains(&today) {
+        today
+    } else {
+        today - Duration::days(1)
+    };
 
-    // Sprawdź ciągłość od dzisiaj wstecz
-    loop {
-        let date_str = current_date.format("%Y-%m-%d").to_string();
+    for &date in covered.range(..=today).rev() {
+        if (deadline - date).num_days() >= i64::from(interval) {
+            break;
+        }
+        if done.contains(&date) {
+            streak += 1;
+        }
+        deadline = date - Duration::days(1);
+    }
 
-        // Znajdź najnowszy wpis dla tej daty (największy created_at)
-        if let Some(entry) = entries
-            .iter()
-            .filter(|e| e.date == date_str)
-            .max_by_key(|e| e.created_at)
-        {
-            let is_completed = match habit.habit_type {
-                HabitType::Boolean => entry.completed,
-                HabitType::Counter => {
-                    if let Some(target) = habit.target_value {
-                        entry.value >= target
-                    } else {
-                        entry.value > 0
+    streak
+}
+
+/// Oblicza streak według harmonogramu
+///
+/// Dni spoza harmonogramu nie przerywają streaka (i nie są do niego liczone).
+/// Dla `TimesPerWeek` streak to liczba kolejnych tygodni z osiągniętym celem,
+/// a trwający tydzień przerywa go dopiero po swoim zakończeniu. Niezalogowany
+/// dzisiejszy dzień nie przerywa streaka, a dni zamrożone wypełniają luki
+/// bez zwiększania streaka.
+///
+/// # Arguments
+/// * `schedule` - Harmonogram nawyku
+/// * `done` - Dni, w których nawyk został wykonany
+/// * `frozen` - Dni pokryte żetonami zamrożenia
+/// * `today` - Bieżący dzień
+fn streak_for_schedule(
+    schedule: &HabitSchedule,
+    done: &BTreeSet<NaiveDate>,
+    frozen: &BTreeSet<NaiveDate>,
+    today: NaiveDate,
+) -> i32 {
+    let first = match (done.first(), frozen.first()) {
+        (Some(&done), Some(&frozen)) => done.min(frozen),
+        (Some(&date), None) | (None, Some(&date)) => date,
+        (None, None) => return 0,
+    };
+
+    match schedule {
+        HabitSchedule::Daily => interval_streak(done, frozen, today, 1),
+        HabitSchedule::EveryNDays { interval } => interval_streak(done, frozen, today, *interval),
+        HabitSchedule::Weekdays { days } => {
+            let mut streak = 0;
+            let mut date = today;
+            if !done.contains(&today) && !frozen.contains(&today) {
+                date -= Duration::days(1);
+            }
+
+            // Sprawdź kolejne zaplanowane dni od dzisiaj (lub od wczoraj) wstecz
+            while date >= first {
+                if days.contains(&date.weekday().number_from_monday()) {
+                    if done.contains(&date) {
+                        streak += 1;
+                    } else if !frozen.contains(&date) {
+                        break;
                     }
                 }
+                date -= Duration::days(1);
+            }
+
+            streak
+        }
+        HabitSchedule::TimesPerWeek { times } => {
+            let mut streak = 0;
+            let mut start = week_start(today);
+
+            loop {
+                let end = (start + Duration::days(7)).min(today + Duration::days(1));
+                let count = done.range(start..end).count();
+                let covered = count + frozen.range(start..end).count();
+                if count >= *times as usize {
+                    streak += 1;
+                } else if covered < *times as usize && end <= today {
+                    break;
+                }
+
+                if start <= first {
+                    break;
+                }
+                start -= Duration::days(7);
+            }
+
+            streak
+        }
+    }
+}
+
+/// Wyznacza opuszczone dni ostatniej luki w harmonogramie (od ostatniego pokrytego dnia do dzisiaj)
+///
+/// Dla `TimesPerWeek` są to brakujące dni w zakończonych tygodniach bez osiągniętego celu
+/// (od końca tygodnia). Zwraca pusty wektor, gdy luki nie ma, gdy przed nią nie było
+/// czego ratować albo gdy potrzeba więcej niż `limit` dni.
+///
+/// # Arguments
+/// * `schedule` - Harmonogram nawyku
+/// * `covered` - Dni wykonane lub zamrożone przed dzisiejszym dniem
+/// * `today` - Bieżący dzień
+/// * `limit` - Największa liczba dni, którą da się pokryć
+fn missed_days(
+    schedule: &HabitSchedule,
+    covered: &BTreeSet<NaiveDate>,
+    today: NaiveDate,
+    limit: usize,
+) -> Vec<NaiveDate> {
+    let Some(&first) = covered.first() else {
+        return Vec::new();
+    };
+    let mut missed = Vec::new();
+
+    match schedule {
+        HabitSchedule::Daily | HabitSchedule::EveryNDays { .. } => {
+            let interval = match schedule {
+                HabitSchedule::EveryNDays { interval } => i64::from(*interval),
+                _ => 1,
+            };
+            let mut previous = *covered.last().unwrap_or(&first);
+            while (today - previous).num_days() > interval {
+                previous += Duration::days(interval);
+                missed.push(previous);
+                if missed.len() > limit {
+                    return Vec::new();
+                }
+            }
+        }
+        HabitSchedule::Weekdays { days } => {
+            let is_scheduled =
+                |date: &NaiveDate| days.contains(&date.weekday().number_from_monday());
+            let Some(&last) = covered.iter().rev().find(|date| is_scheduled(date)) else {
+                return Vec::new();
             };
+            let mut date = last + Duration::days(1);
+            while date < today {
+                if is_scheduled(&date) {
+                    missed.push(date);
+                    if missed.len() > limit {
+                        return Vec::new();
+                    }
+                }
+                date += Duration::days(1);
+            }
+        }
+        HabitSchedule::TimesPerWeek { times } => {
+            let target = *times as usize;
+            let mut start = week_start(today) - Duration::days(7);
+
+            loop {
+                // Brak wcześniejszego tygodnia z osiągniętym celem - nie ma czego ratować
+                if start + Duration::days(7) <= first {
+                    return Vec::new();
+                }
+                let count = covered.range(start..start + Duration::days(7)).count();
+                if count >= target {
+                    break;
+                }
 
-            if is_completed {
-                streak += 1;
-                current_date = current_date - chrono::Duration::days(1);
-            } else {
-                break;
+                let mut date = start + Duration::days(6);
+                for _ in count..target {
+                    while covered.contains(&date) {
+                        date -= Duration::days(1);
+                    }
+                    missed.push(date);
+                    date -= Duration::days(1);
+                }
+                if missed.len() > limit {
+                    return Vec::new();
+                }
+                start -= Duration::days(7);
             }
-        } else {
-            break;
         }
     }
 
-    Ok(streak)
+    missed
+}
+
+/// Pokrywa żetonami zamrożenia opuszczone dni przed dzisiejszym wykonaniem nawyku
+///
+/// Żetony są zużywane tylko wtedy, gdy wystarczy ich na całą lukę. Zamrożone dni trafiają
+/// do historii wpisów z flagą `frozen`.
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `habit` - Nawyk
+/// * `today` - Bieżący dzień
+///
+/// # Returns
+/// * `Result<usize>` - Liczba zamrożonych dni lub błąd
+fn apply_streak_freezes(conn: &Connection, habit: &Habit, today: NaiveDate) -> Result<usize> {
+    let entries = get_habit_entries_for_habit(conn, habit.id)?;
+    let (done, frozen) = entry_dates(habit, &entries);
+    let covered: BTreeSet<NaiveDate> = done
+        .union(&frozen)
+        .copied()
+        .filter(|date| *date < today)
+        .collect();
+
+    let missed = missed_days(
+        &habit.schedule,
+        &covered,
+        today,
+        MAX_STREAK_FREEZES as usize,
+    );
+    if missed.is_empty() || !character_service::use_streak_freezes(conn, missed.len() as i32)? {
+        return Ok(0);
+    }
+
+    let now = chrono::Utc::now().timestamp();
+    for date in &missed {
+        conn.execute(
+            "INSERT INTO habit_entries (habit_id, date, completed, value, frozen, created_at)
+             VALUES (?1, ?2, 0, 0, 1, ?3)
+             ON CONFLICT(habit_id, date) DO UPDATE SET frozen = 1",
+            (habit.id, date.format("%Y-%m-%d").to_string(), now),
+        )?;
+    }
+
+    Ok(missed.len())
+}
+
+/// Sprawdza stan harmonogramu w danym dniu
+///
+/// # Returns
+/// * `(bool, bool)` - Czy dzień jest zaplanowany i czy harmonogram jest na ten dzień spełniony
+pub(crate) fn day_status(
+    schedule: &HabitSchedule,
+    done: &BTreeSet<NaiveDate>,
+    date: NaiveDate,
+) -> (bool, bool) {
+    let completed = done.contains(&date);
+
+    match schedule {
+        HabitSchedule::Daily => (true, completed),
+        HabitSchedule::Weekdays { days } => (
+            days.contains(&date.weekday().number_from_monday()),
+            completed,
+        ),
+        HabitSchedule::EveryNDays { interval } => {
+            let window_start = date - Duration::days(i64::from(*interval) - 1);
+            (done.range(window_start..date).next().is_none(), completed)
+        }
+        HabitSchedule::TimesPerWeek { times } => {
+            let before = done.range(week_start(date)..date).count();
+            let target = *times as usize;
+            (before < target, before + usize::from(completed) >= target)
+        }
+    }
+}
+
+/// Oblicza aktualny streak dla nawyku (według jego harmonogramu)
+///
+/// "Dzisiaj" wyznacza strefa czasowa i godzina rozpoczęcia dnia z ustawień.
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `habit_id` - ID nawyku
+///
+/// # Returns
+/// * `Result<i32>` - Aktualny streak lub błąd
+pub fn calculate_streak(conn: &Connection, habit_id: i32) -> Result<i32> {
+    let habit = get_habit_by_id(conn, habit_id)?;
+    let entries = get_habit_entries_for_habit(conn, habit_id)?;
+    let today = settings_service::today(conn)?;
+    let (done, frozen) = entry_dates(&habit, &entries);
+
+    Ok(streak_for_schedule(&habit.schedule, &done, &frozen, today))
+}
+
+/// Sprawdza, czy nawyk jest zaplanowany na dany dzień i czy został na ten dzień wykonany
+///
+/// Dla `TimesPerWeek` dzień jest zaplanowany, dopóki wcześniej w tygodniu brakuje wykonań,
+/// a wykonany, gdy cel tygodniowy został osiągnięty. Dla `EveryNDays` dzień jest zaplanowany,
+/// gdy od ostatniego wykonania minęło co najmniej N dni.
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `habit` - Nawyk
+/// * `date` - Sprawdzany dzień
+///
+/// # Returns
+/// * `Result<(bool, bool)>` - Czy dzień jest zaplanowany i czy harmonogram jest spełniony
+pub fn schedule_status(conn: &Connection, habit: &Habit, date: NaiveDate) -> Result<(bool, bool)> {
+    let entries = get_habit_entries_for_habit(conn, habit.id)?;
+    let (done, frozen) = entry_dates(habit, &entries);
+
+    Ok(day_status(
+        &habit.schedule,
+        &done.union(&frozen).copied().collect(),
+        date,
+    ))
 }
 
 /// Aktualizuje streak nawyku w bazie danych
@@ -385,13 +902,37 @@ pub fn calculate_streak(conn: &Connection, habit_id: i32) -> Result<i32> {
 ///
 /// # Returns
 /// * `Result<()>` - Sukces lub błąd
-fn update_habit_streak(conn: &Connection, habit_id: i32, new_streak: i32) -> Result<()> {
+pub(crate) fn update_habit_streak(conn: &Connection, habit_id: i32, new_streak: i32) -> Result<()> {
     let sql = "UPDATE habits SET current_streak = ?1, updated_at = ?2 WHERE id = ?3";
     let now = chrono::Utc::now().timestamp();
     conn.execute(sql, (new_streak, now, habit_id))?;
     Ok(())
 }
 
+/// Przelicza najlepszy streak i łączną liczbę dni z wykonanym nawykiem
+///
+/// Najlepszy streak pochodzi z pełnej historii serii, więc uwzględnia wpisy dodane wstecz,
+/// a po obniżeniu wpisu (i cofnięciu nagrody) spada razem z przerwaną serią.
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `habit` - Nawyk (z aktualnym typem, wartością docelową i harmonogramem)
+///
+/// # Returns
+/// * `Result<()>` - Sukces lub błąd
+pub(crate) fn update_habit_totals(conn: &Connection, habit: &Habit) -> Result<()> {
+    let entries = get_habit_entries_for_habit(conn, habit.id)?;
+    let (done, frozen) = entry_dates(habit, &entries);
+    let today = settings_service::today(conn)?;
+    let longest = habit_stats_service::longest_streak(&habit.schedule, &done, &frozen, today);
+
+    conn.execute(
+        "UPDATE habits SET best_streak = ?1, total_completions = ?2 WHERE id = ?3",
+        (longest, done.len() as i32, habit.id),
+    )?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -408,9 +949,13 @@ mod tests {
                 title TEXT NOT NULL,
                 habit_type TEXT NOT NULL CHECK (habit_type IN ('Boolean', 'Counter')),
                 target_value INTEGER,
+                schedule TEXT NOT NULL DEFAULT '{\"type\":\"Daily\"}',
                 current_streak INTEGER NOT NULL DEFAULT 0,
+                best_streak INTEGER NOT NULL DEFAULT 0,
+                total_completions INTEGER NOT NULL DEFAULT 0,
                 created_at INTEGER NOT NULL,
-                updated_at INTEGER NOT NULL
+                updated_at INTEGER NOT NULL,
+                deleted_at INTEGER
             )",
             [],
         )?;
@@ -422,6 +967,7 @@ mod tests {
                 date TEXT NOT NULL,
                 completed BOOLEAN NOT NULL DEFAULT 0,
                 value INTEGER NOT NULL DEFAULT 0,
+                frozen INTEGER NOT NULL DEFAULT 0,
                 created_at INTEGER NOT NULL,
                 FOREIGN KEY (habit_id) REFERENCES habits(id) ON DELETE CASCADE,
                 UNIQUE(habit_id, date)
@@ -429,6 +975,15 @@ mod tests {
             [],
         )?;
 
+        conn.execute(
+            "CREATE TABLE settings (
+                key TEXT PRIMARY KEY NOT NULL,
+                value TEXT NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
         Ok(conn)
     }
 
@@ -439,6 +994,7 @@ mod tests {
             title: "Daily meditation".to_string(),
             habit_type: HabitType::Boolean,
             target_value: None,
+            ..Default::default()
         };
 
         let habit = add_habit(&conn, request).unwrap();
@@ -457,6 +1013,7 @@ mod tests {
             title: "Drink water".to_string(),
             habit_type: HabitType::Counter,
             target_value: Some(8),
+            ..Default::default()
         };
 
         let habit = add_habit(&conn, request).unwrap();
@@ -475,11 +1032,13 @@ mod tests {
             title: "Meditation".to_string(),
             habit_type: HabitType::Boolean,
             target_value: None,
+            ..Default::default()
         };
         let request2 = CreateHabitRequest {
             title: "Water".to_string(),
             habit_type: HabitType::Counter,
             target_value: Some(8),
+            ..Default::default()
         };
 
         add_habit(&conn, request1).unwrap();
@@ -493,6 +1052,74 @@ mod tests {
         assert!(habit_titles.contains(&"Water".to_string()));
     }
 
+    #[test]
+    fn test_query_habits() {
+        let conn = create_test_db().unwrap();
+
+        for (title, habit_type, streak) in [
+            ("Meditation", HabitType::Boolean, 3),
+            ("Water", HabitType::Counter, 10),
+            ("Reading", HabitType::Boolean, 7),
+        ] {
+            let habit = add_habit(
+                &conn,
+                CreateHabitRequest {
+                    title: title.to_string(),
+                    habit_type,
+                    target_value: None,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            update_habit_streak(&conn, habit.id, streak).unwrap();
+        }
+
+        let page = query_habits(
+            &conn,
+            HabitQuery {
+                habit_type: Some(HabitType::Boolean),
+                sort: Some(HabitSortField::CurrentStreak),
+                limit: Some(1),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(page.items[0].title, "Reading");
+
+        let next = query_habits(
+            &conn,
+            HabitQuery {
+                habit_type: Some(HabitType::Boolean),
+                sort: Some(HabitSortField::CurrentStreak),
+                limit: Some(1),
+                cursor: page.next_cursor,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(next.items[0].title, "Meditation");
+        assert!(next.next_cursor.is_none());
+
+        // Kursor z innym polem sortowania jest odrzucany
+        let first = query_habits(
+            &conn,
+            HabitQuery {
+                limit: Some(1),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(query_habits(
+            &conn,
+            HabitQuery {
+                sort: Some(HabitSortField::Title),
+                cursor: first.next_cursor,
+                ..Default::default()
+            },
+        )
+        .is_err());
+    }
+
     #[test]
     fn test_add_habit_entry() {
         let conn = create_test_db().unwrap();
@@ -501,6 +1128,7 @@ mod tests {
             title: "Test habit".to_string(),
             habit_type: HabitType::Boolean,
             target_value: None,
+            ..Default::default()
         };
         let habit = add_habit(&conn, habit_request).unwrap();
 
@@ -518,4 +1146,291 @@ mod tests {
         assert!(entry.completed);
         assert_eq!(entry.value, 0);
     }
+
+    #[test]
+    fn test_add_habit_entry_for_missing_habit() {
+        let conn = create_test_db().unwrap();
+
+        let entry_request = CreateHabitEntryRequest {
+            habit_id: 999,
+            date: "2025-01-20".to_string(),
+            completed: Some(true),
+            value: None,
+        };
+
+        let err = add_habit_entry(&conn, entry_request).unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<error::ServiceError>(),
+            Some(error::ServiceError::NotFound(_))
+        ));
+    }
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn dates(values: &[&str]) -> BTreeSet<NaiveDate> {
+        values.iter().map(|value| date(value)).collect()
+    }
+
+    #[test]
+    fn test_streak_skips_days_outside_schedule() {
+        let gym = HabitSchedule::Weekdays {
+            days: vec![1, 3, 5],
+        };
+        // Wtorek 2025-03-11: poniedziałek, piątek i środa wykonane, poniedziałek 03-03 pominięty
+        let done = dates(&["2025-03-10", "2025-03-07", "2025-03-05", "2025-02-28"]);
+        assert_eq!(
+            streak_for_schedule(&gym, &done, &BTreeSet::new(), date("2025-03-11")),
+            3
+        );
+        assert_eq!(
+            streak_for_schedule(
+                &HabitSchedule::Daily,
+                &done,
+                &BTreeSet::new(),
+                date("2025-03-11")
+            ),
+            1
+        );
+        assert_eq!(day_status(&gym, &done, date("2025-03-11")), (false, false));
+        assert_eq!(day_status(&gym, &done, date("2025-03-12")), (true, false));
+    }
+
+    #[test]
+    fn test_streak_for_weekly_target_and_interval() {
+        let weekly = HabitSchedule::TimesPerWeek { times: 3 };
+        let mut done = dates(&[
+            "2025-03-10",
+            "2025-03-03",
+            "2025-03-05",
+            "2025-03-09",
+            "2025-02-25",
+        ]);
+        // Trwający tydzień z jednym wykonaniem jeszcze nie przerywa streaka
+        assert_eq!(
+            streak_for_schedule(&weekly, &done, &BTreeSet::new(), date("2025-03-12")),
+            1
+        );
+        assert_eq!(
+            day_status(&weekly, &done, date("2025-03-12")),
+            (true, false)
+        );
+
+        done.extend(dates(&["2025-03-11", "2025-03-12"]));
+        assert_eq!(
+            streak_for_schedule(&weekly, &done, &BTreeSet::new(), date("2025-03-12")),
+            2
+        );
+        assert_eq!(day_status(&weekly, &done, date("2025-03-12")), (true, true));
+        assert_eq!(
+            day_status(&weekly, &done, date("2025-03-13")),
+            (false, true)
+        );
+
+        let every_third = HabitSchedule::EveryNDays { interval: 3 };
+        let done = dates(&["2025-03-10", "2025-03-07", "2025-03-04", "2025-02-28"]);
+        assert_eq!(
+            streak_for_schedule(&every_third, &done, &BTreeSet::new(), date("2025-03-12")),
+            3
+        );
+        assert_eq!(
+            streak_for_schedule(&every_third, &done, &BTreeSet::new(), date("2025-03-13")),
+            3
+        );
+        assert_eq!(
+            streak_for_schedule(&every_third, &done, &BTreeSet::new(), date("2025-03-14")),
+            0
+        );
+        assert_eq!(
+            day_status(&every_third, &done, date("2025-03-12")),
+            (false, false)
+        );
+        assert_eq!(
+            day_status(&every_third, &done, date("2025-03-13")),
+            (true, false)
+        );
+    }
+
+    #[test]
+    fn test_streak_waits_for_today_and_bridges_frozen_days() {
+        let done = dates(&["2025-03-08", "2025-03-09", "2025-03-11"]);
+        let frozen = dates(&["2025-03-10"]);
+        // Dzisiaj (03-12) jeszcze bez wpisu, a zamrożony 03-10 nie zwiększa streaka
+        assert_eq!(
+            streak_for_schedule(&HabitSchedule::Daily, &done, &frozen, date("2025-03-12")),
+            3
+        );
+        assert_eq!(
+            streak_for_schedule(
+                &HabitSchedule::Daily,
+                &done,
+                &BTreeSet::new(),
+                date("2025-03-12")
+            ),
+            1
+        );
+
+        let covered = dates(&["2025-03-09"]);
+        assert_eq!(
+            missed_days(&HabitSchedule::Daily, &covered, date("2025-03-12"), 3),
+            vec![date("2025-03-10"), date("2025-03-11")]
+        );
+        assert!(missed_days(&HabitSchedule::Daily, &covered, date("2025-03-12"), 1).is_empty());
+
+        // Tydzień 03-03 bez celu zostaje uzupełniony od niedzieli
+        let weekly = HabitSchedule::TimesPerWeek { times: 2 };
+        let covered = dates(&["2025-02-24", "2025-02-25", "2025-03-03"]);
+        assert_eq!(
+            missed_days(&weekly, &covered, date("2025-03-12"), 3),
+            vec![date("2025-03-09")]
+        );
+    }
+
+    #[test]
+    fn test_streak_freeze_covers_missed_day() {
+        let db = crate::database::Database::new(":memory:".into()).unwrap();
+        let conn = db.connection();
+        character_service::create_character(
+            conn,
+            crate::models::CreateCharacterRequest {
+                character_class: crate::models::CharacterClass::Warrior,
+            },
+        )
+        .unwrap();
+        character_service::add_experience(conn, 150).unwrap();
+
+        let habit = add_habit(
+            conn,
+            CreateHabitRequest {
+                title: "Bieganie".to_string(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let today = settings_service::today(conn).unwrap();
+        let log = |days_ago: i64| {
+            add_habit_entry(
+                conn,
+                CreateHabitEntryRequest {
+                    habit_id: habit.id,
+                    date: (today - Duration::days(days_ago))
+                        .format("%Y-%m-%d")
+                        .to_string(),
+                    completed: Some(true),
+                    value: None,
+                },
+            )
+            .unwrap();
+        };
+
+        log(3);
+        log(2);
+        log(0);
+
+        assert_eq!(get_habit_by_id(conn, habit.id).unwrap().current_streak, 3);
+        assert_eq!(
+            character_service::get_character(conn)
+                .unwrap()
+                .streak_freezes,
+            0
+        );
+        let entries = get_habit_entries_for_habit(conn, habit.id).unwrap();
+        let frozen: Vec<&str> = entries
+            .iter()
+            .filter(|entry| entry.frozen)
+            .map(|entry| entry.date.as_str())
+            .collect();
+        assert_eq!(
+            frozen,
+            vec![(today - Duration::days(1)).format("%Y-%m-%d").to_string()]
+        );
+    }
+
+    #[test]
+    fn test_streak_follows_configured_time_zone() {
+        let conn = create_test_db().unwrap();
+        let habit = add_habit(
+            &conn,
+            CreateHabitRequest {
+                title: "Medytacja".to_string(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        // UTC+14 i UTC-11 zawsze wskazują różne dni
+        settings_service::set_time_zone(&conn, "Pacific/Kiritimati").unwrap();
+        let today = settings_service::today(&conn).unwrap();
+        add_habit_entry(
+            &conn,
+            CreateHabitEntryRequest {
+                habit_id: habit.id,
+                date: today.format("%Y-%m-%d").to_string(),
+                completed: Some(true),
+                value: None,
+            },
+        )
+        .unwrap();
+        assert_eq!(calculate_streak(&conn, habit.id).unwrap(), 1);
+
+        settings_service::set_time_zone(&conn, "Pacific/Pago_Pago").unwrap();
+        assert!(settings_service::today(&conn).unwrap() < today);
+        assert_eq!(calculate_streak(&conn, habit.id).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_habit_schedule_is_validated_and_stored() {
+        let conn = create_test_db().unwrap();
+        let invalid = add_habit(
+            &conn,
+            CreateHabitRequest {
+                title: "Siłownia".to_string(),
+                schedule: HabitSchedule::Weekdays { days: vec![] },
+                ..Default::default()
+            },
+        );
+        assert!(invalid.is_err());
+
+        let habit = add_habit(
+            &conn,
+            CreateHabitRequest {
+                title: "Siłownia".to_string(),
+                schedule: HabitSchedule::Weekdays {
+                    days: vec![5, 1, 3, 1],
+                },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            get_habit_by_id(&conn, habit.id).unwrap().schedule,
+            HabitSchedule::Weekdays {
+                days: vec![1, 3, 5]
+            }
+        );
+
+        let updated = update_habit(
+            &conn,
+            habit.id,
+            UpdateHabitRequest {
+                title: None,
+                target_value: None,
+                schedule: Some(HabitSchedule::TimesPerWeek { times: 2 }),
+            },
+        )
+        .unwrap();
+        assert_eq!(updated.schedule, HabitSchedule::TimesPerWeek { times: 2 });
+        assert!(update_habit(
+            &conn,
+            habit.id,
+            UpdateHabitRequest {
+                title: None,
+                target_value: None,
+                schedule: Some(HabitSchedule::EveryNDays { interval: 0 }),
+            },
+        )
+        .is_err());
+    }
 }
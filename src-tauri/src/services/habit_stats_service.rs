@@ -0,0 +1,755 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use anyhow::Result;
+use chrono::{Datelike, Duration, NaiveDate};
+use rusqlite::Connection;
+
+use crate::models::{
+    Habit, HabitEntry, HabitPeriodStats, HabitSchedule, HabitStats, HabitType, HeatmapDay,
+    HeatmapQuery, StreakRun, WeekdayStats,
+};
+use crate::services::habit_service::{self, week_start};
+use crate::services::{error, settings_service};
+
+/// Okresy (w dniach), dla których liczony jest odsetek wykonania
+const PERIODS: [u32; 3] = [7, 30, 90];
+/// Najdłuższy przedział kalendarza wypełnienia (w dniach)
+const MAX_HEATMAP_DAYS: i64 = 366;
+/// Długość domyślnego przedziału kalendarza wypełnienia (w dniach)
+const DEFAULT_HEATMAP_DAYS: i64 = 365;
+
+/// Seria w trakcie budowania historii
+struct Run {
+    start: NaiveDate,
+    end: NaiveDate,
+    length: i32,
+}
+
+/// Przedłuża bieżącą serię o kolejny termin harmonogramu (lub zaczyna nową)
+fn extend_run(current: &mut Option<Run>, start: NaiveDate, end: NaiveDate) {
+    match current {
+        Some(run) => {
+            run.end = end;
+            run.length += 1;
+        }
+        None => {
+            *current = Some(Run {
+                start,
+                end,
+                length: 1,
+            })
+        }
+    }
+}
+
+/// Odtwarza serie nawyku od najstarszej
+///
+/// Zasady są te same co przy liczeniu bieżącego streaka: dni spoza harmonogramu i dni
+/// zamrożone nie przerywają serii, a niezalogowany dzisiejszy dzień (lub trwający tydzień
+/// dla `TimesPerWeek`) jeszcze jej nie kończy.
+///
+/// # Arguments
+/// * `schedule` - Harmonogram nawyku
+/// * `done` - Dni, w których nawyk został wykonany
+/// * `frozen` - Dni pokryte żetonami zamrożenia
+/// * `today` - Bieżący dzień
+fn streak_runs(
+    schedule: &HabitSchedule,
+    done: &BTreeSet<NaiveDate>,
+    frozen: &BTreeSet<NaiveDate>,
+    today: NaiveDate,
+) -> Vec<Run> {
+    let covered: BTreeSet<NaiveDate> = done.union(frozen).copied().collect();
+    let Some(&first) = covered.first() else {
+        return Vec::new();
+    };
+    let mut runs = Vec::new();
+    let mut current: Option<Run> = None;
+
+    match schedule {
+        HabitSchedule::Daily | HabitSchedule::EveryNDays { .. } => {
+            let interval = match schedule {
+                HabitSchedule::EveryNDays { interval } => i64::from(*interval),
+                _ => 1,
+            };
+            let mut previous: Option<NaiveDate> = None;
+
+            for &date in covered.range(..=today) {
+                let chained =
+                    previous.is_some_and(|previous| (date - previous).num_days() <= interval);
+                if !chained {
+                    runs.extend(current.take());
+                }
+                previous = Some(date);
+                if done.contains(&date) {
+                    extend_run(&mut current, date, date);
+                }
+            }
+        }
+        HabitSchedule::Weekdays { days } => {
+            let mut date = first;
+
+            while date <= today {
+                if days.contains(&date.weekday().number_from_monday()) {
+                    if done.contains(&date) {
+                        extend_run(&mut current, date, date);
+                    } else if !frozen.contains(&date) && date < today {
+                        runs.extend(current.take());
+                    }
+                }
+                date += Duration::days(1);
+            }
+        }
+        HabitSchedule::TimesPerWeek { times } => {
+            let target = *times as usize;
+            let mut start = week_start(first);
+
+            while start <= today {
+                let end = (start + Duration::days(7)).min(today + Duration::days(1));
+                let week: Vec<NaiveDate> = done.range(start..end).copied().collect();
+                let covered_count = week.len() + frozen.range(start..end).count();
+                if week.len() >= target {
+                    if let (Some(&week_first), Some(&week_last)) = (week.first(), week.last()) {
+                        extend_run(&mut current, week_first, week_last);
+                    }
+                } else if covered_count < target && end <= today {
+                    runs.extend(current.take());
+                }
+                start += Duration::days(7);
+            }
+        }
+    }
+
+    runs.extend(current);
+    runs
+}
+
+/// Zwraca długość najdłuższej serii w historii nawyku
+///
+/// # Arguments
+/// * `schedule` - Harmonogram nawyku
+/// * `done` - Dni, w których nawyk został wykonany
+/// * `frozen` - Dni pokryte żetonami zamrożenia
+/// * `today` - Bieżący dzień
+pub(crate) fn longest_streak(
+    schedule: &HabitSchedule,
+    done: &BTreeSet<NaiveDate>,
+    frozen: &BTreeSet<NaiveDate>,
+    today: NaiveDate,
+) -> i32 {
+    streak_runs(schedule, done, frozen, today)
+        .iter()
+        .map(|run| run.length)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Liczba wykonań oczekiwana według harmonogramu w przedziale dni (włącznie)
+fn expected_completions(schedule: &HabitSchedule, start: NaiveDate, end: NaiveDate) -> u32 {
+    let days = u32::try_from((end - start).num_days() + 1).unwrap_or(0);
+
+    match schedule {
+        HabitSchedule::Daily => days,
+        HabitSchedule::Weekdays { days: weekdays } => start
+            .iter_days()
+            .take(days as usize)
+            .filter(|date| weekdays.contains(&date.weekday().number_from_monday()))
+            .count() as u32,
+        HabitSchedule::EveryNDays { interval } => days.div_ceil(*interval),
+        HabitSchedule::TimesPerWeek { times } => (days * times).div_ceil(7),
+    }
+}
+
+/// Odsetek wykonania (0.0, gdy nic nie było oczekiwane)
+fn completion_rate(completed: u32, expected: u32) -> f64 {
+    if expected == 0 {
+        0.0
+    } else {
+        f64::from(completed) / f64::from(expected)
+    }
+}
+
+/// Liczy wykonanie nawyku w ostatnich `days` dniach kończących się w dniu `last_day`
+///
+/// Okres nie sięga przed początek historii nawyku, a wykonania ponad harmonogram
+/// (np. codzienne wykonania nawyku "co 3 dni") nie podnoszą odsetka powyżej 100%.
+fn period_stats(
+    habit: &Habit,
+    done: &BTreeSet<NaiveDate>,
+    values: &BTreeMap<NaiveDate, i32>,
+    first_day: NaiveDate,
+    last_day: NaiveDate,
+    days: u32,
+) -> HabitPeriodStats {
+    let start = (last_day - Duration::days(i64::from(days) - 1)).max(first_day);
+    if start > last_day {
+        return HabitPeriodStats {
+            days,
+            expected: 0,
+            completed: 0,
+            completion_rate: 0.0,
+            average_value: None,
+        };
+    }
+
+    let expected = expected_completions(&habit.schedule, start, last_day);
+    let completed = done
+        .range(start..=last_day)
+        .filter(|date| match &habit.schedule {
+            HabitSchedule::Weekdays { days } => days.contains(&date.weekday().number_from_monday()),
+            _ => true,
+        })
+        .count() as u32;
+    let completed = completed.min(expected);
+
+    let average_value = matches!(habit.habit_type, HabitType::Counter).then(|| {
+        let total: i32 = values.range(start..=last_day).map(|(_, value)| value).sum();
+        f64::from(total) / ((last_day - start).num_days() + 1) as f64
+    });
+
+    HabitPeriodStats {
+        days,
+        expected,
+        completed,
+        completion_rate: completion_rate(completed, expected),
+        average_value,
+    }
+}
+
+/// Liczy wykonanie nawyku w poszczególne dni tygodnia (od poniedziałku)
+fn weekday_stats(
+    done: &BTreeSet<NaiveDate>,
+    first_day: NaiveDate,
+    last_day: NaiveDate,
+) -> Vec<WeekdayStats> {
+    let mut stats: Vec<WeekdayStats> = (1..=7)
+        .map(|weekday| WeekdayStats {
+            weekday,
+            days: 0,
+            completed: 0,
+            completion_rate: 0.0,
+        })
+        .collect();
+
+    let mut date = first_day;
+    while date <= last_day {
+        let day = &mut stats[date.weekday().num_days_from_monday() as usize];
+        day.days += 1;
+        if done.contains(&date) {
+            day.completed += 1;
+        }
+        date += Duration::days(1);
+    }
+
+    for day in &mut stats {
+        day.completion_rate = completion_rate(day.completed, day.days);
+    }
+    stats
+}
+
+/// Pobiera statystyki nawyku
+///
+/// Dopóki dzisiaj nie ma wykonania, okresy kończą się wczoraj (tak jak streak).
+/// Historia zaczyna się w dniu utworzenia nawyku lub w dniu najstarszego wpisu, jeśli jest wcześniejszy.
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `habit_id` - ID nawyku
+///
+/// # Returns
+/// * `Result<HabitStats>` - Statystyki nawyku lub błąd not found
+pub fn get_habit_stats(conn: &Connection, habit_id: i32) -> Result<HabitStats> {
+    let habit = habit_service::get_habit_by_id(conn, habit_id)?;
+    let entries = habit_service::get_habit_entries_for_habit(conn, habit_id)?;
+    let (done, frozen) = habit_service::entry_dates(&habit, &entries);
+    let today = settings_service::today(conn)?;
+    let current_streak = habit_service::calculate_streak(conn, habit_id)?;
+
+    // Wpisy są posortowane od najnowszych, więc liczy się pierwszy wpis z danego dnia
+    let mut values = BTreeMap::new();
+    for entry in &entries {
+        if let Ok(date) = NaiveDate::parse_from_str(&entry.date, "%Y-%m-%d") {
+            values.entry(date).or_insert(entry.value);
+        }
+    }
+
+    let created = chrono::DateTime::from_timestamp(habit.created_at, 0)
+        .map(|created_at| {
+            settings_service::get_time_settings(conn).map(|time| time.day_of(created_at))
+        })
+        .transpose()?
+        .unwrap_or(today);
+    let first_day = values
+        .keys()
+        .next()
+        .map_or(created, |&first| first.min(created));
+    let last_day = if done.contains(&today) {
+        today
+    } else {
+        today - Duration::days(1)
+    };
+
+    let runs = streak_runs(&habit.schedule, &done, &frozen, today);
+    let best_streak = runs
+        .iter()
+        .map(|run| run.length)
+        .chain([current_streak])
+        .max()
+        .unwrap_or(0);
+    let run_count = runs.len();
+    let streak_history = runs
+        .into_iter()
+        .enumerate()
+        .map(|(index, run)| StreakRun {
+            start: run.start.format("%Y-%m-%d").to_string(),
+            end: run.end.format("%Y-%m-%d").to_string(),
+            length: run.length,
+            active: index + 1 == run_count && current_streak > 0,
+        })
+        .collect();
+
+    Ok(HabitStats {
+        habit_id: habit.id,
+        current_streak,
+        best_streak,
+        total_completions: habit.total_completions,
+        periods: PERIODS
+            .iter()
+            .map(|&days| period_stats(&habit, &done, &values, first_day, last_day, days))
+            .collect(),
+        weekdays: weekday_stats(&done, first_day, last_day),
+        streak_history,
+    })
+}
+
+/// Odczytuje dzień w formacie YYYY-MM-DD
+fn parse_day(value: &str, field: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+        error::validation(format!(
+            "Invalid {} '{}' (expected YYYY-MM-DD)",
+            field, value
+        ))
+    })
+}
+
+/// Pobiera kalendarz wypełnienia (heatmapę) dla jednego nawyku lub wszystkich nawyków razem
+///
+/// Zwraca każdy dzień przedziału, także dni bez wpisów. Stopień wykonania dnia to średnia
+/// `Habit::entry_intensity` nawyków zaplanowanych tego dnia według harmonogramu (już utworzonych)
+/// oraz nawyków mających tego dnia wpis, więc te same reguły decydują o heatmapie, streakach i EXP.
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `query` - Przedział dni (domyślnie ostatnie 365 dni) i opcjonalnie ID nawyku
+///
+/// # Returns
+/// * `Result<Vec<HeatmapDay>>` - Dni od najstarszego lub błąd walidacji / not found
+pub fn get_heatmap(conn: &Connection, query: HeatmapQuery) -> Result<Vec<HeatmapDay>> {
+    let to = match &query.to {
+        Some(to) => parse_day(to, "to")?,
+        None => settings_service::today(conn)?,
+    };
+    let from = match &query.from {
+        Some(from) => parse_day(from, "from")?,
+        None => to - Duration::days(DEFAULT_HEATMAP_DAYS - 1),
+    };
+    if from > to {
+        return Err(error::validation("'from' must not be after 'to'"));
+    }
+    if (to - from).num_days() >= MAX_HEATMAP_DAYS {
+        return Err(error::validation(format!(
+            "Date range is too long (max {} days)",
+            MAX_HEATMAP_DAYS
+        )));
+    }
+
+    let habits = match query.habit_id {
+        Some(habit_id) => vec![habit_service::get_habit_by_id(conn, habit_id)?],
+        None => habit_service::get_all_habits(conn)?,
+    };
+    let time = settings_service::get_time_settings(conn)?;
+    let created_days: HashMap<i32, NaiveDate> = habits
+        .iter()
+        .map(|habit| {
+            let created = chrono::DateTime::from_timestamp(habit.created_at, 0)
+                .map_or(from, |created_at| time.day_of(created_at));
+            (habit.id, created)
+        })
+        .collect();
+    let habits_by_id: HashMap<i32, &Habit> = habits.iter().map(|habit| (habit.id, habit)).collect();
+
+    // Wcześniejsze wpisy decydują, czy pierwsze dni przedziału są zaplanowane (EveryNDays, TimesPerWeek)
+    let history_start = from - Duration::days(i64::from(habit_service::MAX_SCHEDULE_INTERVAL));
+    let entries = habit_service::get_habit_entries_in_range(
+        conn,
+        &history_start.format("%Y-%m-%d").to_string(),
+        &to.format("%Y-%m-%d").to_string(),
+        query.habit_id,
+    )?;
+    let mut entries_by_day: BTreeMap<NaiveDate, Vec<&HabitEntry>> = BTreeMap::new();
+    for entry in &entries {
+        if let Ok(date) = NaiveDate::parse_from_str(&entry.date, "%Y-%m-%d") {
+            if date >= from {
+                entries_by_day.entry(date).or_default().push(entry);
+            }
+        }
+    }
+    let done_by_habit: HashMap<i32, BTreeSet<NaiveDate>> = habits
+        .iter()
+        .map(|habit| {
+            let habit_entries: Vec<HabitEntry> = entries
+                .iter()
+                .filter(|entry| entry.habit_id == habit.id)
+                .cloned()
+                .collect();
+            (
+                habit.id,
+                habit_service::entry_dates(habit, &habit_entries).0,
+            )
+        })
+        .collect();
+
+    let days = from
+        .iter_days()
+        .take_while(|date| *date <= to)
+        .map(|date| {
+            let day_entries = entries_by_day.get(&date).map_or(&[][..], Vec::as_slice);
+            let mut intensity = 0.0;
+            let mut completed = 0;
+            let mut frozen = 0;
+
+            for entry in day_entries {
+                let Some(habit) = habits_by_id.get(&entry.habit_id) else {
+                    continue;
+                };
+                intensity += habit.entry_intensity(entry);
+                if habit.is_entry_completed(entry) {
+                    completed += 1;
+                } else if entry.frozen {
+                    frozen += 1;
+                }
+            }
+
+            let total = habits
+                .iter()
+                .filter(|habit| {
+                    let scheduled = created_days[&habit.id] <= date
+                        && habit_service::day_status(
+                            &habit.schedule,
+                            &done_by_habit[&habit.id],
+                            date,
+                        )
+                        .0;
+                    scheduled || day_entries.iter().any(|entry| entry.habit_id == habit.id)
+                })
+                .count() as u32;
+
+            HeatmapDay {
+                date: date.format("%Y-%m-%d").to_string(),
+                intensity: if total == 0 {
+                    0.0
+                } else {
+                    intensity / f64::from(total)
+                },
+                completed,
+                frozen,
+                total,
+            }
+        })
+        .collect();
+
+    Ok(days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use crate::models::{CreateHabitEntryRequest, CreateHabitRequest};
+
+    fn heatmap(
+        conn: &Connection,
+        from: &str,
+        to: &str,
+        habit_id: Option<i32>,
+    ) -> Result<Vec<HeatmapDay>> {
+        get_heatmap(
+            conn,
+            HeatmapQuery {
+                from: Some(from.to_string()),
+                to: Some(to.to_string()),
+                habit_id,
+            },
+        )
+    }
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn dates(values: &[&str]) -> BTreeSet<NaiveDate> {
+        values.iter().map(|value| date(value)).collect()
+    }
+
+    fn lengths(runs: &[Run]) -> Vec<(NaiveDate, NaiveDate, i32)> {
+        runs.iter()
+            .map(|run| (run.start, run.end, run.length))
+            .collect()
+    }
+
+    #[test]
+    fn test_streak_runs_follow_schedule() {
+        let done = dates(&[
+            "2025-03-01",
+            "2025-03-02",
+            "2025-03-03",
+            "2025-03-06",
+            "2025-03-08",
+            "2025-03-09",
+        ]);
+        let frozen = dates(&["2025-03-07"]);
+        let runs = streak_runs(&HabitSchedule::Daily, &done, &frozen, date("2025-03-10"));
+        assert_eq!(
+            lengths(&runs),
+            vec![
+                (date("2025-03-01"), date("2025-03-03"), 3),
+                (date("2025-03-06"), date("2025-03-09"), 3),
+            ]
+        );
+
+        // Pon/śr/pt: opuszczony piątek 03-07 kończy pierwszą serię
+        let gym = HabitSchedule::Weekdays {
+            days: vec![1, 3, 5],
+        };
+        let done = dates(&["2025-03-03", "2025-03-05", "2025-03-10", "2025-03-12"]);
+        let runs = streak_runs(&gym, &done, &BTreeSet::new(), date("2025-03-14"));
+        assert_eq!(
+            lengths(&runs),
+            vec![
+                (date("2025-03-03"), date("2025-03-05"), 2),
+                (date("2025-03-10"), date("2025-03-12"), 2),
+            ]
+        );
+
+        let weekly = HabitSchedule::TimesPerWeek { times: 2 };
+        let done = dates(&[
+            "2025-02-24",
+            "2025-02-27",
+            "2025-03-04",
+            "2025-03-05",
+            "2025-03-10",
+        ]);
+        let runs = streak_runs(&weekly, &done, &BTreeSet::new(), date("2025-03-12"));
+        assert_eq!(
+            lengths(&runs),
+            vec![(date("2025-02-24"), date("2025-03-05"), 2)]
+        );
+    }
+
+    #[test]
+    fn test_expected_completions() {
+        let (start, end) = (date("2025-03-03"), date("2025-03-16"));
+        assert_eq!(expected_completions(&HabitSchedule::Daily, start, end), 14);
+        assert_eq!(
+            expected_completions(&HabitSchedule::Weekdays { days: vec![1, 5] }, start, end),
+            4
+        );
+        assert_eq!(
+            expected_completions(&HabitSchedule::EveryNDays { interval: 3 }, start, end),
+            5
+        );
+        assert_eq!(
+            expected_completions(&HabitSchedule::TimesPerWeek { times: 3 }, start, end),
+            6
+        );
+    }
+
+    #[test]
+    fn test_get_habit_stats() {
+        let db = Database::new(":memory:".into()).unwrap();
+        let conn = db.connection();
+        let habit = habit_service::add_habit(
+            conn,
+            CreateHabitRequest {
+                title: "Woda".to_string(),
+                habit_type: HabitType::Counter,
+                target_value: Some(8),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let today = settings_service::today(conn).unwrap();
+        let log = |days_ago: i64, value: i32| {
+            habit_service::add_habit_entry(
+                conn,
+                CreateHabitEntryRequest {
+                    habit_id: habit.id,
+                    date: (today - Duration::days(days_ago))
+                        .format("%Y-%m-%d")
+                        .to_string(),
+                    completed: None,
+                    value: Some(value),
+                },
+            )
+            .unwrap();
+        };
+
+        // Dwie serie: 5-3 dni temu i wczoraj, a 2 dni temu zabrakło do celu
+        for days_ago in [5, 4, 3, 1] {
+            log(days_ago, 8);
+        }
+        log(2, 4);
+
+        let stats = get_habit_stats(conn, habit.id).unwrap();
+        assert_eq!(stats.current_streak, 1);
+        assert_eq!(stats.best_streak, 3);
+        assert_eq!(stats.total_completions, 4);
+        assert_eq!(stats.streak_history.len(), 2);
+        assert!(!stats.streak_history[0].active);
+        assert!(stats.streak_history[1].active);
+
+        // Tydzień kończy się wczoraj (dzisiaj jeszcze bez wpisu) i zaczyna 5 dni temu
+        let week = &stats.periods[0];
+        assert_eq!((week.days, week.expected, week.completed), (7, 5, 4));
+        assert_eq!(week.average_value, Some(36.0 / 5.0));
+        assert_eq!(stats.periods[2].expected, 5);
+
+        let weekday_days: u32 = stats.weekdays.iter().map(|day| day.days).sum();
+        let weekday_completed: u32 = stats.weekdays.iter().map(|day| day.completed).sum();
+        assert_eq!((weekday_days, weekday_completed), (5, 4));
+
+        let habit = habit_service::get_habit_by_id(conn, habit.id).unwrap();
+        assert_eq!((habit.best_streak, habit.total_completions), (3, 4));
+
+        // Obniżenie wpisu w środku serii skraca też najlepszy streak
+        log(4, 2);
+        let habit = habit_service::get_habit_by_id(conn, habit.id).unwrap();
+        assert_eq!((habit.best_streak, habit.total_completions), (1, 3));
+        assert_eq!(get_habit_stats(conn, habit.id).unwrap().best_streak, 1);
+    }
+
+    #[test]
+    fn test_heatmap_uses_habit_completion_rules() {
+        let db = Database::new(":memory:".into()).unwrap();
+        let conn = db.connection();
+        let reading = habit_service::add_habit(
+            conn,
+            CreateHabitRequest {
+                title: "Czytanie".to_string(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let water = habit_service::add_habit(
+            conn,
+            CreateHabitRequest {
+                title: "Woda".to_string(),
+                habit_type: HabitType::Counter,
+                target_value: Some(4),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        conn.execute("UPDATE habits SET created_at = 0", [])
+            .unwrap();
+        let log = |habit_id: i32, date: &str, completed: bool, value: i32| {
+            habit_service::add_habit_entry(
+                conn,
+                CreateHabitEntryRequest {
+                    habit_id,
+                    date: date.to_string(),
+                    completed: Some(completed),
+                    value: Some(value),
+                },
+            )
+            .unwrap();
+        };
+        log(reading.id, "2025-03-01", true, 0);
+        log(water.id, "2025-03-01", false, 2);
+        log(water.id, "2025-03-03", false, 6);
+
+        let days = heatmap(conn, "2025-03-01", "2025-03-03", None).unwrap();
+        let summary: Vec<(&str, f64, u32, u32)> = days
+            .iter()
+            .map(|day| (day.date.as_str(), day.intensity, day.completed, day.total))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("2025-03-01", 0.75, 1, 2),
+                ("2025-03-02", 0.0, 0, 2),
+                ("2025-03-03", 0.5, 1, 2),
+            ]
+        );
+
+        let water_days = heatmap(conn, "2025-03-01", "2025-03-03", Some(water.id)).unwrap();
+        assert_eq!(water_days[0].intensity, 0.5);
+        assert_eq!(water_days[0].total, 1);
+
+        assert!(heatmap(conn, "2025-03-03", "2025-03-01", None).is_err());
+        assert!(heatmap(conn, "2024-01-01", "2025-03-01", None).is_err());
+        assert!(heatmap(conn, "2025-03-01", "2025-03-03", Some(999)).is_err());
+    }
+
+    #[test]
+    fn test_heatmap_counts_only_scheduled_habits() {
+        let db = Database::new(":memory:".into()).unwrap();
+        let conn = db.connection();
+        let reading = habit_service::add_habit(
+            conn,
+            CreateHabitRequest {
+                title: "Czytanie".to_string(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        // Siłownia w poniedziałki i środy
+        let gym = habit_service::add_habit(
+            conn,
+            CreateHabitRequest {
+                title: "Siłownia".to_string(),
+                schedule: HabitSchedule::Weekdays { days: vec![1, 3] },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        conn.execute("UPDATE habits SET created_at = 0", [])
+            .unwrap();
+        let log = |habit_id: i32, date: &str| {
+            habit_service::add_habit_entry(
+                conn,
+                CreateHabitEntryRequest {
+                    habit_id,
+                    date: date.to_string(),
+                    completed: Some(true),
+                    value: None,
+                },
+            )
+            .unwrap();
+        };
+        log(reading.id, "2025-03-10");
+        log(reading.id, "2025-03-11");
+        log(gym.id, "2025-03-10");
+        // Dodatkowy trening w czwartek też się liczy
+        log(gym.id, "2025-03-13");
+
+        let days = heatmap(conn, "2025-03-10", "2025-03-13", None).unwrap();
+        let summary: Vec<(&str, f64, u32, u32)> = days
+            .iter()
+            .map(|day| (day.date.as_str(), day.intensity, day.completed, day.total))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("2025-03-10", 1.0, 2, 2),
+                ("2025-03-11", 1.0, 1, 1),
+                ("2025-03-12", 0.0, 0, 2),
+                ("2025-03-13", 0.5, 1, 2),
+            ]
+        );
+
+        let gym_days = heatmap(conn, "2025-03-10", "2025-03-13", Some(gym.id)).unwrap();
+        let totals: Vec<u32> = gym_days.iter().map(|day| day.total).collect();
+        assert_eq!(totals, vec![1, 0, 1, 1]);
+    }
+}
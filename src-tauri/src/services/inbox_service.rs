@@ -0,0 +1,237 @@
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::models::{InboxStatus, Task, TriageTaskRequest, UpdateTaskRequest};
+use crate::services::{settings_service, task_service};
+
+/// Liczy nieukończone zadania czekające w skrzynce
+fn count_inbox(conn: &Connection) -> Result<i32> {
+    let count = conn.query_row(
+        "SELECT COUNT(*) FROM tasks WHERE completed = 0 AND inbox = 1 AND deleted_at IS NULL",
+        [],
+        |row| row.get(0),
+    )?;
+
+    Ok(count)
+}
+
+/// Przegląda zadanie ze skrzynki: ustawia projekt, cel, termin, tagi i priorytet jednym wywołaniem
+///
+/// Zadanie opuszcza skrzynkę nawet bez żadnych zmian (np. gdy jest już gotowe do pracy).
+/// Przegląd ostatniego zadania liczy się jako dzień z pustą skrzynką (patrz [`record_inbox_zero`]).
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `task_id` - ID zadania
+/// * `request` - Pola do ustawienia (pominięte pozostają bez zmian)
+///
+/// # Returns
+/// * `Result<Task>` - Zaktualizowane zadanie lub błąd
+pub fn triage_task(conn: &Connection, task_id: i32, request: TriageTaskRequest) -> Result<Task> {
+    let was_in_inbox = task_service::get_task_by_id(conn, task_id)?.inbox;
+
+    let task = task_service::update_task(
+        conn,
+        task_id,
+        UpdateTaskRequest {
+            priority: request.priority,
+            tags: request.tags,
+            due_at: request.due_at.map(Some),
+            scheduled_for: request.scheduled_for.map(Some),
+            project_id: request.project_id.map(Some),
+            goal_id: request.goal_id.map(Some),
+            inbox: Some(false),
+            ..Default::default()
+        },
+    )?;
+
+    if was_in_inbox {
+        record_inbox_zero(conn)?;
+    }
+
+    Ok(task)
+}
+
+/// Zapisuje dzisiejszy dzień jako dzień z pustą skrzynką, jeśli skrzynka jest pusta
+///
+/// Wywoływana, gdy zadanie opuszcza skrzynkę (przegląd, ukończenie, usunięcie).
+/// Dzień liczy się najwyżej raz, więc wielokrotne opróżnianie skrzynki nie nabija odznak.
+///
+/// # Returns
+/// * `Result<bool>` - True, jeśli dzień został właśnie zaliczony
+pub fn record_inbox_zero(conn: &Connection) -> Result<bool> {
+    if count_inbox(conn)? > 0 {
+        return Ok(false);
+    }
+
+    // Dzień liczony według strefy czasowej i godziny rozpoczęcia dnia użytkownika
+    let today = settings_service::today(conn)?;
+    let rows_affected = conn.execute(
+        "INSERT OR IGNORE INTO inbox_zero_days (day, reached_at) VALUES (?1, ?2)",
+        (today.format("%Y-%m-%d").to_string(), Utc::now().timestamp()),
+    )?;
+
+    Ok(rows_affected > 0)
+}
+
+/// Pobiera stan skrzynki: liczbę zadań i licznik dni z pustą skrzynką
+pub fn get_inbox_status(conn: &Connection) -> Result<InboxStatus> {
+    let (inbox_zero_count, last_inbox_zero): (i32, Option<String>) = conn
+        .query_row(
+            "SELECT COUNT(*), MAX(day) FROM inbox_zero_days",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?
+        .unwrap_or((0, None));
+
+    Ok(InboxStatus {
+        count: count_inbox(conn)?,
+        inbox_zero_count,
+        last_inbox_zero,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CreateTaskRequest, TaskPriority};
+
+    fn create_test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+
+        conn.execute_batch(
+            "CREATE TABLE tasks (
+                id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                title TEXT NOT NULL,
+                notes TEXT,
+                completed BOOLEAN NOT NULL DEFAULT 0,
+                priority TEXT NOT NULL DEFAULT 'Medium',
+                attribute TEXT,
+                due_at INTEGER,
+                scheduled_for TEXT,
+                recurrence_id INTEGER,
+                project_id INTEGER,
+                parent_id INTEGER,
+                goal_id INTEGER,
+                inbox BOOLEAN NOT NULL DEFAULT 0,
+                estimate_minutes INTEGER,
+                deleted_at INTEGER,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            );
+            CREATE TABLE tags (
+                id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                created_at INTEGER NOT NULL
+            );
+            CREATE TABLE task_tags (
+                task_id INTEGER NOT NULL,
+                tag_id INTEGER NOT NULL,
+                PRIMARY KEY (task_id, tag_id)
+            );
+            CREATE TABLE task_recurrences (
+                id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                rule TEXT NOT NULL,
+                active BOOLEAN NOT NULL DEFAULT 1,
+                occurrences INTEGER NOT NULL DEFAULT 1,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            );
+            CREATE TABLE projects (
+                id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                color TEXT NOT NULL DEFAULT '#6366f1',
+                attribute TEXT,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            );
+            CREATE TABLE inbox_zero_days (
+                day TEXT PRIMARY KEY NOT NULL,
+                reached_at INTEGER NOT NULL
+            );
+            CREATE TABLE settings (
+                key TEXT PRIMARY KEY NOT NULL,
+                value TEXT NOT NULL
+            );
+            INSERT INTO projects (name, created_at, updated_at) VALUES ('Dom', 0, 0);",
+        )
+        .unwrap();
+
+        conn
+    }
+
+    fn capture(conn: &Connection, title: &str) -> Task {
+        task_service::add_task(
+            conn,
+            CreateTaskRequest {
+                title: title.to_string(),
+                ..Default::default()
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_quick_capture_lands_in_inbox() {
+        let conn = create_test_db();
+
+        let captured = capture(&conn, "Oddzwonić do banku");
+        assert!(captured.inbox);
+
+        let planned = task_service::add_task(
+            &conn,
+            CreateTaskRequest {
+                title: "Raport".to_string(),
+                scheduled_for: Some("2025-01-20".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(!planned.inbox);
+
+        let inbox = task_service::get_inbox_tasks(&conn).unwrap();
+        assert_eq!(inbox.len(), 1);
+        assert_eq!(inbox[0].id, captured.id);
+        assert_eq!(get_inbox_status(&conn).unwrap().count, 1);
+    }
+
+    #[test]
+    fn test_triage_applies_fields_and_counts_inbox_zero() {
+        let conn = create_test_db();
+        let first = capture(&conn, "Naprawić kran");
+        let second = capture(&conn, "Kupić farbę");
+
+        let triaged = triage_task(
+            &conn,
+            first.id,
+            TriageTaskRequest {
+                project_id: Some(1),
+                scheduled_for: Some("2025-01-25".to_string()),
+                tags: Some(vec!["#Dom".to_string()]),
+                priority: Some(TaskPriority::High),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(!triaged.inbox);
+        assert_eq!(triaged.project_id, Some(1));
+        assert_eq!(triaged.scheduled_for.as_deref(), Some("2025-01-25"));
+        assert_eq!(triaged.tags, vec!["dom".to_string()]);
+        assert_eq!(triaged.priority, TaskPriority::High);
+        assert_eq!(get_inbox_status(&conn).unwrap().inbox_zero_count, 0);
+
+        // Usunięcie ostatniego zadania opróżnia skrzynkę
+        task_service::delete_task(&conn, second.id).unwrap();
+        let status = get_inbox_status(&conn).unwrap();
+        assert_eq!(status.count, 0);
+        assert_eq!(status.inbox_zero_count, 1);
+        assert!(status.last_inbox_zero.is_some());
+
+        // Kolejne opróżnienie tego samego dnia nie zwiększa licznika
+        let third = capture(&conn, "Podlać kwiaty");
+        triage_task(&conn, third.id, TriageTaskRequest::default()).unwrap();
+        assert_eq!(get_inbox_status(&conn).unwrap().inbox_zero_count, 1);
+    }
+}
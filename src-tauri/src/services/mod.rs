@@ -1,5 +1,21 @@
 pub mod achievement_service;
+pub mod api_key_service;
 pub mod character_service;
+pub mod error;
+pub mod events;
+pub mod goal_service;
 pub mod habit_service;
+pub mod habit_stats_service;
+pub mod inbox_service;
+pub mod pagination;
+pub mod project_service;
 pub mod quest_service;
+pub mod quick_add;
+pub mod recurrence;
+pub mod search_service;
+pub mod settings_service;
+pub mod tag_service;
 pub mod task_service;
+pub mod trash_service;
+pub mod webhook_dispatcher;
+pub mod webhook_service;
@@ -0,0 +1,236 @@
+use anyhow::Result;
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection, Row};
+
+use crate::models::{Page, SortOrder};
+use crate::services::error;
+
+/// Liczba wyników zwracana, gdy klient nie podał limitu
+pub const DEFAULT_PAGE_SIZE: u32 = 50;
+/// Największy dozwolony rozmiar strony
+pub const MAX_PAGE_SIZE: u32 = 500;
+
+/// Warunki WHERE listy wraz z ich parametrami
+#[derive(Default)]
+pub struct Filters {
+    conditions: Vec<String>,
+    params: Vec<Value>,
+}
+
+impl Filters {
+    /// Dodaje warunek z jednym parametrem oznaczonym w SQL jako `?`
+    pub fn add(&mut self, condition: &str, value: impl Into<Value>) {
+        self.conditions.push(condition.to_string());
+        self.params.push(value.into());
+    }
+
+    /// Dodaje warunek bez parametrów
+    pub fn add_condition(&mut self, condition: &str) {
+        self.conditions.push(condition.to_string());
+    }
+
+    /// Dodaje wyszukiwanie fragmentu tekstu w kolumnie (bez rozróżniania wielkości liter)
+    pub fn add_search(&mut self, column: &str, search: Option<&str>) {
+        if let Some(search) = search.map(str::trim).filter(|search| !search.is_empty()) {
+            self.add(
+                &format!("instr(lower({}), lower(?)) > 0", column),
+                search.to_string(),
+            );
+        }
+    }
+}
+
+/// Parametry stronicowania jednej strony wyników
+pub struct PageRequest<'a> {
+    /// Nazwa pola sortowania zapisywana w kursorze
+    pub sort_name: &'static str,
+    /// Kolumna SQL odpowiadająca polu sortowania
+    pub sort_column: &'static str,
+    pub order: SortOrder,
+    pub limit: Option<u32>,
+    pub cursor: Option<&'a str>,
+}
+
+/// Pozycja w posortowanej liście: wartość pola sortowania i ID ostatniego zwróconego wiersza
+struct Cursor {
+    sort_name: String,
+    value: Value,
+    id: i32,
+}
+
+impl Cursor {
+    /// Koduje kursor jako nieprzezroczysty, bezpieczny w URL ciąg znaków
+    fn encode(&self) -> String {
+        let value = match &self.value {
+            Value::Integer(number) => format!("i{}", number),
+            Value::Text(text) => format!("t{}", text),
+            Value::Real(number) => format!("r{}", number),
+            Value::Null | Value::Blob(_) => "n".to_string(),
+        };
+
+        format!("{}|{}|{}", self.sort_name, self.id, value)
+            .bytes()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    fn decode(encoded: &str) -> Result<Self> {
+        let invalid = || error::validation("Invalid pagination cursor");
+
+        let bytes = encoded
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| {
+                std::str::from_utf8(pair)
+                    .ok()
+                    .filter(|hex| hex.len() == 2)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            })
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(invalid)?;
+        let decoded = String::from_utf8(bytes).map_err(|_| invalid())?;
+
+        let mut parts = decoded.splitn(3, '|');
+        let (Some(sort_name), Some(id), Some(value)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+        let id = id.parse().map_err(|_| invalid())?;
+        let value = match value.split_at_checked(1) {
+            Some(("i", number)) => Value::Integer(number.parse().map_err(|_| invalid())?),
+            Some(("t", text)) => Value::Text(text.to_string()),
+            Some(("r", number)) => Value::Real(number.parse().map_err(|_| invalid())?),
+            Some(("n", "")) => Value::Null,
+            _ => return Err(invalid()),
+        };
+
+        Ok(Cursor {
+            sort_name: sort_name.to_string(),
+            value,
+            id,
+        })
+    }
+}
+
+/// Zwraca rozmiar strony, sprawdzając limit podany przez klienta
+pub fn page_size(limit: Option<u32>) -> Result<u32> {
+    match limit {
+        None => Ok(DEFAULT_PAGE_SIZE),
+        Some(limit) if (1..=MAX_PAGE_SIZE).contains(&limit) => Ok(limit),
+        Some(_) => Err(error::validation(format!(
+            "Limit must be between 1 and {}",
+            MAX_PAGE_SIZE
+        ))),
+    }
+}
+
+/// Pobiera jedną stronę wyników, stronicując po kluczu (pole sortowania + `id`)
+///
+/// W przeciwieństwie do OFFSET kursor nie gubi ani nie dubluje wierszy,
+/// gdy w międzyczasie zostaną dodane nowe rekordy.
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `select` - Zapytanie `SELECT ... FROM tabela` bez WHERE/ORDER BY
+/// * `filters` - Warunki filtrowania
+/// * `request` - Sortowanie, limit i kursor
+/// * `map_row` - Konwersja wiersza na model
+/// * `sort_key` - Wartość pola sortowania i ID modelu (do zbudowania następnego kursora)
+pub fn fetch_page<T>(
+    conn: &Connection,
+    select: &str,
+    mut filters: Filters,
+    request: PageRequest,
+    map_row: impl FnMut(&Row) -> rusqlite::Result<T>,
+    sort_key: impl Fn(&T) -> (Value, i32),
+) -> Result<Page<T>> {
+    let limit = page_size(request.limit)?;
+    let (comparison, direction) = match request.order {
+        SortOrder::Asc => (">", "ASC"),
+        SortOrder::Desc => ("<", "DESC"),
+    };
+
+    if let Some(cursor) = request.cursor {
+        let cursor = Cursor::decode(cursor)?;
+        if cursor.sort_name != request.sort_name {
+            return Err(error::validation(
+                "Pagination cursor was created for a different sort field",
+            ));
+        }
+
+        let column = request.sort_column;
+        filters.conditions.push(format!(
+            "({column} {comparison} ? OR ({column} = ? AND id {comparison} ?))"
+        ));
+        filters.params.push(cursor.value.clone());
+        filters.params.push(cursor.value);
+        filters.params.push(Value::Integer(cursor.id.into()));
+    }
+
+    let mut sql = select.to_string();
+    if !filters.conditions.is_empty() {
+        sql.push_str(" WHERE ");
+        sql.push_str(&filters.conditions.join(" AND "));
+    }
+    // Pobierz jeden wiersz więcej, żeby wiedzieć czy istnieje następna strona
+    sql.push_str(&format!(
+        " ORDER BY {} {direction}, id {direction} LIMIT {}",
+        request.sort_column,
+        limit + 1
+    ));
+
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(params_from_iter(filters.params), map_row)?;
+
+    let mut items = Vec::new();
+    for item in rows {
+        items.push(item?);
+    }
+
+    let next_cursor = if items.len() > limit as usize {
+        items.truncate(limit as usize);
+        items.last().map(|last| {
+            let (value, id) = sort_key(last);
+            Cursor {
+                sort_name: request.sort_name.to_string(),
+                value,
+                id,
+            }
+            .encode()
+        })
+    } else {
+        None
+    };
+
+    Ok(Page { items, next_cursor })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let cursor = Cursor {
+            sort_name: "title".to_string(),
+            value: Value::Text("Zadanie | z kreską".to_string()),
+            id: 7,
+        };
+
+        let decoded = Cursor::decode(&cursor.encode()).unwrap();
+        assert_eq!(decoded.sort_name, "title");
+        assert_eq!(decoded.value, cursor.value);
+        assert_eq!(decoded.id, 7);
+
+        assert!(Cursor::decode("zz").is_err());
+        assert!(Cursor::decode("abc").is_err());
+    }
+
+    #[test]
+    fn test_page_size() {
+        assert_eq!(page_size(None).unwrap(), DEFAULT_PAGE_SIZE);
+        assert_eq!(page_size(Some(10)).unwrap(), 10);
+        assert!(page_size(Some(0)).is_err());
+        assert!(page_size(Some(MAX_PAGE_SIZE + 1)).is_err());
+    }
+}
@@ -0,0 +1,415 @@
+use anyhow::Result;
+use rusqlite::{Connection, OptionalExtension, Row};
+use std::collections::HashMap;
+
+use crate::models::{Attribute, CreateProjectRequest, Project, UpdateProjectRequest};
+use crate::services::{error, task_service};
+
+/// Kolor nowego projektu, gdy użytkownik nie wybrał własnego
+pub const DEFAULT_PROJECT_COLOR: &str = "#6366f1";
+/// Maksymalna długość nazwy projektu
+const MAX_PROJECT_NAME_LENGTH: usize = 50;
+
+/// Konwertuje wiersz bazy danych na projekt (bez statystyk zadań)
+fn row_to_project(row: &Row) -> Result<Project, rusqlite::Error> {
+    let attribute: Option<String> = row.get("attribute")?;
+
+    Ok(Project {
+        id: row.get("id")?,
+        name: row.get("name")?,
+        color: row.get("color")?,
+        attribute: attribute.as_deref().and_then(Attribute::from_name),
+        task_count: 0,
+        completed_count: 0,
+        progress: 0.0,
+        created_at: row.get("created_at")?,
+        updated_at: row.get("updated_at")?,
+    })
+}
+
+/// Sprawdza i przycina nazwę projektu
+fn validate_name(name: &str) -> Result<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(error::validation("Project name cannot be empty"));
+    }
+    if name.chars().count() > MAX_PROJECT_NAME_LENGTH {
+        return Err(error::validation(format!(
+            "Project name is too long (max {} characters)",
+            MAX_PROJECT_NAME_LENGTH
+        )));
+    }
+
+    Ok(name.to_string())
+}
+
+/// Sprawdza kolor w formacie `#RRGGBB` i zamienia go na małe litery
+fn validate_color(color: &str) -> Result<String> {
+    let color = color.trim();
+    let valid = color.len() == 7
+        && color.starts_with('#')
+        && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+    if !valid {
+        return Err(error::validation(format!(
+            "Invalid project color '{}' (expected #RRGGBB)",
+            color
+        )));
+    }
+
+    Ok(color.to_lowercase())
+}
+
+/// Zwraca błąd konfliktu, gdy nazwa jest zajęta przez inny projekt
+fn ensure_unique_name(conn: &Connection, name: &str, project_id: Option<i32>) -> Result<()> {
+    let existing: Option<i32> = conn
+        .query_row("SELECT id FROM projects WHERE name = ?1", [name], |row| {
+            row.get(0)
+        })
+        .optional()?;
+
+    match existing {
+        Some(id) if Some(id) != project_id => Err(error::conflict(format!(
+            "Project '{}' already exists",
+            name
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Uzupełnia liczniki i postęp projektów na podstawie ich drzew zadań
+fn attach_stats(conn: &Connection, projects: &mut [Project]) -> Result<()> {
+    let mut trees = HashMap::new();
+    for project in projects.iter() {
+        trees.insert(
+            project.id,
+            task_service::get_project_tree(conn, project.id)?,
+        );
+    }
+
+    for project in projects.iter_mut() {
+        let Some(roots) = trees.remove(&project.id) else {
+            continue;
+        };
+        let (task_count, completed_count) = task_service::count_tree(&roots);
+        project.task_count = task_count;
+        project.completed_count = completed_count;
+        project.progress = task_service::average_progress(&roots);
+    }
+
+    Ok(())
+}
+
+/// Tworzy nowy projekt
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `request` - Nazwa, opcjonalny kolor i atrybut projektu
+///
+/// # Returns
+/// * `Result<Project>` - Utworzony projekt lub błąd (konflikt, gdy nazwa jest zajęta)
+pub fn create_project(conn: &Connection, request: CreateProjectRequest) -> Result<Project> {
+    let name = validate_name(&request.name)?;
+    let color = validate_color(request.color.as_deref().unwrap_or(DEFAULT_PROJECT_COLOR))?;
+    ensure_unique_name(conn, &name, None)?;
+
+    let now = chrono::Utc::now().timestamp();
+    conn.execute(
+        "INSERT INTO projects (name, color, attribute, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?4)",
+        (
+            &name,
+            &color,
+            request.attribute.map(|attribute| attribute.as_str()),
+            now,
+        ),
+    )?;
+
+    get_project(conn, conn.last_insert_rowid() as i32)
+}
+
+/// Pobiera wszystkie projekty z postępem
+///
+/// # Returns
+/// * `Result<Vec<Project>>` - Projekty posortowane alfabetycznie
+pub fn list_projects(conn: &Connection) -> Result<Vec<Project>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name, color, attribute, created_at, updated_at
+         FROM projects ORDER BY name",
+    )?;
+    let project_iter = stmt.query_map([], row_to_project)?;
+
+    let mut projects = Vec::new();
+    for project in project_iter {
+        projects.push(project?);
+    }
+    attach_stats(conn, &mut projects)?;
+
+    Ok(projects)
+}
+
+/// Pobiera projekt po ID
+///
+/// # Returns
+/// * `Result<Project>` - Projekt z postępem lub błąd not found
+pub fn get_project(conn: &Connection, project_id: i32) -> Result<Project> {
+    let project = conn
+        .query_row(
+            "SELECT id, name, color, attribute, created_at, updated_at
+             FROM projects WHERE id = ?1",
+            [project_id],
+            row_to_project,
+        )
+        .optional()?;
+
+    let Some(project) = project else {
+        return Err(error::not_found(format!(
+            "Project with id {} not found",
+            project_id
+        )));
+    };
+
+    let mut projects = vec![project];
+    attach_stats(conn, &mut projects)?;
+
+    Ok(projects.remove(0))
+}
+
+/// Aktualizuje nazwę, kolor lub atrybut projektu
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `project_id` - ID projektu
+/// * `request` - Zmieniane pola
+///
+/// # Returns
+/// * `Result<Project>` - Zaktualizowany projekt lub błąd
+pub fn update_project(
+    conn: &Connection,
+    project_id: i32,
+    request: UpdateProjectRequest,
+) -> Result<Project> {
+    let mut project = get_project(conn, project_id)?;
+
+    if let Some(name) = request.name {
+        let name = validate_name(&name)?;
+        ensure_unique_name(conn, &name, Some(project_id))?;
+        project.name = name;
+    }
+    if let Some(color) = request.color {
+        project.color = validate_color(&color)?;
+    }
+    if let Some(attribute) = request.attribute {
+        project.attribute = attribute;
+    }
+    project.updated_at = chrono::Utc::now().timestamp();
+
+    conn.execute(
+        "UPDATE projects SET name = ?1, color = ?2, attribute = ?3, updated_at = ?4 WHERE id = ?5",
+        (
+            &project.name,
+            &project.color,
+            project.attribute.map(|attribute| attribute.as_str()),
+            project.updated_at,
+            project_id,
+        ),
+    )?;
+
+    Ok(project)
+}
+
+/// Usuwa projekt; jego zadania zostają, ale bez projektu
+pub fn delete_project(conn: &Connection, project_id: i32) -> Result<()> {
+    conn.execute(
+        "UPDATE tasks SET project_id = NULL WHERE project_id = ?1",
+        [project_id],
+    )?;
+    let rows_affected = conn.execute("DELETE FROM projects WHERE id = ?1", [project_id])?;
+
+    if rows_affected == 0 {
+        return Err(error::not_found(format!(
+            "Project with id {} not found",
+            project_id
+        )));
+    }
+
+    Ok(())
+}
+
+/// Pobiera atrybut projektu (używany, gdy ukończone zadanie nie ma własnego)
+pub fn get_project_attribute(conn: &Connection, project_id: i32) -> Result<Option<Attribute>> {
+    let attribute: Option<Option<String>> = conn
+        .query_row(
+            "SELECT attribute FROM projects WHERE id = ?1",
+            [project_id],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    Ok(attribute
+        .flatten()
+        .as_deref()
+        .and_then(Attribute::from_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CreateTaskRequest, TaskPriority};
+
+    fn create_test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+
+        conn.execute_batch(
+            "CREATE TABLE tasks (
+                id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                title TEXT NOT NULL,
+                notes TEXT,
+                completed BOOLEAN NOT NULL DEFAULT 0,
+                priority TEXT NOT NULL DEFAULT 'Medium',
+                attribute TEXT,
+                due_at INTEGER,
+                scheduled_for TEXT,
+                recurrence_id INTEGER,
+                project_id INTEGER,
+                parent_id INTEGER,
+                goal_id INTEGER,
+                inbox BOOLEAN NOT NULL DEFAULT 0,
+                estimate_minutes INTEGER,
+                deleted_at INTEGER,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            );
+            CREATE TABLE tags (
+                id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                created_at INTEGER NOT NULL
+            );
+            CREATE TABLE task_tags (
+                task_id INTEGER NOT NULL,
+                tag_id INTEGER NOT NULL,
+                PRIMARY KEY (task_id, tag_id)
+            );
+            CREATE TABLE task_recurrences (
+                id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                rule TEXT NOT NULL,
+                active BOOLEAN NOT NULL DEFAULT 1,
+                occurrences INTEGER NOT NULL DEFAULT 1,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            );
+            CREATE TABLE projects (
+                id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                color TEXT NOT NULL DEFAULT '#6366f1',
+                attribute TEXT,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            );",
+        )
+        .unwrap();
+
+        conn
+    }
+
+    fn add_task(conn: &Connection, title: &str, project_id: i32) -> i32 {
+        task_service::add_task(
+            conn,
+            CreateTaskRequest {
+                title: title.to_string(),
+                priority: TaskPriority::Low,
+                project_id: Some(project_id),
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .id
+    }
+
+    #[test]
+    fn test_create_and_update_project() {
+        let conn = create_test_db();
+
+        let project = create_project(
+            &conn,
+            CreateProjectRequest {
+                name: "  Nauka  ".to_string(),
+                attribute: Some(Attribute::Intelligence),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(project.name, "Nauka");
+        assert_eq!(project.color, DEFAULT_PROJECT_COLOR);
+
+        let duplicate = create_project(
+            &conn,
+            CreateProjectRequest {
+                name: "NAUKA".to_string(),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(
+            duplicate.downcast_ref::<error::ServiceError>(),
+            Some(error::ServiceError::Conflict(_))
+        ));
+
+        let updated = update_project(
+            &conn,
+            project.id,
+            UpdateProjectRequest {
+                color: Some("#FF8800".to_string()),
+                attribute: Some(None),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(updated.color, "#ff8800");
+        assert_eq!(updated.attribute, None);
+
+        let invalid = update_project(
+            &conn,
+            project.id,
+            UpdateProjectRequest {
+                color: Some("red".to_string()),
+                ..Default::default()
+            },
+        );
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn test_project_progress_and_delete() {
+        let conn = create_test_db();
+        let project = create_project(
+            &conn,
+            CreateProjectRequest {
+                name: "Dom".to_string(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let done = add_task(&conn, "Pranie", project.id);
+        add_task(&conn, "Zakupy", project.id);
+        conn.execute("UPDATE tasks SET completed = 1 WHERE id = ?1", [done])
+            .unwrap();
+
+        let projects = list_projects(&conn).unwrap();
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].task_count, 2);
+        assert_eq!(projects[0].completed_count, 1);
+        assert_eq!(projects[0].progress, 50.0);
+
+        delete_project(&conn, project.id).unwrap();
+        assert!(get_project(&conn, project.id).is_err());
+        // Zadania zostają, ale bez projektu
+        assert_eq!(
+            task_service::get_task_by_id(&conn, done)
+                .unwrap()
+                .project_id,
+            None
+        );
+        assert!(delete_project(&conn, project.id).is_err());
+    }
+}
@@ -1,16 +1,19 @@
 use crate::models::{
-    CreateQuestRequest, Habit, HabitEntry, Quest, QuestStatus, QuestType, Task, UpdateQuestRequest,
+    CreateQuestRequest, DomainEvent, GoalLevel, GoalStatus, Habit, HabitEntry, HabitSchedule,
+    Quest, QuestStatus, QuestType, Task, UpdateQuestRequest,
 };
-use crate::services::character_service;
+use crate::services::{character_service, error, events, goal_service, settings_service};
 use anyhow::Result;
 use chrono::{Datelike, IsoWeek, Utc};
 use rusqlite::Connection;
 
-/// Pobiera aktualny tydzień w formacie YYYY-WW
-fn get_current_week() -> String {
-    let now = Utc::now();
-    let iso_week = now.iso_week();
-    format!("{}-{:02}", iso_week.year(), iso_week.week())
+/// O ile punktów procentowych quest celu każe posunąć cel kwartalny
+const GOAL_QUEST_PROGRESS_STEP: i32 = 25;
+
+/// Pobiera aktualny tydzień w formacie YYYY-WW (według strefy czasowej i godziny rozpoczęcia dnia z ustawień)
+fn get_current_week(conn: &Connection) -> Result<String> {
+    let iso_week = settings_service::today(conn)?.iso_week();
+    Ok(format!("{}-{:02}", iso_week.year(), iso_week.week()))
 }
 
 /// Generuje questy tygodniowe na podstawie danych użytkownika
@@ -21,7 +24,7 @@ fn get_current_week() -> String {
 /// # Returns
 /// * `Result<Vec<Quest>>` - Lista nowo wygenerowanych questów lub błąd
 pub fn generate_weekly_quests(conn: &Connection) -> Result<Vec<Quest>> {
-    let current_week = get_current_week();
+    let current_week = get_current_week(conn)?;
 
     // Sprawdź czy questy na ten tydzień już istnieją
     let existing_count: i32 = conn.query_row(
@@ -39,7 +42,7 @@ pub fn generate_weekly_quests(conn: &Connection) -> Result<Vec<Quest>> {
 
     // Quest 1: Ukończ zadania (na podstawie nieukończonych zadań)
     let incomplete_tasks_count: i32 = conn.query_row(
-        "SELECT COUNT(*) FROM tasks WHERE completed = 0",
+        "SELECT COUNT(*) FROM tasks WHERE completed = 0 AND deleted_at IS NULL",
         [],
         |row| row.get(0),
     )?;
@@ -60,24 +63,74 @@ pub fn generate_weekly_quests(conn: &Connection) -> Result<Vec<Quest>> {
         new_quests.push(quest);
     }
 
-    // Quest 2: Utrzymaj najdłuższy streak nawyku
-    let longest_habit_result: Result<(i32, String, i32), rusqlite::Error> = conn.query_row(
-        "SELECT id, title, current_streak FROM habits ORDER BY current_streak DESC LIMIT 1",
+    // Quest 2: Nadrób zaległości (zadania po terminie)
+    let overdue_tasks_count: i32 = conn.query_row(
+        "SELECT COUNT(*) FROM tasks WHERE completed = 0 AND due_at < ?1 AND deleted_at IS NULL",
+        [now],
+        |row| row.get(0),
+    )?;
+
+    if overdue_tasks_count > 0 {
+        let target = std::cmp::min(3, overdue_tasks_count); // Max 3 zaległe zadania
+        let quest = Quest::new(
+            "Pogromca Zaległości".to_string(),
+            format!("Ukończ {} zaległe zadania (po terminie)", target),
+            QuestType::Overdue,
+            target,
+            None,
+            None,
+            60, // 60 EXP nagrody
+            Some(now + 7 * 24 * 60 * 60),
+            current_week.clone(),
+        );
+        new_quests.push(quest);
+    }
+
+    // Quest 3: Utrzymaj najdłuższy streak nawyku
+    let longest_habit_result: Result<(i32, String, String, i32), rusqlite::Error> = conn.query_row(
+        "SELECT id, title, schedule, current_streak FROM habits WHERE deleted_at IS NULL
+         ORDER BY current_streak DESC LIMIT 1",
         [],
-        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
     );
 
-    if let Ok((habit_id, habit_title, current_streak)) = longest_habit_result {
-        if current_streak >= 3 {
-            // Tylko jeśli streak jest przynajmniej 3 dni
-            let quest = Quest::new(
-                "Mistrz Konsekwencji".to_string(),
+    if let Ok((habit_id, habit_title, schedule, current_streak)) = longest_habit_result {
+        let schedule: HabitSchedule = serde_json::from_str(&schedule).unwrap_or_default();
+        // Nawyki tygodniowe liczą streak w tygodniach, więc tydzień to jeden kolejny termin
+        let (target, description) = match schedule {
+            HabitSchedule::Daily => (
+                7,
                 format!(
                     "Utrzymaj nawyk '{}' przez cały tydzień (7 dni z rzędu)",
                     habit_title
                 ),
+            ),
+            HabitSchedule::TimesPerWeek { times } => (
+                current_streak + 1,
+                format!(
+                    "Utrzymaj nawyk '{}' przez cały tydzień ({} razy w tygodniu)",
+                    habit_title, times
+                ),
+            ),
+            schedule => {
+                let occurrences = schedule.occurrences_per_week();
+                (
+                    current_streak + occurrences,
+                    format!(
+                        "Utrzymaj nawyk '{}' przez cały tydzień ({} kolejnych terminów)",
+                        habit_title, occurrences
+                    ),
+                )
+            }
+        };
+
+        if current_streak >= 3 {
+            // Tylko jeśli streak jest przynajmniej 3 dni
+            let quest = Quest::new(
+                "Mistrz Konsekwencji".to_string(),
+                description,
                 QuestType::Habit,
-                7,
+                target,
                 None,
                 Some(habit_id),
                 75, // 75 EXP nagrody
@@ -88,7 +141,7 @@ pub fn generate_weekly_quests(conn: &Connection) -> Result<Vec<Quest>> {
         }
     }
 
-    // Quest 3: Zdobądź EXP (quest związany z postacią)
+    // Quest 4: Zdobądź EXP (quest związany z postacią)
     let character_result: Result<i32, rusqlite::Error> =
         conn.query_row("SELECT level FROM characters WHERE id = 1", [], |row| {
             row.get(0)
@@ -113,35 +166,26 @@ pub fn generate_weekly_quests(conn: &Connection) -> Result<Vec<Quest>> {
         new_quests.push(quest);
     }
 
-    // Quest 4: Dodatkowy quest dla konkretnej kategorii (jeśli istnieją zadania z kategoriami)
+    // Quest 5: Quest dla najczęstszego tagu wśród nieukończonych zadań
     let category_result: Result<String, rusqlite::Error> = conn.query_row(
-        "SELECT title FROM tasks WHERE completed = 0 AND (
-            LOWER(title) LIKE '%nauka%' OR 
-            LOWER(title) LIKE '%sport%' OR 
-            LOWER(title) LIKE '%praca%' OR 
-            LOWER(title) LIKE '%projekt%'
-        ) LIMIT 1",
+        "SELECT tags.name FROM tags
+         JOIN task_tags ON task_tags.tag_id = tags.id
+         JOIN tasks ON tasks.id = task_tags.task_id
+         WHERE tasks.completed = 0 AND tasks.deleted_at IS NULL
+         GROUP BY tags.id
+         ORDER BY COUNT(*) DESC, tags.name
+         LIMIT 1",
         [],
         |row| row.get(0),
     );
 
-    if let Ok(task_title) = category_result {
-        let category = if task_title.to_lowercase().contains("nauka") {
-            "nauka"
-        } else if task_title.to_lowercase().contains("sport") {
-            "sport"
-        } else if task_title.to_lowercase().contains("praca") {
-            "praca"
-        } else {
-            "projekt"
-        };
-
+    if let Ok(category) = category_result {
         let quest = Quest::new(
             format!("Specjalista: {}", category.to_uppercase()),
-            format!("Ukończ 3 zadania związane z kategorią '{}'", category),
+            format!("Ukończ 3 zadania z tagiem '{}'", category),
             QuestType::Task,
             3,
-            Some(category.to_string()),
+            Some(category),
             None,
             60, // 60 EXP nagrody
             Some(now + 7 * 24 * 60 * 60),
@@ -150,12 +194,37 @@ pub fn generate_weekly_quests(conn: &Connection) -> Result<Vec<Quest>> {
         new_quests.push(quest);
     }
 
+    // Quest 6: Posuń naprzód aktywny cel kwartalny z najbliższym terminem
+    let quarterly_goal = goal_service::list_goals(conn, Some(GoalLevel::Quarterly))?
+        .into_iter()
+        .find(|goal| goal.status == GoalStatus::Active && goal.progress < 100.0);
+
+    if let Some(goal) = quarterly_goal {
+        let target = std::cmp::min(100, goal.progress as i32 + GOAL_QUEST_PROGRESS_STEP);
+        let mut quest = Quest::new(
+            format!("Strateg: {}", goal.title),
+            format!(
+                "Doprowadź cel kwartalny '{}' do {}% postępu",
+                goal.title, target
+            ),
+            QuestType::Goal,
+            target,
+            None,
+            None,
+            80, // 80 EXP nagrody
+            Some(now + 7 * 24 * 60 * 60),
+            current_week.clone(),
+        );
+        quest.goal_id = Some(goal.id);
+        new_quests.push(quest);
+    }
+
     // Zapisz questy do bazy danych
     for quest in &new_quests {
         let sql = "INSERT INTO quests (
             title, description, quest_type, target_value, current_progress,
-            category, habit_id, status, reward_exp, deadline, week, created_at, updated_at
-        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)";
+            category, habit_id, status, reward_exp, deadline, week, created_at, updated_at, goal_id
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)";
 
         conn.execute(
             sql,
@@ -166,6 +235,8 @@ pub fn generate_weekly_quests(conn: &Connection) -> Result<Vec<Quest>> {
                     QuestType::Task => "Task",
                     QuestType::Habit => "Habit",
                     QuestType::Character => "Character",
+                    QuestType::Goal => "Goal",
+                    QuestType::Overdue => "Overdue",
                 },
                 quest.target_value,
                 quest.current_progress,
@@ -181,6 +252,7 @@ pub fn generate_weekly_quests(conn: &Connection) -> Result<Vec<Quest>> {
                 &quest.week,
                 quest.created_at,
                 quest.updated_at,
+                quest.goal_id,
             ),
         )?;
     }
@@ -197,10 +269,14 @@ pub fn generate_weekly_quests(conn: &Connection) -> Result<Vec<Quest>> {
 /// # Returns
 /// * `Result<Vec<Quest>>` - Lista questów lub błąd
 pub fn get_quests_for_week(conn: &Connection, week: Option<String>) -> Result<Vec<Quest>> {
-    let target_week = week.unwrap_or_else(get_current_week);
+    let target_week = match week {
+        Some(week) => week,
+        None => get_current_week(conn)?,
+    };
 
     let sql = "SELECT id, title, description, quest_type, target_value, current_progress,
-                      category, habit_id, status, reward_exp, deadline, week, created_at, updated_at
+                      category, habit_id, status, reward_exp, deadline, week, created_at, updated_at,
+                      goal_id
                FROM quests WHERE week = ?1 ORDER BY created_at ASC";
 
     let mut stmt = conn.prepare(sql)?;
@@ -209,6 +285,8 @@ pub fn get_quests_for_week(conn: &Connection, week: Option<String>) -> Result<Ve
             "Task" => QuestType::Task,
             "Habit" => QuestType::Habit,
             "Character" => QuestType::Character,
+            "Goal" => QuestType::Goal,
+            "Overdue" => QuestType::Overdue,
             _ => QuestType::Task,
         };
 
@@ -228,6 +306,7 @@ pub fn get_quests_for_week(conn: &Connection, week: Option<String>) -> Result<Ve
             current_progress: row.get(5)?,
             category: row.get(6)?,
             habit_id: row.get(7)?,
+            goal_id: row.get(14)?,
             status,
             reward_exp: row.get(9)?,
             deadline: row.get(10)?,
@@ -253,7 +332,8 @@ pub fn get_quests_for_week(conn: &Connection, week: Option<String>) -> Result<Ve
 /// # Returns
 /// * `Result<Vec<Quest>>` - Lista zaktualizowanych questów lub błąd
 pub fn update_all_quest_progress(conn: &Connection) -> Result<Vec<Quest>> {
-    let current_week = get_current_week();
+    let current_week = get_current_week(conn)?;
+    let week_start = settings_service::week_start_timestamp(conn)?;
     let mut updated_quests = Vec::new();
 
     // Pobierz wszystkie aktywne questy dla obecnego tygodnia
@@ -264,21 +344,36 @@ pub fn update_all_quest_progress(conn: &Connection) -> Result<Vec<Quest>> {
 
     for quest in active_quests {
         let new_progress = match quest.quest_type {
+            QuestType::Overdue => {
+                // Zlicz zadania, które w chwili wygenerowania questu były po terminie
+                // i zostały od tego czasu ukończone
+                conn.query_row(
+                    "SELECT COUNT(*) FROM tasks
+                     WHERE completed = 1 AND updated_at >= ?1 AND due_at < ?1
+                       AND deleted_at IS NULL",
+                    [quest.created_at],
+                    |row| row.get::<_, i32>(0),
+                )
+                .unwrap_or(0)
+            }
             QuestType::Task => {
                 if let Some(category) = &quest.category {
-                    // Zlicz ukończone zadania z określonej kategorii w tym tygodniu
-                    let week_start = Utc::now().timestamp() - (7 * 24 * 60 * 60);
-                    let category_pattern = format!("%{}%", category.to_lowercase());
+                    // Zlicz ukończone zadania z tagiem kategorii w tym tygodniu
                     conn.query_row(
-                        "SELECT COUNT(*) FROM tasks WHERE completed = 1 AND updated_at >= ?1 AND LOWER(title) LIKE ?2",
-                        (week_start, category_pattern),
-                        |row| row.get::<_, i32>(0)
-                    ).unwrap_or(0)
+                        "SELECT COUNT(*) FROM tasks
+                         JOIN task_tags ON task_tags.task_id = tasks.id
+                         JOIN tags ON tags.id = task_tags.tag_id
+                         WHERE tasks.completed = 1 AND tasks.updated_at >= ?1 AND tags.name = ?2
+                           AND tasks.deleted_at IS NULL",
+                        (week_start, category),
+                        |row| row.get::<_, i32>(0),
+                    )
+                    .unwrap_or(0)
                 } else {
                     // Zlicz wszystkie ukończone zadania w tym tygodniu
-                    let week_start = Utc::now().timestamp() - (7 * 24 * 60 * 60);
                     conn.query_row(
-                        "SELECT COUNT(*) FROM tasks WHERE completed = 1 AND updated_at >= ?1",
+                        "SELECT COUNT(*) FROM tasks
+                         WHERE completed = 1 AND updated_at >= ?1 AND deleted_at IS NULL",
                         [week_start],
                         |row| row.get::<_, i32>(0),
                     )
@@ -289,7 +384,7 @@ pub fn update_all_quest_progress(conn: &Connection) -> Result<Vec<Quest>> {
                 if let Some(habit_id) = quest.habit_id {
                     // Sprawdź streak dla konkretnego nawyku
                     conn.query_row(
-                        "SELECT current_streak FROM habits WHERE id = ?1",
+                        "SELECT current_streak FROM habits WHERE id = ?1 AND deleted_at IS NULL",
                         [habit_id],
                         |row| row.get::<_, i32>(0),
                     )
@@ -298,6 +393,15 @@ pub fn update_all_quest_progress(conn: &Connection) -> Result<Vec<Quest>> {
                     0
                 }
             }
+            QuestType::Goal => {
+                // Postęp questu to procentowy postęp celu kwartalnego
+                match quest.goal_id {
+                    Some(goal_id) => goal_service::get_goal(conn, goal_id)
+                        .map(|goal| goal.progress as i32)
+                        .unwrap_or(0),
+                    None => 0,
+                }
+            }
             QuestType::Character => {
                 // Sprawdź aktualny EXP postaci dla questów EXP
                 match crate::services::character_service::get_character(conn) {
@@ -332,15 +436,21 @@ pub fn update_all_quest_progress(conn: &Connection) -> Result<Vec<Quest>> {
             if updated_quest.is_completed()
                 && matches!(updated_quest.status, QuestStatus::Completed)
             {
+                events::publish(DomainEvent::QuestCompleted {
+                    quest_id: updated_quest.id,
+                    title: updated_quest.title.clone(),
+                    reward_exp: updated_quest.reward_exp,
+                });
+
                 match character_service::add_experience(conn, updated_quest.reward_exp) {
                     Ok((_, level_up)) => {
                         if level_up {
-                            println!(
+                            eprintln!(
                                 "Level up! Quest '{}' caused character to level up!",
                                 updated_quest.title
                             );
                         }
-                        println!(
+                        eprintln!(
                             "Quest completed: '{}' - {} EXP earned!",
                             updated_quest.title, updated_quest.reward_exp
                         );
@@ -385,7 +495,8 @@ pub fn expire_overdue_quests(conn: &Connection) -> Result<i32> {
 /// * `Result<Vec<Quest>>` - Lista aktywnych questów lub błąd
 pub fn get_active_quests(conn: &Connection) -> Result<Vec<Quest>> {
     let sql = "SELECT id, title, description, quest_type, target_value, current_progress,
-                      category, habit_id, status, reward_exp, deadline, week, created_at, updated_at
+                      category, habit_id, status, reward_exp, deadline, week, created_at, updated_at,
+                      goal_id
                FROM quests WHERE status = 'Active' ORDER BY deadline ASC";
 
     let mut stmt = conn.prepare(sql)?;
@@ -394,6 +505,8 @@ pub fn get_active_quests(conn: &Connection) -> Result<Vec<Quest>> {
             "Task" => QuestType::Task,
             "Habit" => QuestType::Habit,
             "Character" => QuestType::Character,
+            "Goal" => QuestType::Goal,
+            "Overdue" => QuestType::Overdue,
             _ => QuestType::Task,
         };
 
@@ -413,6 +526,7 @@ pub fn get_active_quests(conn: &Connection) -> Result<Vec<Quest>> {
             current_progress: row.get(5)?,
             category: row.get(6)?,
             habit_id: row.get(7)?,
+            goal_id: row.get(14)?,
             status,
             reward_exp: row.get(9)?,
             deadline: row.get(10)?,
@@ -441,7 +555,8 @@ pub fn get_active_quests(conn: &Connection) -> Result<Vec<Quest>> {
 pub fn complete_quest(conn: &Connection, quest_id: i32) -> Result<Quest> {
     // Pobierz quest
     let sql = "SELECT id, title, description, quest_type, target_value, current_progress,
-                      category, habit_id, status, reward_exp, deadline, week, created_at, updated_at
+                      category, habit_id, status, reward_exp, deadline, week, created_at, updated_at,
+                      goal_id
                FROM quests WHERE id = ?1";
 
     let mut stmt = conn.prepare(sql)?;
@@ -450,6 +565,8 @@ pub fn complete_quest(conn: &Connection, quest_id: i32) -> Result<Quest> {
             "Task" => QuestType::Task,
             "Habit" => QuestType::Habit,
             "Character" => QuestType::Character,
+            "Goal" => QuestType::Goal,
+            "Overdue" => QuestType::Overdue,
             _ => QuestType::Task,
         };
 
@@ -469,6 +586,7 @@ pub fn complete_quest(conn: &Connection, quest_id: i32) -> Result<Quest> {
             current_progress: row.get(5)?,
             category: row.get(6)?,
             habit_id: row.get(7)?,
+            goal_id: row.get(14)?,
             status,
             reward_exp: row.get(9)?,
             deadline: row.get(10)?,
@@ -479,7 +597,7 @@ pub fn complete_quest(conn: &Connection, quest_id: i32) -> Result<Quest> {
     })?;
 
     if !matches!(quest.status, QuestStatus::Active) {
-        return Err(anyhow::anyhow!("Quest is not active"));
+        return Err(error::conflict("Quest is not active"));
     }
 
     // Oznacz jako ukończony
@@ -500,16 +618,22 @@ pub fn complete_quest(conn: &Connection, quest_id: i32) -> Result<Quest> {
         ),
     )?;
 
+    events::publish(DomainEvent::QuestCompleted {
+        quest_id: quest.id,
+        title: quest.title.clone(),
+        reward_exp: quest.reward_exp,
+    });
+
     // Daj nagrodę EXP
     match character_service::add_experience(conn, quest.reward_exp) {
         Ok((_, level_up)) => {
             if level_up {
-                println!(
+                eprintln!(
                     "Level up! Quest '{}' caused character to level up!",
                     quest.title
                 );
             }
-            println!(
+            eprintln!(
                 "Quest completed: '{}' - {} EXP earned!",
                 quest.title, quest.reward_exp
             );
@@ -521,3 +645,42 @@ pub fn complete_quest(conn: &Connection, quest_id: i32) -> Result<Quest> {
 
     Ok(quest)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use crate::models::CreateTaskRequest;
+    use crate::services::task_service;
+
+    #[test]
+    fn test_overdue_quest_has_its_own_type() {
+        let db = Database::new(":memory:".into()).unwrap();
+        let conn = db.connection();
+        let task = task_service::add_task(
+            conn,
+            CreateTaskRequest {
+                title: "Zaległy raport".to_string(),
+                due_at: Some(Utc::now().timestamp() - 86_400),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let quests = generate_weekly_quests(conn).unwrap();
+        let overdue = quests
+            .iter()
+            .find(|quest| matches!(quest.quest_type, QuestType::Overdue))
+            .unwrap();
+        assert_eq!(overdue.target_value, 1);
+        assert_eq!(overdue.category, None);
+
+        task_service::toggle_task_status(conn, task.id).unwrap();
+        let overdue = get_quests_for_week(conn, None)
+            .unwrap()
+            .into_iter()
+            .find(|quest| matches!(quest.quest_type, QuestType::Overdue))
+            .unwrap();
+        assert_eq!(overdue.current_progress, 1);
+    }
+}
@@ -0,0 +1,516 @@
+use anyhow::Result;
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime, Utc, Weekday};
+use rusqlite::Connection;
+
+use crate::models::{
+    CreateTaskRequest, ParsedTask, QuickAddRequest, QuickAddResult, TaskPriority, TimeSettings,
+};
+use crate::services::{settings_service, task_service};
+
+/// Najdalszy termin względny (`za 3650 dni`)
+const MAX_RELATIVE_DAYS: i64 = 3650;
+
+/// Słowa poprzedzające datę, usuwane razem z nią (`w piątek`, `on friday`)
+const DATE_PREPOSITIONS: &[&str] = &["w", "we", "na", "on"];
+/// Słowa poprzedzające godzinę, usuwane razem z nią (`o 18:00`, `at 9:30`)
+const TIME_PREPOSITIONS: &[&str] = &["o", "at"];
+
+/// Zamienia polskie litery na odpowiedniki bez ogonków ("środa" → "sroda")
+pub fn fold_diacritics(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            'ą' => 'a',
+            'ć' => 'c',
+            'ę' => 'e',
+            'ł' => 'l',
+            'ń' => 'n',
+            'ó' => 'o',
+            'ś' => 's',
+            'ź' | 'ż' => 'z',
+            'Ą' => 'A',
+            'Ć' => 'C',
+            'Ę' => 'E',
+            'Ł' => 'L',
+            'Ń' => 'N',
+            'Ó' => 'O',
+            'Ś' => 'S',
+            'Ź' | 'Ż' => 'Z',
+            c => c,
+        })
+        .collect()
+}
+
+fn parse_priority(word: &str) -> Option<TaskPriority> {
+    Some(match word {
+        "low" | "niski" => TaskPriority::Low,
+        "medium" | "normal" | "sredni" | "normalny" => TaskPriority::Medium,
+        "high" | "wysoki" => TaskPriority::High,
+        "urgent" | "pilny" => TaskPriority::Urgent,
+        _ => return None,
+    })
+}
+
+fn parse_weekday(word: &str) -> Option<Weekday> {
+    Some(match word {
+        "poniedzialek" | "monday" => Weekday::Mon,
+        "wtorek" | "tuesday" => Weekday::Tue,
+        "sroda" | "srode" | "wednesday" => Weekday::Wed,
+        "czwartek" | "thursday" => Weekday::Thu,
+        "piatek" | "friday" => Weekday::Fri,
+        "sobota" | "sobote" | "saturday" => Weekday::Sat,
+        "niedziela" | "niedziele" | "sunday" => Weekday::Sun,
+        _ => return None,
+    })
+}
+
+/// Najbliższy podany dzień tygodnia po dniu `today`
+fn next_weekday(today: NaiveDate, weekday: Weekday) -> NaiveDate {
+    let days = (7 + weekday.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+    today + Duration::days(if days == 0 { 7 } else { i64::from(days) })
+}
+
+/// Rozpoznaje datę z jednego słowa: `dziś`, `jutro`, dzień tygodnia, `2025-01-25`, `25.01(.2025)`
+///
+/// Data bez roku, która w tym roku już minęła, wypada w przyszłym roku.
+fn parse_date(word: &str, today: NaiveDate) -> Option<NaiveDate> {
+    match word {
+        "dzis" | "dzisiaj" | "today" => return Some(today),
+        "jutro" | "tomorrow" => return Some(today + Duration::days(1)),
+        "pojutrze" => return Some(today + Duration::days(2)),
+        _ => {}
+    }
+    if let Some(weekday) = parse_weekday(word) {
+        return Some(next_weekday(today, weekday));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(word, "%Y-%m-%d") {
+        return Some(date);
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(word, "%d.%m.%Y") {
+        return Some(date);
+    }
+    // Miesiąc zawsze dwucyfrowo, żeby "1.5 kg" nie stało się datą
+    let (_, month) = word.split_once('.')?;
+    if month.len() != 2 {
+        return None;
+    }
+
+    let this_year =
+        NaiveDate::parse_from_str(&format!("{}.{}", word, today.year()), "%d.%m.%Y").ok()?;
+    if this_year >= today {
+        Some(this_year)
+    } else {
+        this_year.with_year(today.year() + 1)
+    }
+}
+
+/// Rozpoznaje datę na początku `words` (także `w piątek`, `za 3 dni`, `in 2 weeks`)
+///
+/// # Returns
+/// * `Option<(NaiveDate, usize)>` - Data i liczba zużytych słów
+fn parse_date_phrase(words: &[String], today: NaiveDate) -> Option<(NaiveDate, usize)> {
+    let first = words.first()?;
+    if let Some(date) = parse_date(first, today) {
+        return Some((date, 1));
+    }
+    if DATE_PREPOSITIONS.contains(&first.as_str()) {
+        if let Some(date) = words.get(1).and_then(|word| parse_date(word, today)) {
+            return Some((date, 2));
+        }
+    }
+    if first == "za" || first == "in" {
+        let count: i64 = words.get(1)?.parse().ok()?;
+        let days = match words.get(2)?.as_str() {
+            "dzien" | "dni" | "day" | "days" => count,
+            "tydzien" | "tygodnie" | "tygodni" | "week" | "weeks" => count * 7,
+            _ => return None,
+        };
+        if (1..=MAX_RELATIVE_DAYS).contains(&days) {
+            return Some((today + Duration::days(days), 3));
+        }
+    }
+
+    None
+}
+
+/// Rozpoznaje godzinę (`18:00`, `o 9:30`) na początku `words`
+///
+/// # Returns
+/// * `Option<(NaiveTime, usize)>` - Godzina i liczba zużytych słów
+fn parse_time_phrase(words: &[String]) -> Option<(NaiveTime, usize)> {
+    let parse_time = |word: &String| NaiveTime::parse_from_str(word, "%H:%M").ok();
+
+    let first = words.first()?;
+    if let Some(time) = parse_time(first) {
+        return Some((time, 1));
+    }
+    if TIME_PREPOSITIONS.contains(&first.as_str()) {
+        return words.get(1).and_then(parse_time).map(|time| (time, 2));
+    }
+
+    None
+}
+
+/// Rozpoznaje szacowany czas po `~`: `30m`, `45min`, `2h`, `1h30m`
+fn parse_estimate(text: &str) -> Option<i32> {
+    let mut minutes: i32 = 0;
+    let mut rest = text;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let value: i32 = rest[..digits].parse().ok()?;
+        rest = &rest[digits..];
+
+        let unit = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let factor = match &rest[..unit] {
+            "m" | "min" => 1,
+            "h" | "godz" => 60,
+            _ => return None,
+        };
+        minutes = minutes.checked_add(value.checked_mul(factor)?)?;
+        rest = &rest[unit..];
+    }
+
+    (minutes > 0).then_some(minutes)
+}
+
+/// Parsuje składnię szybkiego dodawania zadania (po polsku i angielsku)
+///
+/// Rozpoznawane fragmenty: `#tag`, `!high` / `!pilny` (priorytet), `+Projekt` (istniejący,
+/// `_` zastępuje spację), `~30m` (szacowany czas), data (`jutro`, `piątek`, `za 3 dni`,
+/// `25.01`) i godzina (`18:00`, `o 18:00`). Data z godziną daje termin (`due_at`), sama
+/// data - zaplanowany dzień, sama godzina - termin dzisiaj. Uwzględniane są tylko pierwsza
+/// data, godzina, priorytet i projekt; reszta tekstu zostaje w tytule.
+///
+/// # Arguments
+/// * `text` - Tekst wpisany przez użytkownika
+/// * `today` - Bieżący dzień użytkownika, od którego liczone są daty względne
+/// * `time_settings` - Strefa czasowa, w której godziny z tekstu zamieniane są na termin
+/// * `projects` - Istniejące projekty (ID i nazwa)
+pub fn parse(
+    text: &str,
+    today: NaiveDate,
+    time_settings: &TimeSettings,
+    projects: &[(i32, String)],
+) -> ParsedTask {
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    let words: Vec<String> = tokens
+        .iter()
+        .map(|token| fold_diacritics(&token.to_lowercase()))
+        .collect();
+
+    let mut parsed = ParsedTask::default();
+    let mut title = Vec::new();
+    let mut date = None;
+    let mut time = None;
+
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = tokens[i];
+        let word = &words[i];
+
+        let mut consumed = 0;
+        if let Some(tag) = token.strip_prefix('#').filter(|tag| !tag.is_empty()) {
+            let tag = tag.to_lowercase();
+            if !parsed.tags.contains(&tag) {
+                parsed.tags.push(tag);
+            }
+            consumed = 1;
+        } else if let Some(priority) = word.strip_prefix('!').and_then(parse_priority) {
+            if parsed.priority.is_none() {
+                parsed.priority = Some(priority);
+                consumed = 1;
+            }
+        } else if let Some(name) = word.strip_prefix('+') {
+            let name = name.replace('_', " ");
+            let project = projects
+                .iter()
+                .find(|(_, project)| fold_diacritics(&project.to_lowercase()) == name);
+            if let (Some((id, project)), None) = (project, parsed.project_id) {
+                parsed.project_id = Some(*id);
+                parsed.project = Some(project.clone());
+                consumed = 1;
+            }
+        } else if let Some(estimate) = word.strip_prefix('~').and_then(parse_estimate) {
+            if parsed.estimate_minutes.is_none() {
+                parsed.estimate_minutes = Some(estimate);
+                consumed = 1;
+            }
+        }
+
+        if consumed == 0 && date.is_none() {
+            if let Some((found, count)) = parse_date_phrase(&words[i..], today) {
+                date = Some(found);
+                consumed = count;
+            }
+        }
+        if consumed == 0 && time.is_none() {
+            if let Some((found, count)) = parse_time_phrase(&words[i..]) {
+                time = Some(found);
+                consumed = count;
+            }
+        }
+
+        if consumed == 0 {
+            title.push(token);
+            i += 1;
+        } else {
+            i += consumed;
+        }
+    }
+
+    match (date, time) {
+        (date, Some(time)) => {
+            let due = date.unwrap_or(today).and_time(time);
+            parsed.due_at = Some(time_settings.local_timestamp(due));
+        }
+        (Some(date), None) => {
+            parsed.scheduled_for = Some(date.format("%Y-%m-%d").to_string());
+        }
+        (None, None) => {}
+    }
+    parsed.title = title.join(" ");
+
+    parsed
+}
+
+/// Parsuje tekst szybkiego dodawania względem istniejących projektów (patrz [`parse`])
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `text` - Tekst wpisany przez użytkownika
+///
+/// # Returns
+/// * `Result<ParsedTask>` - Rozpoznane pola lub błąd
+pub fn parse_text(conn: &Connection, text: &str) -> Result<ParsedTask> {
+    let mut stmt = conn.prepare("SELECT id, name FROM projects")?;
+    let projects = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<(i32, String)>, _>>()?;
+
+    let time_settings = settings_service::get_time_settings(conn)?;
+    let today = time_settings.day_of(Utc::now());
+
+    Ok(parse(text, today, &time_settings, &projects))
+}
+
+/// Łączy rozpoznane pola z żądaniem utworzenia zadania
+///
+/// Jawnie podane pola żądania mają pierwszeństwo; tagi są sumowane, a projekt
+/// z tekstu jest pomijany dla podzadań (dziedziczą projekt rodzica).
+pub fn merge(parsed: ParsedTask, request: CreateTaskRequest) -> CreateTaskRequest {
+    let mut tags = request.tags;
+    tags.extend(parsed.tags);
+
+    CreateTaskRequest {
+        title: parsed.title,
+        priority: match request.priority {
+            TaskPriority::Medium => parsed.priority.unwrap_or_default(),
+            priority => priority,
+        },
+        tags,
+        due_at: request.due_at.or(parsed.due_at),
+        scheduled_for: request.scheduled_for.or(parsed.scheduled_for),
+        project_id: request
+            .project_id
+            .or(parsed.project_id.filter(|_| request.parent_id.is_none())),
+        estimate_minutes: request.estimate_minutes.or(parsed.estimate_minutes),
+        quick_add: false,
+        ..request
+    }
+}
+
+/// Tworzy zadanie z tekstu szybkiego dodawania
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `request` - Tekst zadania
+///
+/// # Returns
+/// * `Result<QuickAddResult>` - Utworzone zadanie i rozpoznane pola lub błąd
+pub fn quick_add_task(conn: &Connection, request: QuickAddRequest) -> Result<QuickAddResult> {
+    let parsed = parse_text(conn, &request.text)?;
+    let task = task_service::add_task(conn, merge(parsed.clone(), CreateTaskRequest::default()))?;
+
+    Ok(QuickAddResult { task, parsed })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::{NaiveDateTime, TimeZone};
+
+    /// Środa, 15 stycznia 2025
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()
+    }
+
+    fn utc() -> TimeSettings {
+        TimeSettings {
+            time_zone: "UTC".to_string(),
+            day_rollover_hour: 0,
+        }
+    }
+
+    fn projects() -> Vec<(i32, String)> {
+        vec![(1, "Zdrowie".to_string()), (2, "Praca domowa".to_string())]
+    }
+
+    fn timestamp(date: &str, time: &str) -> i64 {
+        NaiveDateTime::parse_from_str(&format!("{} {}", date, time), "%Y-%m-%d %H:%M")
+            .unwrap()
+            .and_utc()
+            .timestamp()
+    }
+
+    #[test]
+    fn test_parse_full_example() {
+        let parsed = parse(
+            "Trening jutro 18:00 #sport !high +Zdrowie ~30m",
+            today(),
+            &utc(),
+            &projects(),
+        );
+
+        assert_eq!(parsed.title, "Trening");
+        assert_eq!(parsed.due_at, Some(timestamp("2025-01-16", "18:00")));
+        assert_eq!(parsed.scheduled_for, None);
+        assert_eq!(parsed.tags, vec!["sport".to_string()]);
+        assert_eq!(parsed.priority, Some(TaskPriority::High));
+        assert_eq!(parsed.project_id, Some(1));
+        assert_eq!(parsed.project.as_deref(), Some("Zdrowie"));
+        assert_eq!(parsed.estimate_minutes, Some(30));
+    }
+
+    #[test]
+    fn test_parse_dates_in_polish_and_english() {
+        let scheduled = |text: &str| parse(text, today(), &utc(), &[]).scheduled_for;
+
+        assert_eq!(scheduled("Raport dziś").as_deref(), Some("2025-01-15"));
+        assert_eq!(scheduled("Raport tomorrow").as_deref(), Some("2025-01-16"));
+        assert_eq!(scheduled("Raport w piątek").as_deref(), Some("2025-01-17"));
+        // Ten sam dzień tygodnia oznacza przyszły tydzień
+        assert_eq!(scheduled("Raport środa").as_deref(), Some("2025-01-22"));
+        assert_eq!(scheduled("Report on monday").as_deref(), Some("2025-01-20"));
+        assert_eq!(scheduled("Raport za 3 dni").as_deref(), Some("2025-01-18"));
+        assert_eq!(
+            scheduled("Report in 2 weeks").as_deref(),
+            Some("2025-01-29")
+        );
+        assert_eq!(
+            scheduled("Raport 2025-03-01").as_deref(),
+            Some("2025-03-01")
+        );
+        assert_eq!(scheduled("Raport 20.01").as_deref(), Some("2025-01-20"));
+        assert_eq!(scheduled("Raport 10.01").as_deref(), Some("2026-01-10"));
+
+        let parsed = parse("Spotkanie o 9:30", today(), &utc(), &[]);
+        assert_eq!(parsed.title, "Spotkanie");
+        assert_eq!(parsed.due_at, Some(timestamp("2025-01-15", "09:30")));
+    }
+
+    #[test]
+    fn test_parse_keeps_unrecognized_text_in_title() {
+        let parsed = parse(
+            "Zadzwoń do +Banku w sprawie !czegoś ~później # 2 razy jutro pojutrze",
+            today(),
+            &utc(),
+            &projects(),
+        );
+
+        // Druga data zostaje w tytule, nieznany projekt i priorytet też
+        assert_eq!(
+            parsed.title,
+            "Zadzwoń do +Banku w sprawie !czegoś ~później # 2 razy pojutrze"
+        );
+        assert_eq!(parsed.scheduled_for.as_deref(), Some("2025-01-16"));
+        assert_eq!(parsed.project_id, None);
+        assert_eq!(parsed.priority, None);
+        assert_eq!(parsed.estimate_minutes, None);
+    }
+
+    #[test]
+    fn test_parse_projects_priorities_and_estimates() {
+        let parsed = parse(
+            "Odkurzyć +praca_domowa !PILNY ~1h30m",
+            today(),
+            &utc(),
+            &projects(),
+        );
+        assert_eq!(parsed.title, "Odkurzyć");
+        assert_eq!(parsed.project_id, Some(2));
+        assert_eq!(parsed.priority, Some(TaskPriority::Urgent));
+        assert_eq!(parsed.estimate_minutes, Some(90));
+
+        assert_eq!(parse_estimate("45min"), Some(45));
+        assert_eq!(parse_estimate("2h"), Some(120));
+        assert_eq!(parse_estimate("0m"), None);
+        assert_eq!(parse_estimate("30"), None);
+        assert_eq!(parse_estimate("99999999h"), None);
+        assert_eq!(fold_diacritics("Książka Żółć"), "Ksiazka Zolc");
+    }
+
+    #[test]
+    fn test_merge_prefers_explicit_fields() {
+        let parsed = parse(
+            "Trening jutro #sport !high +Zdrowie",
+            today(),
+            &utc(),
+            &projects(),
+        );
+        let request = merge(
+            parsed,
+            CreateTaskRequest {
+                title: "ignored".to_string(),
+                priority: TaskPriority::Low,
+                tags: vec!["zdrowie".to_string()],
+                scheduled_for: Some("2025-02-01".to_string()),
+                parent_id: Some(7),
+                quick_add: true,
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(request.title, "Trening");
+        assert_eq!(request.priority, TaskPriority::Low);
+        assert_eq!(
+            request.tags,
+            vec!["zdrowie".to_string(), "sport".to_string()]
+        );
+        assert_eq!(request.scheduled_for.as_deref(), Some("2025-02-01"));
+        // Podzadanie dziedziczy projekt rodzica
+        assert_eq!(request.project_id, None);
+        assert!(!request.quick_add);
+    }
+
+    #[test]
+    fn test_parse_uses_user_time_zone() {
+        let warsaw = TimeSettings {
+            time_zone: "Europe/Warsaw".to_string(),
+            day_rollover_hour: 0,
+        };
+        // 23:30 UTC to w Warszawie już 00:30 następnego dnia
+        let today = warsaw.day_of(
+            chrono::Utc
+                .with_ymd_and_hms(2025, 1, 15, 23, 30, 0)
+                .unwrap(),
+        );
+        assert_eq!(today, NaiveDate::from_ymd_opt(2025, 1, 16).unwrap());
+
+        // 18:00 czasu zimowego w Warszawie to 17:00 UTC
+        let parsed = parse("Trening jutro 18:00", today, &warsaw, &[]);
+        assert_eq!(parsed.due_at, Some(timestamp("2025-01-17", "17:00")));
+
+        let db = crate::database::Database::new(":memory:".into()).unwrap();
+        let conn = db.connection();
+        settings_service::set_time_zone(conn, "Europe/Warsaw").unwrap();
+        let parsed = parse_text(conn, "Trening jutro 18:00").unwrap();
+        let tomorrow = settings_service::today(conn).unwrap() + Duration::days(1);
+        let due = chrono::DateTime::from_timestamp(parsed.due_at.unwrap(), 0).unwrap();
+        let local = due.with_timezone(&chrono_tz::Europe::Warsaw).naive_local();
+        assert_eq!(local, tomorrow.and_hms_opt(18, 0, 0).unwrap());
+    }
+}
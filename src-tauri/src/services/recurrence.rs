@@ -0,0 +1,430 @@
+use anyhow::Result;
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::services::error;
+
+/// Największy dozwolony odstęp (INTERVAL) między wystąpieniami
+const MAX_INTERVAL: u32 = 365;
+/// Ile okresów (tygodni, miesięcy) przeszukujemy w poszukiwaniu kolejnego wystąpienia,
+/// licząc od okresu zawierającego dzień, po którym szukamy
+const MAX_PERIODS_SCANNED: u32 = 1000;
+
+/// Częstotliwość powtarzania (FREQ)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+/// Reguła powtarzania zadania - podzbiór RRULE z RFC 5545
+///
+/// Obsługiwane części: `FREQ` (DAILY, WEEKLY, MONTHLY), `INTERVAL`,
+/// `BYDAY` (tylko z WEEKLY, bez numerów), `BYMONTHDAY` (tylko z MONTHLY,
+/// wartości ujemne liczą od końca miesiąca), `COUNT` i `UNTIL` (data YYYYMMDD).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub frequency: Frequency,
+    pub interval: u32,
+    pub by_day: Vec<Weekday>,
+    pub by_month_day: Vec<i32>,
+    /// Łączna liczba wystąpień serii
+    pub count: Option<u32>,
+    /// Ostatni dzień, w którym może wypaść wystąpienie
+    pub until: Option<NaiveDate>,
+}
+
+fn weekday_code(day: Weekday) -> &'static str {
+    match day {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+fn parse_weekday(code: &str) -> Option<Weekday> {
+    Some(match code {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return None,
+    })
+}
+
+fn parse_number<T: FromStr>(key: &str, value: &str) -> Result<T> {
+    value
+        .parse()
+        .map_err(|_| error::validation(format!("Invalid {} value '{}'", key, value)))
+}
+
+impl FromStr for RecurrenceRule {
+    type Err = anyhow::Error;
+
+    fn from_str(rule: &str) -> Result<Self> {
+        let rule = rule.trim();
+        let rule = rule.strip_prefix("RRULE:").unwrap_or(rule);
+
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut by_day = Vec::new();
+        let mut by_month_day = Vec::new();
+        let mut count = None;
+        let mut until = None;
+
+        for part in rule.split(';').filter(|part| !part.trim().is_empty()) {
+            let Some((key, value)) = part.split_once('=') else {
+                return Err(error::validation(format!(
+                    "Invalid recurrence rule part '{}'",
+                    part
+                )));
+            };
+            let key = key.trim().to_uppercase();
+            let value = value.trim().to_uppercase();
+
+            match key.as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        _ => {
+                            return Err(error::validation(format!(
+                                "Unsupported FREQ '{}' (use DAILY, WEEKLY or MONTHLY)",
+                                value
+                            )))
+                        }
+                    })
+                }
+                "INTERVAL" => interval = parse_number(&key, &value)?,
+                "BYDAY" => {
+                    for code in value.split(',') {
+                        let day = parse_weekday(code.trim()).ok_or_else(|| {
+                            error::validation(format!("Invalid BYDAY value '{}'", code))
+                        })?;
+                        by_day.push(day);
+                    }
+                }
+                "BYMONTHDAY" => {
+                    for day in value.split(',') {
+                        let day: i32 = parse_number(&key, day.trim())?;
+                        if day == 0 || !(-31..=31).contains(&day) {
+                            return Err(error::validation(format!(
+                                "Invalid BYMONTHDAY value '{}'",
+                                day
+                            )));
+                        }
+                        by_month_day.push(day);
+                    }
+                }
+                "COUNT" => count = Some(parse_number(&key, &value)?),
+                "UNTIL" => {
+                    let date = value.get(..8).unwrap_or(&value);
+                    until = Some(NaiveDate::parse_from_str(date, "%Y%m%d").map_err(|_| {
+                        error::validation(format!("Invalid UNTIL value '{}'", value))
+                    })?);
+                }
+                _ => {
+                    return Err(error::validation(format!(
+                        "Unsupported recurrence rule part '{}' (supported: FREQ, INTERVAL, BYDAY, BYMONTHDAY, COUNT, UNTIL)",
+                        key
+                    )))
+                }
+            }
+        }
+
+        let Some(frequency) = frequency else {
+            return Err(error::validation("Recurrence rule must contain FREQ"));
+        };
+        if interval == 0 || interval > MAX_INTERVAL {
+            return Err(error::validation(format!(
+                "INTERVAL must be between 1 and {}",
+                MAX_INTERVAL
+            )));
+        }
+        if !by_day.is_empty() && frequency != Frequency::Weekly {
+            return Err(error::validation(
+                "BYDAY is only supported with FREQ=WEEKLY",
+            ));
+        }
+        if !by_month_day.is_empty() && frequency != Frequency::Monthly {
+            return Err(error::validation(
+                "BYMONTHDAY is only supported with FREQ=MONTHLY",
+            ));
+        }
+        if count == Some(0) {
+            return Err(error::validation("COUNT must be at least 1"));
+        }
+        if count.is_some() && until.is_some() {
+            return Err(error::validation("COUNT and UNTIL cannot be used together"));
+        }
+
+        by_day.sort_by_key(|day| day.num_days_from_monday());
+        by_day.dedup();
+        by_month_day.sort_unstable();
+        by_month_day.dedup();
+
+        Ok(RecurrenceRule {
+            frequency,
+            interval,
+            by_day,
+            by_month_day,
+            count,
+            until,
+        })
+    }
+}
+
+impl fmt::Display for RecurrenceRule {
+    /// Zapisuje regułę w postaci kanonicznej, np. `FREQ=WEEKLY;BYDAY=MO,TH`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frequency = match self.frequency {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+        };
+        write!(f, "FREQ={}", frequency)?;
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if !self.by_day.is_empty() {
+            let days: Vec<&str> = self.by_day.iter().map(|day| weekday_code(*day)).collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        if !self.by_month_day.is_empty() {
+            let days: Vec<String> = self.by_month_day.iter().map(i32::to_string).collect();
+            write!(f, ";BYMONTHDAY={}", days.join(","))?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={}", count)?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", until.format("%Y%m%d"))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Dzień miesiąca z BYMONTHDAY (ujemne liczą od końca); `None`, gdy miesiąc jest za krótki
+fn resolve_month_day(year: i32, month: u32, day: i32) -> Option<NaiveDate> {
+    if day > 0 {
+        return NaiveDate::from_ymd_opt(year, month, day as u32);
+    }
+
+    let first_of_next = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1)?
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1)?
+    };
+    let date = first_of_next + Duration::days(day.into());
+    (date.month() == month).then_some(date)
+}
+
+impl RecurrenceRule {
+    /// Wylicza dzień kolejnego wystąpienia
+    ///
+    /// Wystąpienia liczone są od `anchor` (dnia bieżącego wystąpienia). Zwracany jest
+    /// pierwszy dzień po `anchor`, który nie wypada przed `today` - zaległe wystąpienia
+    /// ukończone po czasie nie tworzą kolejnych zaległych zadań.
+    ///
+    /// # Returns
+    /// * `Option<NaiveDate>` - Dzień kolejnego wystąpienia lub `None`, gdy minął `UNTIL`
+    pub fn next_occurrence(&self, anchor: NaiveDate, today: NaiveDate) -> Option<NaiveDate> {
+        let after = anchor.max(today.pred_opt()?);
+
+        let next = match self.frequency {
+            Frequency::Daily => {
+                let interval = i64::from(self.interval);
+                let periods = (after - anchor).num_days() / interval + 1;
+                Some(anchor + Duration::days(periods * interval))
+            }
+            Frequency::Weekly => {
+                let days = if self.by_day.is_empty() {
+                    vec![anchor.weekday()]
+                } else {
+                    self.by_day.clone()
+                };
+                let monday_of = |date: NaiveDate| {
+                    date - Duration::days(date.weekday().num_days_from_monday().into())
+                };
+                // Skanowanie zaczyna się od okresu zawierającego `after`, więc odległa kotwica nie wyczerpuje limitu
+                let interval = i64::from(self.interval);
+                let anchor_week = monday_of(anchor);
+                let first_period = (monday_of(after) - anchor_week).num_weeks() / interval;
+                let week_start = anchor_week + Duration::weeks(first_period * interval);
+
+                (0..MAX_PERIODS_SCANNED)
+                    .map(|period| week_start + Duration::weeks((period * self.interval).into()))
+                    .flat_map(|monday| {
+                        days.iter().map(move |day| {
+                            monday + Duration::days(day.num_days_from_monday().into())
+                        })
+                    })
+                    .find(|date| *date > after)
+            }
+            Frequency::Monthly => {
+                let days = if self.by_month_day.is_empty() {
+                    vec![anchor.day() as i32]
+                } else {
+                    self.by_month_day.clone()
+                };
+                let anchor_month = anchor.year() * 12 + anchor.month0() as i32;
+                let after_month = after.year() * 12 + after.month0() as i32;
+                let interval = self.interval as i32;
+                let first_month = anchor_month + (after_month - anchor_month) / interval * interval;
+
+                (0..MAX_PERIODS_SCANNED)
+                    .map(|period| first_month + (period * self.interval) as i32)
+                    .flat_map(|month| {
+                        let (year, month) = (month / 12, month % 12 + 1);
+                        let mut dates: Vec<NaiveDate> = days
+                            .iter()
+                            .filter_map(|day| resolve_month_day(year, month as u32, *day))
+                            .collect();
+                        dates.sort();
+                        dates
+                    })
+                    .find(|date| *date > after)
+            }
+        }?;
+
+        match self.until {
+            Some(until) if next > until => None,
+            _ => Some(next),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_format() {
+        let rule: RecurrenceRule = "RRULE:freq=weekly;byday=th,mo,MO;interval=2"
+            .parse()
+            .unwrap();
+        assert_eq!(rule.by_day, vec![Weekday::Mon, Weekday::Thu]);
+        assert_eq!(rule.to_string(), "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH");
+
+        let rule: RecurrenceRule = "FREQ=MONTHLY;BYMONTHDAY=-1;UNTIL=20261231T000000Z"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            rule.to_string(),
+            "FREQ=MONTHLY;BYMONTHDAY=-1;UNTIL=20261231"
+        );
+
+        for invalid in [
+            "",
+            "INTERVAL=2",
+            "FREQ=YEARLY",
+            "FREQ=DAILY;INTERVAL=0",
+            "FREQ=DAILY;BYDAY=MO",
+            "FREQ=WEEKLY;BYDAY=1MO",
+            "FREQ=MONTHLY;BYMONTHDAY=32",
+            "FREQ=DAILY;COUNT=3;UNTIL=20261231",
+            "FREQ=DAILY;BYHOUR=9",
+        ] {
+            assert!(invalid.parse::<RecurrenceRule>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_next_occurrence_daily_and_weekly() {
+        let every_three_days: RecurrenceRule = "FREQ=DAILY;INTERVAL=3".parse().unwrap();
+        let anchor = date("2026-10-01");
+        assert_eq!(
+            every_three_days.next_occurrence(anchor, anchor),
+            Some(date("2026-10-04"))
+        );
+        // Ukończone po czasie - kolejne wystąpienie nie wypada w przeszłości
+        assert_eq!(
+            every_three_days.next_occurrence(anchor, date("2026-10-09")),
+            Some(date("2026-10-10"))
+        );
+
+        // 2026-10-12 to poniedziałek
+        let mon_thu: RecurrenceRule = "FREQ=WEEKLY;BYDAY=MO,TH".parse().unwrap();
+        let monday = date("2026-10-12");
+        assert_eq!(
+            mon_thu.next_occurrence(monday, monday),
+            Some(date("2026-10-15"))
+        );
+        assert_eq!(
+            mon_thu.next_occurrence(date("2026-10-15"), date("2026-10-15")),
+            Some(date("2026-10-19"))
+        );
+
+        let fortnightly: RecurrenceRule = "FREQ=WEEKLY;INTERVAL=2".parse().unwrap();
+        assert_eq!(
+            fortnightly.next_occurrence(monday, monday),
+            Some(date("2026-10-26"))
+        );
+    }
+
+    #[test]
+    fn test_next_occurrence_monthly_and_until() {
+        let last_day: RecurrenceRule = "FREQ=MONTHLY;BYMONTHDAY=-1".parse().unwrap();
+        assert_eq!(
+            last_day.next_occurrence(date("2026-01-31"), date("2026-01-31")),
+            Some(date("2026-02-28"))
+        );
+
+        // Miesiące bez 31. dnia są pomijane
+        let day_31: RecurrenceRule = "FREQ=MONTHLY".parse().unwrap();
+        assert_eq!(
+            day_31.next_occurrence(date("2026-03-31"), date("2026-03-31")),
+            Some(date("2026-05-31"))
+        );
+
+        let until: RecurrenceRule = "FREQ=WEEKLY;UNTIL=20261020".parse().unwrap();
+        assert_eq!(
+            until.next_occurrence(date("2026-10-12"), date("2026-10-12")),
+            Some(date("2026-10-19"))
+        );
+        assert_eq!(
+            until.next_occurrence(date("2026-10-19"), date("2026-10-19")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_next_occurrence_with_distant_anchor() {
+        // Odległa kotwica (ponad 1000 okresów wstecz) nie może wygasić serii przez limit skanowania
+        let anchor = date("2006-10-02");
+        let today = date("2026-10-14");
+
+        let mon_thu: RecurrenceRule = "FREQ=WEEKLY;BYDAY=MO,TH".parse().unwrap();
+        assert_eq!(
+            mon_thu.next_occurrence(anchor, today),
+            Some(date("2026-10-15"))
+        );
+
+        let fortnightly: RecurrenceRule = "FREQ=WEEKLY;INTERVAL=2".parse().unwrap();
+        assert_eq!(
+            fortnightly.next_occurrence(anchor, today),
+            Some(date("2026-10-19"))
+        );
+
+        let bimonthly: RecurrenceRule = "FREQ=MONTHLY;INTERVAL=2;BYMONTHDAY=15".parse().unwrap();
+        assert_eq!(
+            bimonthly.next_occurrence(date("1850-01-15"), today),
+            Some(date("2026-11-15"))
+        );
+    }
+}
@@ -0,0 +1,260 @@
+use anyhow::Result;
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection, Row};
+
+use crate::models::{SearchKind, SearchQuery, SearchResult};
+use crate::services::error;
+use crate::services::quick_add::fold_diacritics;
+
+/// Liczba wyników zwracana, gdy klient nie podał limitu
+const DEFAULT_LIMIT: u32 = 20;
+/// Największa dozwolona liczba wyników
+const MAX_LIMIT: u32 = 100;
+/// Ile słów obejmuje snippet notatek lub opisu
+const SNIPPET_TOKENS: i32 = 12;
+/// Waga dopasowania w tytule względem treści w rankingu BM25
+const TITLE_WEIGHT: f64 = 5.0;
+
+/// Buduje zapytanie FTS5 z tekstu użytkownika
+///
+/// Każde słowo staje się prefiksem (`"ksiaz"*`), a słowa muszą wystąpić wszystkie.
+/// Znaki spoza liter i cyfr rozdzielają słowa, więc składnia FTS5 nie przedostaje się
+/// do zapytania. Polskie znaki są zdejmowane tak jak w indeksie.
+///
+/// # Returns
+/// * `Option<String>` - Zapytanie lub None, gdy nie ma czego szukać
+fn build_match_query(text: &str) -> Option<String> {
+    let folded = fold_diacritics(&text.to_lowercase());
+    let terms: Vec<String> = folded
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(|term| format!("\"{}\"*", term))
+        .collect();
+
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+fn row_to_result(row: &Row) -> Result<Option<SearchResult>, rusqlite::Error> {
+    let kind: String = row.get("kind")?;
+    let Some(kind) = SearchKind::from_db(&kind) else {
+        return Ok(None);
+    };
+    let snippet: Option<String> = row.get("snippet")?;
+
+    Ok(Some(SearchResult {
+        kind,
+        id: row.get("item_id")?,
+        title: row.get("title")?,
+        title_highlight: row.get("title_highlight")?,
+        snippet: snippet.filter(|snippet| !snippet.is_empty()),
+        rank: row.get("rank")?,
+    }))
+}
+
+/// Przeszukuje zadania (tytuł i notatki), nawyki (tytuł) i questy (tytuł i opis)
+///
+/// Wyszukiwanie ignoruje wielkość liter i polskie znaki ("ksiazka" znajduje "Książka"),
+/// a wyniki są posortowane od najlepiej dopasowanych (BM25, tytuł waży więcej niż treść).
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `query` - Szukany tekst, opcjonalny rodzaj wyników i limit
+///
+/// # Returns
+/// * `Result<Vec<SearchResult>>` - Wyniki z podświetlonymi dopasowaniami lub błąd
+pub fn search(conn: &Connection, query: SearchQuery) -> Result<Vec<SearchResult>> {
+    let limit = match query.limit {
+        None => DEFAULT_LIMIT,
+        Some(limit) if (1..=MAX_LIMIT).contains(&limit) => limit,
+        Some(_) => {
+            return Err(error::validation(format!(
+                "limit must be between 1 and {}",
+                MAX_LIMIT
+            )))
+        }
+    };
+    let Some(match_query) = build_match_query(&query.q) else {
+        return Ok(Vec::new());
+    };
+
+    let mut params = vec![Value::Text(match_query)];
+    let kind_condition = match query.kind {
+        Some(kind) => {
+            params.push(Value::Text(kind.as_str().to_string()));
+            "AND documents.kind = ?"
+        }
+        None => "",
+    };
+    params.push(Value::Integer(i64::from(limit)));
+
+    let sql = format!(
+        "SELECT documents.kind, documents.item_id, documents.title,
+                highlight(search_index, 0, '<mark>', '</mark>') AS title_highlight,
+                snippet(search_index, 1, '<mark>', '</mark>', '…', {}) AS snippet,
+                bm25(search_index, {}, 1.0) AS rank
+         FROM search_index
+         JOIN search_documents AS documents ON documents.id = search_index.rowid
+         WHERE search_index MATCH ? {}
+         ORDER BY rank
+         LIMIT ?",
+        SNIPPET_TOKENS, TITLE_WEIGHT, kind_condition
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(params_from_iter(params), row_to_result)?;
+
+    let mut results = Vec::new();
+    for result in rows {
+        if let Some(result) = result? {
+            results.push(result);
+        }
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use crate::models::{CreateHabitRequest, CreateTaskRequest, HabitType, UpdateTaskRequest};
+    use crate::services::{habit_service, task_service};
+
+    fn create_test_db() -> Database {
+        Database::new(":memory:".into()).unwrap()
+    }
+
+    fn add_task(conn: &Connection, title: &str, notes: Option<&str>) -> i32 {
+        task_service::add_task(
+            conn,
+            CreateTaskRequest {
+                title: title.to_string(),
+                notes: notes.map(str::to_string),
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .id
+    }
+
+    fn search_for(conn: &Connection, q: &str) -> Vec<SearchResult> {
+        search(
+            conn,
+            SearchQuery {
+                q: q.to_string(),
+                ..Default::default()
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_build_match_query() {
+        assert_eq!(
+            build_match_query("Książka  \"OR\" e-mail*").as_deref(),
+            Some("\"ksiazka\"* \"or\"* \"e\"* \"mail\"*")
+        );
+        assert_eq!(build_match_query(" -*\" "), None);
+    }
+
+    #[test]
+    fn test_search_folds_polish_diacritics() {
+        let db = create_test_db();
+        let conn = db.connection();
+        let task_id = add_task(conn, "Książka do przeczytania", Some("Rozdział o Łodzi"));
+        add_task(conn, "Kupić mleko", None);
+
+        let results = search_for(conn, "ksiazka");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].kind, SearchKind::Task);
+        assert_eq!(results[0].id, task_id);
+        assert_eq!(
+            results[0].title_highlight,
+            "<mark>Książka</mark> do przeczytania"
+        );
+
+        // "ł" nie jest rozkładane przez tokenizer, ale indeks i zapytanie je zamieniają
+        let results = search_for(conn, "lodzi");
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results[0].snippet.as_deref(),
+            Some("Rozdział o <mark>Łodzi</mark>")
+        );
+        assert_eq!(search_for(conn, "ŁÓDŹ").len(), 1);
+    }
+
+    #[test]
+    fn test_search_ranks_and_filters_across_kinds() {
+        let db = create_test_db();
+        let conn = db.connection();
+        add_task(conn, "Zakupy", Some("Kupić buty do biegania"));
+        let habit = habit_service::add_habit(
+            conn,
+            CreateHabitRequest {
+                title: "Bieganie rano".to_string(),
+                habit_type: HabitType::Boolean,
+                target_value: None,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO quests (title, description, quest_type, target_value, reward_exp, week,
+                                 created_at, updated_at)
+             VALUES ('Maratończyk', 'Biegaj 5 razy w tygodniu', 'Habit', 5, 100, '2025-03', 0, 0)",
+            [],
+        )
+        .unwrap();
+
+        let results = search_for(conn, "biega");
+        assert_eq!(results.len(), 3);
+        // Dopasowanie w tytule jest ważniejsze niż w treści
+        assert_eq!(results[0].kind, SearchKind::Habit);
+        assert_eq!(results[0].id, habit.id);
+
+        let quests = search(
+            conn,
+            SearchQuery {
+                q: "biega".to_string(),
+                kind: Some(SearchKind::Quest),
+                limit: Some(5),
+            },
+        )
+        .unwrap();
+        assert_eq!(quests.len(), 1);
+        assert_eq!(quests[0].title, "Maratończyk");
+
+        let invalid = search(
+            conn,
+            SearchQuery {
+                q: "biega".to_string(),
+                limit: Some(0),
+                ..Default::default()
+            },
+        );
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn test_index_follows_updates_and_deletes() {
+        let db = create_test_db();
+        let conn = db.connection();
+        let task_id = add_task(conn, "Napisać raport", None);
+
+        task_service::update_task(
+            conn,
+            task_id,
+            UpdateTaskRequest {
+                title: Some("Napisać sprawozdanie".to_string()),
+                notes: Some(Some("Dla zarządu".to_string())),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(search_for(conn, "raport").is_empty());
+        assert_eq!(search_for(conn, "sprawozdanie").len(), 1);
+        assert_eq!(search_for(conn, "zarzad").len(), 1);
+
+        task_service::delete_task(conn, task_id).unwrap();
+        assert!(search_for(conn, "sprawozdanie").is_empty());
+    }
+}
@@ -0,0 +1,427 @@
+use anyhow::Result;
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use chrono_tz::Tz;
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::models::{ApiServerConfig, TimeSettings};
+use crate::services::error;
+
+/// Klucz ustawienia z listą originów dopuszczonych przez CORS serwera API
+const API_CORS_ORIGINS: &str = "api.cors_origins";
+/// Klucz ustawienia z portem serwera API
+const API_PORT: &str = "api.port";
+/// Klucz ustawienia określającego czy serwer API startuje razem z aplikacją
+const API_AUTO_START: &str = "api.auto_start";
+/// Klucz ustawienia określającego czy ukończenie ostatniego podzadania kończy zadanie nadrzędne
+const TASKS_AUTO_COMPLETE_PARENTS: &str = "tasks.auto_complete_parents";
+/// Klucz ustawienia z liczbą dni, przez które usunięte elementy czekają w koszu
+const TRASH_RETENTION_DAYS: &str = "trash.retention_days";
+/// Klucz ustawienia ze strefą czasową IANA użytkownika
+const TIME_ZONE: &str = "time.zone";
+/// Klucz ustawienia z godziną, o której zaczyna się nowy dzień
+const DAY_ROLLOVER_HOUR: &str = "time.day_rollover_hour";
+/// Port używany, gdy użytkownik nie wybrał własnego
+pub const DEFAULT_API_PORT: u16 = 3000;
+/// Najniższy port, na którym można uruchomić serwer API
+const MIN_API_PORT: u16 = 1000;
+/// Okres przechowywania elementów w koszu, gdy użytkownik nie wybrał własnego
+pub const DEFAULT_TRASH_RETENTION_DAYS: u32 = 30;
+/// Najdłuższy okres przechowywania elementów w koszu
+const MAX_TRASH_RETENTION_DAYS: u32 = 365;
+/// Strefa czasowa używana, gdy użytkownik nie wybrał własnej
+pub const DEFAULT_TIME_ZONE: &str = "UTC";
+
+/// Pobiera wartość ustawienia
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `key` - Klucz ustawienia
+///
+/// # Returns
+/// * `Result<Option<String>>` - Wartość ustawienia lub None jeśli nie zostało zapisane
+pub fn get_setting(conn: &Connection, key: &str) -> Result<Option<String>> {
+    let value = conn
+        .query_row("SELECT value FROM settings WHERE key = ?1", [key], |row| {
+            row.get(0)
+        })
+        .optional()?;
+
+    Ok(value)
+}
+
+/// Zapisuje wartość ustawienia (nadpisuje istniejącą)
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `key` - Klucz ustawienia
+/// * `value` - Nowa wartość
+///
+/// # Returns
+/// * `Result<()>` - Sukces lub błąd
+pub fn set_setting(conn: &Connection, key: &str, value: &str) -> Result<()> {
+    let now = chrono::Utc::now().timestamp();
+
+    conn.execute(
+        "INSERT INTO settings (key, value, updated_at) VALUES (?1, ?2, ?3)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        (key, value, now),
+    )?;
+
+    Ok(())
+}
+
+/// Pobiera listę originów, które mogą odpytywać API z przeglądarki
+///
+/// Pusta lista oznacza, że żadna strona internetowa nie ma dostępu przez CORS.
+pub fn get_cors_origins(conn: &Connection) -> Result<Vec<String>> {
+    let origins = get_setting(conn, API_CORS_ORIGINS)?
+        .map(|value| {
+            value
+                .split(',')
+                .map(|origin| origin.trim().to_string())
+                .filter(|origin| !origin.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(origins)
+}
+
+/// Zapisuje listę originów dopuszczonych przez CORS
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `origins` - Lista originów w postaci `http(s)://host[:port]`
+///
+/// # Returns
+/// * `Result<Vec<String>>` - Zapisana (znormalizowana) lista originów lub błąd
+pub fn set_cors_origins(conn: &Connection, origins: Vec<String>) -> Result<Vec<String>> {
+    let mut normalized = Vec::new();
+
+    for origin in origins {
+        let origin = origin.trim().trim_end_matches('/').to_string();
+
+        if origin == "*" {
+            return Err(error::validation(
+                "Wildcard origin is not allowed, list origins explicitly",
+            ));
+        }
+        if !(origin.starts_with("http://") || origin.starts_with("https://"))
+            || origin.contains(',')
+            || origin.chars().any(char::is_whitespace)
+        {
+            return Err(error::validation(format!(
+                "Invalid origin '{}' (expected http(s)://host[:port])",
+                origin
+            )));
+        }
+
+        if !normalized.contains(&origin) {
+            normalized.push(origin);
+        }
+    }
+
+    set_setting(conn, API_CORS_ORIGINS, &normalized.join(","))?;
+
+    Ok(normalized)
+}
+
+/// Pobiera zapisaną konfigurację serwera API
+///
+/// Brakujące lub uszkodzone wartości są zastępowane domyślnymi (port 3000, bez auto-startu).
+pub fn get_api_server_config(conn: &Connection) -> Result<ApiServerConfig> {
+    let port = get_setting(conn, API_PORT)?
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_API_PORT);
+    let auto_start = get_setting(conn, API_AUTO_START)?.as_deref() == Some("true");
+
+    Ok(ApiServerConfig { port, auto_start })
+}
+
+/// Zapisuje port serwera API
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `port` - Port z zakresu 1000-65535
+///
+/// # Returns
+/// * `Result<()>` - Sukces lub błąd walidacji
+pub fn set_api_port(conn: &Connection, port: u16) -> Result<()> {
+    validate_api_port(port)?;
+
+    set_setting(conn, API_PORT, &port.to_string())
+}
+
+/// Sprawdza czy port nadaje się dla serwera API (bez zapisywania)
+pub fn validate_api_port(port: u16) -> Result<()> {
+    if port < MIN_API_PORT {
+        return Err(error::validation(format!(
+            "API port must be between {} and 65535",
+            MIN_API_PORT
+        )));
+    }
+
+    Ok(())
+}
+
+/// Zapisuje czy serwer API ma startować razem z aplikacją
+pub fn set_api_auto_start(conn: &Connection, enabled: bool) -> Result<()> {
+    set_setting(conn, API_AUTO_START, if enabled { "true" } else { "false" })
+}
+
+/// Sprawdza czy ukończenie ostatniego podzadania ma kończyć zadanie nadrzędne (domyślnie tak)
+pub fn get_auto_complete_parents(conn: &Connection) -> Result<bool> {
+    Ok(get_setting(conn, TASKS_AUTO_COMPLETE_PARENTS)?.as_deref() != Some("false"))
+}
+
+/// Włącza lub wyłącza automatyczne kończenie zadań nadrzędnych
+pub fn set_auto_complete_parents(conn: &Connection, enabled: bool) -> Result<()> {
+    set_setting(
+        conn,
+        TASKS_AUTO_COMPLETE_PARENTS,
+        if enabled { "true" } else { "false" },
+    )
+}
+
+/// Pobiera liczbę dni, po których elementy z kosza są trwale usuwane (domyślnie 30)
+pub fn get_trash_retention_days(conn: &Connection) -> Result<u32> {
+    let days = get_setting(conn, TRASH_RETENTION_DAYS)?
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_TRASH_RETENTION_DAYS);
+
+    Ok(days)
+}
+
+/// Zapisuje okres przechowywania elementów w koszu
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `days` - Liczba dni z zakresu 1-365
+///
+/// # Returns
+/// * `Result<()>` - Sukces lub błąd walidacji
+pub fn set_trash_retention_days(conn: &Connection, days: u32) -> Result<()> {
+    if !(1..=MAX_TRASH_RETENTION_DAYS).contains(&days) {
+        return Err(error::validation(format!(
+            "Trash retention must be between 1 and {} days",
+            MAX_TRASH_RETENTION_DAYS
+        )));
+    }
+
+    set_setting(conn, TRASH_RETENTION_DAYS, &days.to_string())
+}
+
+/// Pobiera strefę czasową i godzinę rozpoczęcia dnia (domyślnie UTC i północ)
+///
+/// Uszkodzona strefa czasowa jest zastępowana domyślną.
+pub fn get_time_settings(conn: &Connection) -> Result<TimeSettings> {
+    let time_zone = get_setting(conn, TIME_ZONE)?
+        .filter(|value| value.parse::<Tz>().is_ok())
+        .unwrap_or_else(|| DEFAULT_TIME_ZONE.to_string());
+    let day_rollover_hour = get_setting(conn, DAY_ROLLOVER_HOUR)?
+        .and_then(|value| value.parse().ok())
+        .filter(|hour| *hour < 24)
+        .unwrap_or(0);
+
+    Ok(TimeSettings {
+        time_zone,
+        day_rollover_hour,
+    })
+}
+
+/// Zapisuje strefę czasową użytkownika
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `time_zone` - Nazwa strefy z bazy IANA (np. "Europe/Warsaw")
+///
+/// # Returns
+/// * `Result<()>` - Sukces lub błąd walidacji
+pub fn set_time_zone(conn: &Connection, time_zone: &str) -> Result<()> {
+    let time_zone: Tz = time_zone.trim().parse().map_err(|_| {
+        error::validation(format!(
+            "Unknown time zone '{}' (expected an IANA name such as Europe/Warsaw)",
+            time_zone
+        ))
+    })?;
+
+    set_setting(conn, TIME_ZONE, time_zone.name())
+}
+
+/// Zapisuje godzinę, o której zaczyna się nowy dzień
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `hour` - Godzina z zakresu 0-23 (np. 4 - wpisy do 03:59 liczą się do poprzedniego dnia)
+///
+/// # Returns
+/// * `Result<()>` - Sukces lub błąd walidacji
+pub fn set_day_rollover_hour(conn: &Connection, hour: u32) -> Result<()> {
+    if hour > 23 {
+        return Err(error::validation(
+            "Day rollover hour must be between 0 and 23",
+        ));
+    }
+
+    set_setting(conn, DAY_ROLLOVER_HOUR, &hour.to_string())
+}
+
+/// Zwraca dzisiejszy dzień według strefy czasowej i godziny rozpoczęcia dnia użytkownika
+pub fn today(conn: &Connection) -> Result<NaiveDate> {
+    Ok(get_time_settings(conn)?.day_of(Utc::now()))
+}
+
+/// Zwraca poniedziałek bieżącego tygodnia według ustawień użytkownika
+pub fn week_start(conn: &Connection) -> Result<NaiveDate> {
+    let today = today(conn)?;
+
+    Ok(today - Duration::days(i64::from(today.weekday().num_days_from_monday())))
+}
+
+/// Zwraca chwilę rozpoczęcia bieżącego tygodnia (poniedziałek) jako Unix timestamp
+pub fn week_start_timestamp(conn: &Connection) -> Result<i64> {
+    Ok(get_time_settings(conn)?.day_start_timestamp(week_start(conn)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use rusqlite::Connection;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn create_test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+
+        conn.execute(
+            "CREATE TABLE settings (
+                key TEXT PRIMARY KEY NOT NULL,
+                value TEXT NOT NULL,
+                updated_at INTEGER NOT NULL
+            )",
+            [],
+        )
+        .unwrap();
+
+        conn
+    }
+
+    #[test]
+    fn test_set_and_get_setting() {
+        let conn = create_test_db();
+
+        assert_eq!(get_setting(&conn, "missing").unwrap(), None);
+
+        set_setting(&conn, "theme", "dark").unwrap();
+        set_setting(&conn, "theme", "light").unwrap();
+
+        assert_eq!(
+            get_setting(&conn, "theme").unwrap(),
+            Some("light".to_string())
+        );
+    }
+
+    #[test]
+    fn test_cors_origins() {
+        let conn = create_test_db();
+
+        assert!(get_cors_origins(&conn).unwrap().is_empty());
+
+        let saved = set_cors_origins(
+            &conn,
+            vec![
+                "http://localhost:5173/".to_string(),
+                "http://localhost:5173".to_string(),
+            ],
+        )
+        .unwrap();
+        assert_eq!(saved, vec!["http://localhost:5173".to_string()]);
+        assert_eq!(get_cors_origins(&conn).unwrap(), saved);
+
+        assert!(set_cors_origins(&conn, vec!["*".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_api_server_config() {
+        let conn = create_test_db();
+
+        assert_eq!(
+            get_api_server_config(&conn).unwrap(),
+            ApiServerConfig {
+                port: DEFAULT_API_PORT,
+                auto_start: false,
+            }
+        );
+
+        set_api_port(&conn, 4000).unwrap();
+        set_api_auto_start(&conn, true).unwrap();
+        assert_eq!(
+            get_api_server_config(&conn).unwrap(),
+            ApiServerConfig {
+                port: 4000,
+                auto_start: true,
+            }
+        );
+
+        assert!(set_api_port(&conn, 80).is_err());
+    }
+
+    #[test]
+    fn test_trash_retention_days() {
+        let conn = create_test_db();
+
+        assert_eq!(
+            get_trash_retention_days(&conn).unwrap(),
+            DEFAULT_TRASH_RETENTION_DAYS
+        );
+
+        set_trash_retention_days(&conn, 7).unwrap();
+        assert_eq!(get_trash_retention_days(&conn).unwrap(), 7);
+
+        assert!(set_trash_retention_days(&conn, 0).is_err());
+        assert!(set_trash_retention_days(&conn, 366).is_err());
+    }
+
+    #[test]
+    fn test_time_settings_shift_the_day() {
+        let conn = create_test_db();
+
+        assert_eq!(
+            get_time_settings(&conn).unwrap(),
+            TimeSettings {
+                time_zone: DEFAULT_TIME_ZONE.to_string(),
+                day_rollover_hour: 0,
+            }
+        );
+        assert!(set_time_zone(&conn, "Mars/Olympus").is_err());
+        assert!(set_day_rollover_hour(&conn, 24).is_err());
+
+        set_time_zone(&conn, " Europe/Warsaw ").unwrap();
+        let settings = get_time_settings(&conn).unwrap();
+        assert_eq!(settings.time_zone, "Europe/Warsaw");
+
+        // 00:30 w Warszawie (zimą UTC+1) to już kolejny dzień, choć w UTC jeszcze nie
+        let instant = Utc.with_ymd_and_hms(2025, 1, 20, 23, 30, 0).unwrap();
+        assert_eq!(settings.day_of(instant), date(2025, 1, 21));
+
+        set_day_rollover_hour(&conn, 4).unwrap();
+        let settings = get_time_settings(&conn).unwrap();
+        assert_eq!(settings.day_of(instant), date(2025, 1, 20));
+        assert_eq!(
+            settings.day_start_timestamp(date(2025, 1, 21)),
+            Utc.with_ymd_and_hms(2025, 1, 21, 3, 0, 0)
+                .unwrap()
+                .timestamp()
+        );
+        // Latem Warszawa jest w UTC+2
+        assert_eq!(
+            settings.day_start_timestamp(date(2025, 7, 1)),
+            Utc.with_ymd_and_hms(2025, 7, 1, 2, 0, 0)
+                .unwrap()
+                .timestamp()
+        );
+    }
+}
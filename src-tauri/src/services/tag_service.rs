@@ -0,0 +1,211 @@
+use anyhow::Result;
+use rusqlite::{params_from_iter, Connection};
+use std::collections::HashMap;
+
+use crate::models::{Tag, Task};
+use crate::services::error;
+
+/// Maksymalna długość nazwy tagu
+const MAX_TAG_LENGTH: usize = 30;
+/// Maksymalna liczba tagów jednego zadania
+const MAX_TAGS_PER_TASK: usize = 10;
+/// Ile zadań dociągamy naraz jednym zapytaniem (limit parametrów SQLite)
+const TAG_LOOKUP_CHUNK: usize = 500;
+
+/// Normalizuje tagi: usuwa `#` i białe znaki, zamienia na małe litery, usuwa duplikaty
+///
+/// # Returns
+/// * `Result<Vec<String>>` - Posortowane tagi lub błąd walidacji
+pub fn normalize_tags(tags: Vec<String>) -> Result<Vec<String>> {
+    let mut normalized = Vec::new();
+    for tag in tags {
+        let tag = tag.trim().trim_start_matches('#').trim().to_lowercase();
+        if tag.is_empty() {
+            continue;
+        }
+        if tag.chars().count() > MAX_TAG_LENGTH {
+            return Err(error::validation(format!(
+                "Tag '{}' is too long (max {} characters)",
+                tag, MAX_TAG_LENGTH
+            )));
+        }
+        if tag.contains(',') {
+            return Err(error::validation(format!(
+                "Tag '{}' cannot contain commas",
+                tag
+            )));
+        }
+        normalized.push(tag);
+    }
+
+    normalized.sort();
+    normalized.dedup();
+
+    if normalized.len() > MAX_TAGS_PER_TASK {
+        return Err(error::validation(format!(
+            "A task can have at most {} tags",
+            MAX_TAGS_PER_TASK
+        )));
+    }
+
+    Ok(normalized)
+}
+
+/// Zastępuje tagi zadania podanymi (tagi są tworzone w razie potrzeby)
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `task_id` - ID zadania
+/// * `tags` - Znormalizowane tagi (patrz [`normalize_tags`])
+pub fn set_task_tags(conn: &Connection, task_id: i32, tags: &[String]) -> Result<()> {
+    conn.execute("DELETE FROM task_tags WHERE task_id = ?1", [task_id])?;
+
+    let now = chrono::Utc::now().timestamp();
+    for tag in tags {
+        conn.execute(
+            "INSERT OR IGNORE INTO tags (name, created_at) VALUES (?1, ?2)",
+            (tag, now),
+        )?;
+        conn.execute(
+            "INSERT OR IGNORE INTO task_tags (task_id, tag_id)
+             SELECT ?1, id FROM tags WHERE name = ?2",
+            (task_id, tag),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Uzupełnia pole `tags` podanych zadań
+pub fn attach_tags(conn: &Connection, tasks: &mut [Task]) -> Result<()> {
+    let mut tags_by_task: HashMap<i32, Vec<String>> = HashMap::new();
+
+    for chunk in tasks.chunks(TAG_LOOKUP_CHUNK) {
+        let placeholders = vec!["?"; chunk.len()].join(", ");
+        let sql = format!(
+            "SELECT task_tags.task_id, tags.name FROM task_tags
+             JOIN tags ON tags.id = task_tags.tag_id
+             WHERE task_tags.task_id IN ({})
+             ORDER BY tags.name",
+            placeholders
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(params_from_iter(chunk.iter().map(|task| task.id)), |row| {
+            Ok((row.get::<_, i32>(0)?, row.get::<_, String>(1)?))
+        })?;
+
+        for row in rows {
+            let (task_id, name) = row?;
+            tags_by_task.entry(task_id).or_default().push(name);
+        }
+    }
+
+    for task in tasks.iter_mut() {
+        task.tags = tags_by_task.remove(&task.id).unwrap_or_default();
+    }
+
+    Ok(())
+}
+
+/// Pobiera wszystkie tagi używane przez zadania
+///
+/// # Returns
+/// * `Result<Vec<Tag>>` - Tagi posortowane alfabetycznie z liczbą zadań
+pub fn list_tags(conn: &Connection) -> Result<Vec<Tag>> {
+    let mut stmt = conn.prepare(
+        "SELECT tags.id, tags.name, COUNT(task_tags.task_id)
+         FROM tags
+         JOIN task_tags ON task_tags.tag_id = tags.id
+         JOIN tasks ON tasks.id = task_tags.task_id
+         WHERE tasks.deleted_at IS NULL
+         GROUP BY tags.id
+         ORDER BY tags.name",
+    )?;
+
+    let tag_iter = stmt.query_map([], |row| {
+        Ok(Tag {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            task_count: row.get(2)?,
+        })
+    })?;
+
+    let mut tags = Vec::new();
+    for tag in tag_iter {
+        tags.push(tag?);
+    }
+
+    Ok(tags)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+
+        conn.execute_batch(
+            "CREATE TABLE tasks (
+                id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                title TEXT NOT NULL,
+                deleted_at INTEGER
+            );
+            CREATE TABLE tags (
+                id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                created_at INTEGER NOT NULL
+            );
+            CREATE TABLE task_tags (
+                task_id INTEGER NOT NULL,
+                tag_id INTEGER NOT NULL,
+                PRIMARY KEY (task_id, tag_id)
+            );
+            INSERT INTO tasks (title) VALUES ('Raport'), ('Trening');",
+        )
+        .unwrap();
+
+        conn
+    }
+
+    #[test]
+    fn test_normalize_tags() {
+        let tags = normalize_tags(vec![
+            " #Praca ".to_string(),
+            "praca".to_string(),
+            "".to_string(),
+            "Nauka".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(tags, vec!["nauka", "praca"]);
+
+        assert!(normalize_tags(vec!["a".repeat(MAX_TAG_LENGTH + 1)]).is_err());
+        assert!(normalize_tags(vec!["a,b".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_set_and_attach_tags() {
+        let conn = create_test_db();
+
+        set_task_tags(&conn, 1, &["praca".to_string(), "pilne".to_string()]).unwrap();
+        set_task_tags(&conn, 2, &["sport".to_string(), "praca".to_string()]).unwrap();
+        // Ponowne ustawienie zastępuje poprzednie tagi
+        set_task_tags(&conn, 2, &["sport".to_string()]).unwrap();
+
+        let mut tasks = vec![
+            Task::new("Raport".to_string()),
+            Task::new("Trening".to_string()),
+        ];
+        tasks[0].id = 1;
+        tasks[1].id = 2;
+        attach_tags(&conn, &mut tasks).unwrap();
+
+        assert_eq!(tasks[0].tags, vec!["pilne", "praca"]);
+        assert_eq!(tasks[1].tags, vec!["sport"]);
+
+        let tags = list_tags(&conn).unwrap();
+        let names: Vec<&str> = tags.iter().map(|tag| tag.name.as_str()).collect();
+        assert_eq!(names, vec!["pilne", "praca", "sport"]);
+        assert!(tags.iter().all(|tag| tag.task_count == 1));
+    }
+}
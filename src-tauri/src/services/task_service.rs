@@ -1,36 +1,298 @@
-use crate::models::{CreateTaskRequest, Task};
-use crate::services::character_service;
+use crate::models::{
+    Attribute, CreateTaskRequest, DomainEvent, Page, Task, TaskNode, TaskPriority, TaskQuery,
+    TaskSortField, TimeSettings, UpdateTaskRequest,
+};
+use crate::services::pagination::{self, Filters, PageRequest};
+use crate::services::recurrence::RecurrenceRule;
+use crate::services::{
+    character_service, error, events, goal_service, inbox_service, quick_add, settings_service,
+    tag_service,
+};
 use anyhow::Result;
-use rusqlite::Connection;
+use chrono::{Duration, NaiveDate, Utc};
+use rusqlite::types::Value;
+use rusqlite::{params_from_iter, Connection, Row};
+use std::collections::{HashMap, HashSet};
 
-/// Dodaje nowe zadanie do bazy danych
+/// Kolumny tabeli tasks odczytywane przez [`row_to_task`]
 ///
-/// # Arguments
-/// * `conn` - Referencja do połączenia z bazą danych
-/// * `request` - Dane nowego zadania
+/// Reguła powtarzania jest dociągana tylko dla aktywnej serii.
+const TASK_COLUMNS: &str =
+    "id, title, notes, completed, priority, attribute, due_at, scheduled_for, recurrence_id,
+     (SELECT rule FROM task_recurrences
+      WHERE task_recurrences.id = tasks.recurrence_id AND task_recurrences.active = 1) AS recurrence,
+     project_id, parent_id, goal_id, inbox, estimate_minutes,
+     (SELECT COUNT(*) FROM tasks AS subtasks
+      WHERE subtasks.parent_id = tasks.id AND subtasks.deleted_at IS NULL) AS subtask_count,
+     created_at, updated_at";
+
+/// Maksymalna długość notatek zadania
+const MAX_NOTES_LENGTH: usize = 10_000;
+
+/// Najdłuższy szacowany czas zadania (jeden dzień)
+const MAX_ESTIMATE_MINUTES: i32 = 24 * 60;
+
+/// ID zadania (parametr `?`) i wszystkich jego podzadań, do użycia w `id IN (...)`
+pub(crate) const SUBTREE_IDS: &str = "WITH RECURSIVE subtree(id) AS (
+         SELECT ?
+         UNION ALL
+         SELECT tasks.id FROM tasks JOIN subtree ON tasks.parent_id = subtree.id
+     )
+     SELECT id FROM subtree";
+
+/// Konwertuje wiersz bazy danych na obiekt Task (bez tagów - patrz `tag_service::attach_tags`)
+fn row_to_task(row: &Row) -> Result<Task, rusqlite::Error> {
+    let priority: String = row.get("priority")?;
+    let attribute: Option<String> = row.get("attribute")?;
+
+    Ok(Task {
+        id: row.get("id")?,
+        title: row.get("title")?,
+        notes: row.get("notes")?,
+        completed: row.get("completed")?,
+        priority: TaskPriority::from_db(&priority),
+        attribute: attribute.as_deref().and_then(Attribute::from_name),
+        tags: Vec::new(),
+        due_at: row.get("due_at")?,
+        scheduled_for: row.get("scheduled_for")?,
+        recurrence_id: row.get("recurrence_id")?,
+        recurrence: row.get("recurrence")?,
+        project_id: row.get("project_id")?,
+        parent_id: row.get("parent_id")?,
+        goal_id: row.get("goal_id")?,
+        inbox: row.get("inbox")?,
+        estimate_minutes: row.get("estimate_minutes")?,
+        subtask_count: row.get("subtask_count")?,
+        created_at: row.get("created_at")?,
+        updated_at: row.get("updated_at")?,
+    })
+}
+
+/// Sprawdza długość tytułu zadania
+fn validate_title(title: &str) -> Result<()> {
+    if title.trim().is_empty() {
+        return Err(error::validation("Task title cannot be empty"));
+    }
+    if title.len() > 100 {
+        return Err(error::validation(
+            "Task title is too long (max 100 characters)",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Sprawdza format zaplanowanego dnia (YYYY-MM-DD) i usuwa białe znaki
+fn validate_scheduled_for(scheduled_for: Option<String>) -> Result<Option<String>> {
+    let Some(date) = scheduled_for.map(|date| date.trim().to_string()) else {
+        return Ok(None);
+    };
+    if NaiveDate::parse_from_str(&date, "%Y-%m-%d").is_err() {
+        return Err(error::validation(format!(
+            "Invalid scheduled date '{}' (expected YYYY-MM-DD)",
+            date
+        )));
+    }
+
+    Ok(Some(date))
+}
+
+/// Sprawdza długość notatek i zamienia puste notatki na brak notatek
+fn validate_notes(notes: Option<String>) -> Result<Option<String>> {
+    let Some(notes) = notes.filter(|notes| !notes.trim().is_empty()) else {
+        return Ok(None);
+    };
+    if notes.chars().count() > MAX_NOTES_LENGTH {
+        return Err(error::validation(format!(
+            "Task notes are too long (max {} characters)",
+            MAX_NOTES_LENGTH
+        )));
+    }
+
+    Ok(Some(notes))
+}
+
+/// Sprawdza, czy szacowany czas mieści się w jednym dniu
+fn validate_estimate(estimate_minutes: Option<i32>) -> Result<()> {
+    if let Some(minutes) = estimate_minutes {
+        if !(1..=MAX_ESTIMATE_MINUTES).contains(&minutes) {
+            return Err(error::validation(format!(
+                "Task estimate must be between 1 and {} minutes",
+                MAX_ESTIMATE_MINUTES
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Sprawdza czy `candidate_id` to zadanie `task_id` lub jedno z jego podzadań
+fn is_in_subtree(conn: &Connection, task_id: i32, candidate_id: i32) -> Result<bool> {
+    let sql = format!("SELECT COUNT(*) FROM ({}) WHERE id = ?", SUBTREE_IDS);
+    let count: i32 = conn.query_row(&sql, (task_id, candidate_id), |row| row.get(0))?;
+
+    Ok(count > 0)
+}
+
+/// Ustala projekt zadania na podstawie żądanego projektu i rodzica
+///
+/// Podzadanie zawsze należy do projektu rodzica; rodzic nie może być
+/// samym zadaniem ani jego podzadaniem.
 ///
 /// # Returns
-/// * `Result<Task>` - Nowo utworzone zadanie lub błąd
-pub fn add_task(conn: &Connection, request: CreateTaskRequest) -> Result<Task> {
-    // Validate title length
-    if request.title.trim().is_empty() {
-        return Err(anyhow::anyhow!("Task title cannot be empty"));
-    }
-    if request.title.len() > 100 {
-        return Err(anyhow::anyhow!(
-            "Task title is too long (max 100 characters)"
-        ));
+/// * `Result<Option<i32>>` - Projekt, do którego trafi zadanie, lub błąd walidacji
+fn resolve_project(
+    conn: &Connection,
+    task_id: Option<i32>,
+    project_id: Option<i32>,
+    parent_id: Option<i32>,
+) -> Result<Option<i32>> {
+    if let Some(parent_id) = parent_id {
+        let parent = get_task_by_id(conn, parent_id)
+            .map_err(|_| error::validation(format!("Parent task {} not found", parent_id)))?;
+        if let Some(task_id) = task_id {
+            if is_in_subtree(conn, task_id, parent_id)? {
+                return Err(error::validation(
+                    "A task cannot be moved under itself or one of its subtasks",
+                ));
+            }
+        }
+        if project_id.is_some() && project_id != parent.project_id {
+            return Err(error::validation(
+                "A subtask must belong to its parent's project",
+            ));
+        }
+        return Ok(parent.project_id);
     }
 
-    let mut task = Task::new(request.title);
+    if let Some(project_id) = project_id {
+        if !project_exists(conn, project_id)? {
+            return Err(error::validation(format!(
+                "Project {} not found",
+                project_id
+            )));
+        }
+    }
+
+    Ok(project_id)
+}
+
+fn project_exists(conn: &Connection, project_id: i32) -> Result<bool> {
+    let count: i32 = conn.query_row(
+        "SELECT COUNT(*) FROM projects WHERE id = ?1",
+        [project_id],
+        |row| row.get(0),
+    )?;
+
+    Ok(count > 0)
+}
+
+/// Zwraca błąd walidacji, gdy cel nie istnieje
+fn ensure_goal_exists(conn: &Connection, goal_id: i32) -> Result<()> {
+    let count: i32 = conn.query_row(
+        "SELECT COUNT(*) FROM goals WHERE id = ?1",
+        [goal_id],
+        |row| row.get(0),
+    )?;
+    if count == 0 {
+        return Err(error::validation(format!("Goal {} not found", goal_id)));
+    }
 
-    let sql =
-        "INSERT INTO tasks (title, completed, created_at, updated_at) VALUES (?1, ?2, ?3, ?4)";
+    Ok(())
+}
+
+/// Sprawdza regułę powtarzania (patrz [`RecurrenceRule`])
+fn parse_recurrence(rule: &str) -> Result<RecurrenceRule> {
+    rule.parse()
+}
+
+/// Dzień bieżącego wystąpienia, od którego liczone jest kolejne
+///
+/// Terminy i data utworzenia są zamieniane na dzień w strefie czasowej użytkownika.
+fn occurrence_anchor(task: &Task, time: &TimeSettings) -> NaiveDate {
+    task.scheduled_for
+        .as_deref()
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+        .or_else(|| {
+            task.due_at
+                .and_then(|due_at| chrono::DateTime::from_timestamp(due_at, 0))
+                .map(|due_at| time.day_of(due_at))
+        })
+        .or_else(|| chrono::DateTime::from_timestamp(task.created_at, 0).map(|c| time.day_of(c)))
+        .unwrap_or_else(|| time.day_of(Utc::now()))
+}
+
+/// Ustawia, zmienia lub (dla `None`) zatrzymuje serię powtarzania zadania
+///
+/// Zadanie powtarzalne bez żadnego terminu dostaje dzisiejszy dzień jako zaplanowany,
+/// żeby było od czego liczyć kolejne wystąpienia. Zmienia tylko strukturę `task`
+/// i tabelę serii - zapis samego zadania należy do wywołującego.
+fn set_task_recurrence(conn: &Connection, task: &mut Task, rule: Option<String>) -> Result<()> {
+    let now = Utc::now().timestamp();
+
+    let Some(rule) = rule else {
+        if let Some(recurrence_id) = task.recurrence_id {
+            conn.execute(
+                "UPDATE task_recurrences SET active = 0, updated_at = ?1 WHERE id = ?2",
+                (now, recurrence_id),
+            )?;
+        }
+        task.recurrence = None;
+        return Ok(());
+    };
+
+    let rule = parse_recurrence(&rule)?.to_string();
+    if task.scheduled_for.is_none() && task.due_at.is_none() {
+        task.scheduled_for = Some(
+            settings_service::today(conn)?
+                .format("%Y-%m-%d")
+                .to_string(),
+        );
+    }
+
+    match task.recurrence_id {
+        Some(recurrence_id) => {
+            conn.execute(
+                "UPDATE task_recurrences SET rule = ?1, active = 1, updated_at = ?2 WHERE id = ?3",
+                (&rule, now, recurrence_id),
+            )?;
+        }
+        None => {
+            conn.execute(
+                "INSERT INTO task_recurrences (rule, active, occurrences, created_at, updated_at)
+                 VALUES (?1, 1, 1, ?2, ?2)",
+                (&rule, now),
+            )?;
+            task.recurrence_id = Some(conn.last_insert_rowid() as i32);
+        }
+    }
+    task.recurrence = Some(rule);
+
+    Ok(())
+}
+
+/// Zapisuje nowe zadanie razem z tagami i uzupełnia jego ID
+fn insert_task(conn: &Connection, task: &mut Task) -> Result<()> {
+    let sql = "INSERT INTO tasks (title, notes, completed, priority, attribute, due_at,
+                                  scheduled_for, recurrence_id, project_id, parent_id, goal_id,
+                                  inbox, estimate_minutes, created_at, updated_at)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)";
     conn.execute(
         sql,
         (
             &task.title,
+            &task.notes,
             task.completed,
+            task.priority.as_str(),
+            task.attribute.map(|attribute| attribute.as_str()),
+            task.due_at,
+            &task.scheduled_for,
+            task.recurrence_id,
+            task.project_id,
+            task.parent_id,
+            task.goal_id,
+            task.inbox,
+            task.estimate_minutes,
             task.created_at,
             task.updated_at,
         ),
@@ -39,6 +301,163 @@ pub fn add_task(conn: &Connection, request: CreateTaskRequest) -> Result<Task> {
     // Pobierz ID nowo utworzonego zadania
     task.id = conn.last_insert_rowid() as i32;
 
+    tag_service::set_task_tags(conn, task.id, &task.tags)?;
+
+    Ok(())
+}
+
+/// Dodaje nowe zadanie do bazy danych
+///
+/// Z `quick_add` tytuł jest najpierw parsowany (patrz [`quick_add::parse_text`]).
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `request` - Dane nowego zadania
+///
+/// # Returns
+/// * `Result<Task>` - Nowo utworzone zadanie lub błąd
+pub fn add_task(conn: &Connection, mut request: CreateTaskRequest) -> Result<Task> {
+    if request.quick_add {
+        let parsed = quick_add::parse_text(conn, &request.title)?;
+        request = quick_add::merge(parsed, request);
+    }
+
+    validate_title(&request.title)?;
+    validate_estimate(request.estimate_minutes)?;
+    let notes = validate_notes(request.notes)?;
+    let tags = tag_service::normalize_tags(request.tags)?;
+
+    let scheduled_for = validate_scheduled_for(request.scheduled_for)?;
+    let project_id = resolve_project(conn, None, request.project_id, request.parent_id)?;
+    if let Some(goal_id) = request.goal_id {
+        ensure_goal_exists(conn, goal_id)?;
+    }
+
+    let mut task = Task::new(request.title);
+    task.notes = notes;
+    task.priority = request.priority;
+    task.attribute = request.attribute;
+    task.due_at = request.due_at;
+    task.scheduled_for = scheduled_for;
+    task.tags = tags;
+    task.project_id = project_id;
+    task.parent_id = request.parent_id;
+    task.goal_id = request.goal_id;
+    task.estimate_minutes = request.estimate_minutes;
+    // Zadanie przechwycone bez żadnego kontekstu czeka w skrzynce na przegląd
+    task.inbox = request.inbox.unwrap_or(
+        task.project_id.is_none()
+            && task.parent_id.is_none()
+            && task.goal_id.is_none()
+            && task.due_at.is_none()
+            && task.scheduled_for.is_none()
+            && request.recurrence.is_none(),
+    );
+
+    if request.recurrence.is_some() {
+        set_task_recurrence(conn, &mut task, request.recurrence)?;
+    }
+
+    insert_task(conn, &mut task)?;
+
+    Ok(task)
+}
+
+/// Aktualizuje zadanie: tytuł, priorytet, atrybut, tagi, terminy, powtarzanie, miejsce w drzewie lub cel
+///
+/// Przeniesienie zadania do innego projektu przenosi też wszystkie jego podzadania.
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `task_id` - ID zadania do aktualizacji
+/// * `request` - Zmieniane pola (pominięte pozostają bez zmian)
+///
+/// # Returns
+/// * `Result<Task>` - Zaktualizowane zadanie lub błąd
+pub fn update_task(conn: &Connection, task_id: i32, request: UpdateTaskRequest) -> Result<Task> {
+    let mut task = get_task_by_id(conn, task_id)?;
+
+    if let Some(title) = request.title {
+        validate_title(&title)?;
+        task.title = title;
+    }
+    if let Some(notes) = request.notes {
+        task.notes = validate_notes(notes)?;
+    }
+    if let Some(priority) = request.priority {
+        task.priority = priority;
+    }
+    if let Some(attribute) = request.attribute {
+        task.attribute = attribute;
+    }
+    if let Some(due_at) = request.due_at {
+        task.due_at = due_at;
+    }
+    if let Some(scheduled_for) = request.scheduled_for {
+        task.scheduled_for = validate_scheduled_for(scheduled_for)?;
+    }
+    if let Some(tags) = request.tags {
+        task.tags = tag_service::normalize_tags(tags)?;
+        tag_service::set_task_tags(conn, task.id, &task.tags)?;
+    }
+    if let Some(recurrence) = request.recurrence {
+        set_task_recurrence(conn, &mut task, recurrence)?;
+    }
+    if request.project_id.is_some() || request.parent_id.is_some() {
+        let parent_id = request.parent_id.unwrap_or(task.parent_id);
+        // Bez jawnego projektu zadanie przeniesione pod nowego rodzica przejmuje jego projekt
+        let project_id = match request.project_id {
+            Some(project_id) => project_id,
+            None if request.parent_id.flatten().is_some() => None,
+            None => task.project_id,
+        };
+        task.project_id = resolve_project(conn, Some(task.id), project_id, parent_id)?;
+        task.parent_id = parent_id;
+
+        let sql = format!(
+            "UPDATE tasks SET project_id = ? WHERE id IN ({}) AND id != ?",
+            SUBTREE_IDS
+        );
+        conn.execute(&sql, (task.project_id, task.id, task.id))?;
+    }
+    if let Some(goal_id) = request.goal_id {
+        if let Some(goal_id) = goal_id {
+            ensure_goal_exists(conn, goal_id)?;
+        }
+        task.goal_id = goal_id;
+    }
+    if let Some(inbox) = request.inbox {
+        task.inbox = inbox;
+    }
+    if let Some(estimate_minutes) = request.estimate_minutes {
+        validate_estimate(estimate_minutes)?;
+        task.estimate_minutes = estimate_minutes;
+    }
+    task.updated_at = chrono::Utc::now().timestamp();
+
+    conn.execute(
+        "UPDATE tasks SET title = ?1, notes = ?2, priority = ?3, attribute = ?4, due_at = ?5,
+                scheduled_for = ?6, recurrence_id = ?7, project_id = ?8, parent_id = ?9,
+                goal_id = ?10, inbox = ?11, estimate_minutes = ?12, updated_at = ?13
+         WHERE id = ?14",
+        (
+            &task.title,
+            &task.notes,
+            task.priority.as_str(),
+            task.attribute.map(|attribute| attribute.as_str()),
+            task.due_at,
+            &task.scheduled_for,
+            task.recurrence_id,
+            task.project_id,
+            task.parent_id,
+            task.goal_id,
+            task.inbox,
+            task.estimate_minutes,
+            task.updated_at,
+            task.id,
+        ),
+    )?;
+
     Ok(task)
 }
 
@@ -50,30 +469,340 @@ pub fn add_task(conn: &Connection, request: CreateTaskRequest) -> Result<Task> {
 /// # Returns
 /// * `Result<Vec<Task>>` - Lista wszystkich zadań lub błąd
 pub fn get_all_tasks(conn: &Connection) -> Result<Vec<Task>> {
-    let sql =
-        "SELECT id, title, completed, created_at, updated_at FROM tasks ORDER BY created_at DESC";
-    let mut stmt = conn.prepare(sql)?;
-
-    let task_iter = stmt.query_map([], |row| {
-        Ok(Task {
-            id: row.get(0)?,
-            title: row.get(1)?,
-            completed: row.get(2)?,
-            created_at: row.get(3)?,
-            updated_at: row.get(4)?,
-        })
-    })?;
+    let sql = format!(
+        "SELECT {} FROM tasks WHERE deleted_at IS NULL ORDER BY created_at DESC",
+        TASK_COLUMNS
+    );
+    let mut stmt = conn.prepare(&sql)?;
+
+    let task_iter = stmt.query_map([], row_to_task)?;
+
+    let mut tasks = Vec::new();
+    for task in task_iter {
+        tasks.push(task?);
+    }
+    tag_service::attach_tags(conn, &mut tasks)?;
+
+    Ok(tasks)
+}
+
+/// Pobiera zadania spełniające warunek (bez zadań z kosza), razem z tagami
+fn fetch_tasks(
+    conn: &Connection,
+    condition: &str,
+    params: Vec<Value>,
+    order_by: &str,
+) -> Result<Vec<Task>> {
+    let sql = format!(
+        "SELECT {} FROM tasks WHERE deleted_at IS NULL AND ({}) ORDER BY {}",
+        TASK_COLUMNS, condition, order_by
+    );
+    let mut stmt = conn.prepare(&sql)?;
+
+    let task_iter = stmt.query_map(params_from_iter(params), row_to_task)?;
 
     let mut tasks = Vec::new();
     for task in task_iter {
         tasks.push(task?);
     }
+    tag_service::attach_tags(conn, &mut tasks)?;
 
     Ok(tasks)
 }
 
+/// Zadania zaplanowane lub z terminem w przedziale dni `[from, to)`
+///
+/// Granice dni wyznaczają strefa czasowa i godzina rozpoczęcia dnia z ustawień.
+fn tasks_between(conn: &Connection, from: NaiveDate, to: NaiveDate) -> Result<Vec<Task>> {
+    let time = settings_service::get_time_settings(conn)?;
+
+    fetch_tasks(
+        conn,
+        "(scheduled_for >= ? AND scheduled_for < ?) OR (due_at >= ? AND due_at < ?)",
+        vec![
+            Value::Text(from.format("%Y-%m-%d").to_string()),
+            Value::Text(to.format("%Y-%m-%d").to_string()),
+            Value::Integer(time.day_start_timestamp(from)),
+            Value::Integer(time.day_start_timestamp(to)),
+        ],
+        "completed, COALESCE(scheduled_for, date(due_at, 'unixepoch')), due_at, created_at",
+    )
+}
+
+/// Pobiera zadania na dziś - zaplanowane na dziś lub z dzisiejszym terminem
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+///
+/// # Returns
+/// * `Result<Vec<Task>>` - Zadania na dziś (nieukończone pierwsze) lub błąd
+pub fn get_tasks_for_today(conn: &Connection) -> Result<Vec<Task>> {
+    let today = settings_service::today(conn)?;
+    tasks_between(conn, today, today + Duration::days(1))
+}
+
+/// Pobiera zadania na bieżący tydzień (poniedziałek - niedziela, według ustawień czasu)
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+///
+/// # Returns
+/// * `Result<Vec<Task>>` - Zadania zaplanowane lub z terminem w tym tygodniu lub błąd
+pub fn get_tasks_for_week(conn: &Connection) -> Result<Vec<Task>> {
+    let monday = settings_service::week_start(conn)?;
+    tasks_between(conn, monday, monday + Duration::days(7))
+}
+
+/// Pobiera nieukończone zadania, których termin już minął
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+///
+/// # Returns
+/// * `Result<Vec<Task>>` - Zaległe zadania (najstarszy termin pierwszy) lub błąd
+pub fn get_overdue_tasks(conn: &Connection) -> Result<Vec<Task>> {
+    fetch_tasks(
+        conn,
+        "completed = 0 AND due_at < ?",
+        vec![Value::Integer(Utc::now().timestamp())],
+        "due_at, created_at",
+    )
+}
+
+/// Pobiera nieukończone zadania bez terminu i bez zaplanowanego dnia
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+///
+/// # Returns
+/// * `Result<Vec<Task>>` - Niezaplanowane zadania (najnowsze pierwsze) lub błąd
+pub fn get_unscheduled_tasks(conn: &Connection) -> Result<Vec<Task>> {
+    fetch_tasks(
+        conn,
+        "completed = 0 AND due_at IS NULL AND scheduled_for IS NULL",
+        Vec::new(),
+        "created_at DESC",
+    )
+}
+
+/// Pobiera nieukończone zadania czekające w skrzynce na przegląd
+///
+/// # Returns
+/// * `Result<Vec<Task>>` - Zadania ze skrzynki (najstarsze pierwsze) lub błąd
+pub fn get_inbox_tasks(conn: &Connection) -> Result<Vec<Task>> {
+    fetch_tasks(
+        conn,
+        "completed = 0 AND inbox = 1",
+        Vec::new(),
+        "created_at ASC, id ASC",
+    )
+}
+
+/// Średni postęp węzłów (0-100, zaokrąglony do 0.1); 0 dla pustej listy
+pub fn average_progress(nodes: &[TaskNode]) -> f64 {
+    if nodes.is_empty() {
+        return 0.0;
+    }
+
+    let total: f64 = nodes.iter().map(|node| node.progress).sum();
+    (total / nodes.len() as f64 * 10.0).round() / 10.0
+}
+
+/// Liczy wszystkie i ukończone zadania w drzewach (razem z podzadaniami)
+pub fn count_tree(nodes: &[TaskNode]) -> (i32, i32) {
+    nodes.iter().fold((0, 0), |(total, completed), node| {
+        let (sub_total, sub_completed) = count_tree(&node.subtasks);
+        (
+            total + 1 + sub_total,
+            completed + i32::from(node.task.completed) + sub_completed,
+        )
+    })
+}
+
+fn build_node(task: Task, children: &mut HashMap<i32, Vec<Task>>) -> TaskNode {
+    let subtasks: Vec<TaskNode> = children
+        .remove(&task.id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|child| build_node(child, children))
+        .collect();
+    let progress = if task.completed {
+        100.0
+    } else {
+        average_progress(&subtasks)
+    };
+
+    TaskNode {
+        task,
+        progress,
+        subtasks,
+    }
+}
+
+/// Układa zadania w drzewa; korzeniami są zadania, których rodzica nie ma na liście
+fn build_forest(tasks: Vec<Task>) -> Vec<TaskNode> {
+    let ids: HashSet<i32> = tasks.iter().map(|task| task.id).collect();
+    let mut children: HashMap<i32, Vec<Task>> = HashMap::new();
+    let mut roots = Vec::new();
+
+    for task in tasks {
+        match task.parent_id.filter(|parent_id| ids.contains(parent_id)) {
+            Some(parent_id) => children.entry(parent_id).or_default().push(task),
+            None => roots.push(task),
+        }
+    }
+
+    roots
+        .into_iter()
+        .map(|task| build_node(task, &mut children))
+        .collect()
+}
+
+/// Pobiera zadanie wraz z drzewem podzadań i postępem
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `task_id` - ID zadania
+///
+/// # Returns
+/// * `Result<TaskNode>` - Zadanie z podzadaniami (w kolejności utworzenia) lub błąd not found
+pub fn get_task_tree(conn: &Connection, task_id: i32) -> Result<TaskNode> {
+    // Sprawdź, że zadanie istnieje
+    get_task_by_id(conn, task_id)?;
+
+    let tasks = fetch_tasks(
+        conn,
+        &format!("id IN ({})", SUBTREE_IDS),
+        vec![Value::Integer(task_id.into())],
+        "created_at, id",
+    )?;
+
+    build_forest(tasks)
+        .into_iter()
+        .find(|node| node.task.id == task_id)
+        .ok_or_else(|| error::not_found(format!("Task with id {} not found", task_id)))
+}
+
+/// Pobiera zadania projektu jako drzewa (zadania najwyższego poziomu z podzadaniami)
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `project_id` - ID projektu
+///
+/// # Returns
+/// * `Result<Vec<TaskNode>>` - Drzewa zadań w kolejności utworzenia lub błąd not found
+pub fn get_project_tree(conn: &Connection, project_id: i32) -> Result<Vec<TaskNode>> {
+    if !project_exists(conn, project_id)? {
+        return Err(error::not_found(format!(
+            "Project with id {} not found",
+            project_id
+        )));
+    }
+
+    let tasks = fetch_tasks(
+        conn,
+        "project_id = ?",
+        vec![Value::Integer(project_id.into())],
+        "created_at, id",
+    )?;
+
+    Ok(build_forest(tasks))
+}
+
+/// Pobiera stronę zadań spełniających filtry
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `query` - Filtry, sortowanie, limit i kursor
+///
+/// # Returns
+/// * `Result<Page<Task>>` - Strona zadań z kursorem następnej strony lub błąd
+pub fn query_tasks(conn: &Connection, query: TaskQuery) -> Result<Page<Task>> {
+    let mut filters = Filters::default();
+    filters.add_condition("deleted_at IS NULL");
+    if let Some(project_id) = query.project_id {
+        filters.add("project_id = ?", project_id);
+    }
+    if let Some(parent_id) = query.parent_id {
+        filters.add("parent_id = ?", parent_id);
+    }
+    if let Some(goal_id) = query.goal_id {
+        filters.add("goal_id = ?", goal_id);
+    }
+    if let Some(inbox) = query.inbox {
+        filters.add("inbox = ?", inbox);
+    }
+    if let Some(tag) = query
+        .tag
+        .as_deref()
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+    {
+        filters.add(
+            "id IN (SELECT task_tags.task_id FROM task_tags
+                    JOIN tags ON tags.id = task_tags.tag_id WHERE tags.name = ?)",
+            tag.trim_start_matches('#').to_string(),
+        );
+    }
+    if let Some(priority) = query.priority {
+        filters.add("priority = ?", priority.as_str().to_string());
+    }
+    if let Some(completed) = query.completed {
+        filters.add("completed = ?", completed);
+    }
+    if let Some(after) = query.created_after {
+        filters.add("created_at >= ?", after);
+    }
+    if let Some(before) = query.created_before {
+        filters.add("created_at < ?", before);
+    }
+    if let Some(after) = query.updated_after {
+        filters.add("updated_at >= ?", after);
+    }
+    if let Some(before) = query.updated_before {
+        filters.add("updated_at < ?", before);
+    }
+    filters.add_search("title", query.search.as_deref());
+
+    let sort = query.sort.unwrap_or_default();
+    let (sort_name, sort_column) = match sort {
+        TaskSortField::CreatedAt => ("created_at", "created_at"),
+        TaskSortField::UpdatedAt => ("updated_at", "updated_at"),
+        TaskSortField::Title => ("title", "title"),
+    };
+
+    let mut page = pagination::fetch_page(
+        conn,
+        &format!("SELECT {} FROM tasks", TASK_COLUMNS),
+        filters,
+        PageRequest {
+            sort_name,
+            sort_column,
+            order: query.order.unwrap_or_default(),
+            limit: query.limit,
+            cursor: query.cursor.as_deref(),
+        },
+        row_to_task,
+        |task| {
+            let value = match sort {
+                TaskSortField::CreatedAt => Value::Integer(task.created_at),
+                TaskSortField::UpdatedAt => Value::Integer(task.updated_at),
+                TaskSortField::Title => Value::Text(task.title.clone()),
+            };
+            (value, task.id)
+        },
+    )?;
+    tag_service::attach_tags(conn, &mut page.items)?;
+
+    Ok(page)
+}
+
 /// Przełącza status ukończenia zadania
 ///
+/// Zmiana statusu, nagroda i następstwa ukończenia (kolejne wystąpienie, status celu,
+/// skrzynka, zadanie nadrzędne) są zapisywane w jednej transakcji - błąd któregokolwiek
+/// kroku cofa całość, więc zadanie nie zostaje ukończone bez kolejnego wystąpienia.
+///
 /// # Arguments
 /// * `conn` - Referencja do połączenia z bazą danych
 /// * `task_id` - ID zadania do przełączenia
@@ -81,19 +810,17 @@ pub fn get_all_tasks(conn: &Connection) -> Result<Vec<Task>> {
 /// # Returns
 /// * `Result<Task>` - Zaktualizowane zadanie lub błąd
 pub fn toggle_task_status(conn: &Connection, task_id: i32) -> Result<Task> {
+    let tx = conn.unchecked_transaction()?;
+    let task = toggle_task_in_transaction(&tx, task_id)?;
+    tx.commit()?;
+
+    Ok(task)
+}
+
+/// Przełącza status zadania w ramach transakcji otwartej przez [`toggle_task_status`]
+fn toggle_task_in_transaction(conn: &Connection, task_id: i32) -> Result<Task> {
     // Pobierz aktualne zadanie
-    let sql = "SELECT id, title, completed, created_at, updated_at FROM tasks WHERE id = ?1";
-    let mut stmt = conn.prepare(sql)?;
-
-    let mut task = stmt.query_row([task_id], |row| {
-        Ok(Task {
-            id: row.get(0)?,
-            title: row.get(1)?,
-            completed: row.get(2)?,
-            created_at: row.get(3)?,
-            updated_at: row.get(4)?,
-        })
-    })?;
+    let mut task = get_task_by_id(conn, task_id)?;
 
     let was_completed = task.completed;
 
@@ -104,32 +831,170 @@ pub fn toggle_task_status(conn: &Connection, task_id: i32) -> Result<Task> {
     let update_sql = "UPDATE tasks SET completed = ?1, updated_at = ?2 WHERE id = ?3";
     conn.execute(update_sql, (task.completed, task.updated_at, task.id))?;
 
-    // Jeśli zadanie zostało ukończone (przeszło z false na true), dodaj EXP
+    // Jeśli zadanie zostało ukończone (przeszło z false na true), dodaj EXP (raz na zadanie)
     if !was_completed && task.completed {
-        // TODO: W przyszłości można dodać logikę wykrywania czy zadanie jest powiązane z celem
-        let is_goal_related = false;
+        events::publish(DomainEvent::TaskCompleted {
+            task_id: task.id,
+            title: task.title.clone(),
+        });
+
+        // Zadania powiązane z celem dają więcej EXP
+        let is_goal_related = task.goal_id.is_some();
 
         // Przetwórz ukończenie zadania i dodaj EXP
-        match character_service::process_task_completion(conn, &task.title, is_goal_related) {
-            Ok((_, level_up)) => {
+        match character_service::process_task_completion(conn, &task, is_goal_related) {
+            Ok(Some((_, level_up))) => {
                 if level_up {
-                    println!(
+                    eprintln!(
                         "Level up! Task '{}' caused character to level up!",
                         task.title
                     );
                 }
             }
+            // Zadanie było już nagrodzone (np. odznaczone i zaznaczone ponownie)
+            Ok(None) => {}
             Err(e) => {
                 // Loguj błąd ale nie przerywaj operacji - zadanie zostało już zaktualizowane
                 eprintln!("Failed to process task completion for EXP: {}", e);
             }
         }
+
+        spawn_next_occurrence(conn, &task, settings_service::today(conn)?)?;
+
+        if let Some(goal_id) = task.goal_id {
+            goal_service::refresh_goal_status(conn, goal_id)?;
+        }
+
+        if task.inbox {
+            inbox_service::record_inbox_zero(conn)?;
+        }
+
+        if let Some(parent_id) = task.parent_id {
+            complete_parent_if_done(conn, parent_id)?;
+        }
+    } else if was_completed && !task.completed {
+        // Odznaczenie zadania cofa przyznaną za nie nagrodę
+        if let Err(e) = character_service::revoke_task_completion(conn, task.id) {
+            eprintln!("Failed to revoke task completion EXP: {}", e);
+        }
     }
 
     Ok(task)
 }
 
-/// Usuwa zadanie z bazy danych
+/// Kończy zadanie nadrzędne, gdy ukończono jego ostatnie podzadanie
+///
+/// Działa tylko przy włączonym ustawieniu `settings_service::get_auto_complete_parents`.
+/// Rodzic jest kończony tą samą ścieżką co [`toggle_task_status`] (w tej samej transakcji),
+/// więc dostaje własne EXP, a ukończenie może przejść dalej w górę drzewa.
+fn complete_parent_if_done(conn: &Connection, parent_id: i32) -> Result<()> {
+    if !settings_service::get_auto_complete_parents(conn)? {
+        return Ok(());
+    }
+
+    let (parent_completed, open_subtasks): (bool, i32) = conn.query_row(
+        "SELECT completed,
+                (SELECT COUNT(*) FROM tasks
+                 WHERE parent_id = ?1 AND completed = 0 AND deleted_at IS NULL)
+         FROM tasks WHERE id = ?1",
+        [parent_id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+
+    if !parent_completed && open_subtasks == 0 {
+        toggle_task_in_transaction(conn, parent_id)?;
+    }
+
+    Ok(())
+}
+
+/// Tworzy kolejne wystąpienie zadania powtarzalnego po ukończeniu bieżącego
+///
+/// Nowe zadanie kopiuje tytuł, priorytet, atrybut i tagi, a terminy są przesuwane
+/// o tyle dni, ile dzieli oba wystąpienia. Gdy reguła się wyczerpie (`COUNT`, `UNTIL`),
+/// seria zostaje zatrzymana.
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `task` - Właśnie ukończone wystąpienie
+/// * `today` - Bieżący dzień (kolejne wystąpienie nie wypada przed nim)
+///
+/// # Returns
+/// * `Result<Option<Task>>` - Nowe wystąpienie, `None` gdy zadanie nie należy do aktywnej serii
+fn spawn_next_occurrence(conn: &Connection, task: &Task, today: NaiveDate) -> Result<Option<Task>> {
+    let Some(recurrence_id) = task.recurrence_id else {
+        return Ok(None);
+    };
+
+    let (rule, active, occurrences): (String, bool, u32) = conn.query_row(
+        "SELECT rule, active, occurrences FROM task_recurrences WHERE id = ?1",
+        [recurrence_id],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+    )?;
+    if !active {
+        return Ok(None);
+    }
+
+    // Kolejne wystąpienie już istnieje (np. zadanie odznaczono i ukończono ponownie)
+    let newer_occurrences: i32 = conn.query_row(
+        "SELECT COUNT(*) FROM tasks WHERE recurrence_id = ?1 AND id > ?2",
+        (recurrence_id, task.id),
+        |row| row.get(0),
+    )?;
+    if newer_occurrences > 0 {
+        return Ok(None);
+    }
+
+    let rule = parse_recurrence(&rule)?;
+    let time = settings_service::get_time_settings(conn)?;
+    let anchor = occurrence_anchor(task, &time);
+    let next = match rule.count {
+        Some(count) if occurrences >= count => None,
+        _ => rule.next_occurrence(anchor, today),
+    };
+    let now = Utc::now().timestamp();
+
+    let Some(next) = next else {
+        conn.execute(
+            "UPDATE task_recurrences SET active = 0, updated_at = ?1 WHERE id = ?2",
+            (now, recurrence_id),
+        )?;
+        return Ok(None);
+    };
+
+    // Termin zachowuje godzinę czasu lokalnego także po zmianie czasu letniego
+    let shift = next - anchor;
+    let mut next_task = Task::new(task.title.clone());
+    next_task.notes = task.notes.clone();
+    next_task.priority = task.priority;
+    next_task.attribute = task.attribute;
+    next_task.tags = task.tags.clone();
+    next_task.due_at = task
+        .due_at
+        .and_then(|due_at| chrono::DateTime::from_timestamp(due_at, 0))
+        .map(|due_at| time.local_timestamp(time.local_time(due_at) + shift));
+    next_task.scheduled_for = (task.scheduled_for.is_some() || task.due_at.is_none())
+        .then(|| next.format("%Y-%m-%d").to_string());
+    next_task.project_id = task.project_id;
+    next_task.parent_id = task.parent_id;
+    next_task.goal_id = task.goal_id;
+    next_task.estimate_minutes = task.estimate_minutes;
+    next_task.recurrence_id = Some(recurrence_id);
+    next_task.recurrence = Some(rule.to_string());
+
+    insert_task(conn, &mut next_task)?;
+    conn.execute(
+        "UPDATE task_recurrences SET occurrences = occurrences + 1, updated_at = ?1 WHERE id = ?2",
+        (now, recurrence_id),
+    )?;
+
+    Ok(Some(next_task))
+}
+
+/// Przenosi zadanie razem z podzadaniami do kosza
+///
+/// Zadanie można przywrócić lub trwale usunąć przez `trash_service`; po okresie
+/// przechowywania jest usuwane automatycznie.
 ///
 /// # Arguments
 /// * `conn` - Referencja do połączenia z bazą danych
@@ -138,17 +1003,24 @@ pub fn toggle_task_status(conn: &Connection, task_id: i32) -> Result<Task> {
 /// # Returns
 /// * `Result<()>` - Sukces lub błąd
 pub fn delete_task(conn: &Connection, task_id: i32) -> Result<()> {
-    let sql = "DELETE FROM tasks WHERE id = ?1";
-    let rows_affected = conn.execute(sql, [task_id])?;
+    let task = get_task_by_id(conn, task_id)?;
 
-    if rows_affected == 0 {
-        return Err(anyhow::anyhow!("Task with id {} not found", task_id));
+    // Podzadania dostają ten sam znacznik, żeby wróciły razem z zadaniem
+    let sql = format!(
+        "UPDATE tasks SET deleted_at = ? WHERE id IN ({}) AND deleted_at IS NULL",
+        SUBTREE_IDS
+    );
+    conn.execute(&sql, (Utc::now().timestamp(), task_id))?;
+
+    // Usunięcie zadania ze skrzynki to też jego przegląd
+    if task.inbox && !task.completed {
+        inbox_service::record_inbox_zero(conn)?;
     }
 
     Ok(())
 }
 
-/// Pobiera zadanie po ID
+/// Pobiera zadanie po ID (zadania z kosza są traktowane jak nieistniejące)
 ///
 /// # Arguments
 /// * `conn` - Referencja do połączenia z bazą danych
@@ -156,20 +1028,20 @@ pub fn delete_task(conn: &Connection, task_id: i32) -> Result<()> {
 ///
 /// # Returns
 /// * `Result<Task>` - Zadanie lub błąd
-#[cfg(test)]
 pub fn get_task_by_id(conn: &Connection, task_id: i32) -> Result<Task> {
-    let sql = "SELECT id, title, completed, created_at, updated_at FROM tasks WHERE id = ?1";
-    let mut stmt = conn.prepare(sql)?;
-
-    let task = stmt.query_row([task_id], |row| {
-        Ok(Task {
-            id: row.get(0)?,
-            title: row.get(1)?,
-            completed: row.get(2)?,
-            created_at: row.get(3)?,
-            updated_at: row.get(4)?,
-        })
-    })?;
+    let sql = format!(
+        "SELECT {} FROM tasks WHERE id = ?1 AND deleted_at IS NULL",
+        TASK_COLUMNS
+    );
+    let mut task = conn
+        .query_row(&sql, [task_id], row_to_task)
+        .map_err(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => {
+                error::not_found(format!("Task with id {} not found", task_id))
+            }
+            e => e.into(),
+        })?;
+    tag_service::attach_tags(conn, std::slice::from_mut(&mut task))?;
 
     Ok(task)
 }
@@ -177,7 +1049,7 @@ pub fn get_task_by_id(conn: &Connection, task_id: i32) -> Result<Task> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::CreateTaskRequest;
+    use crate::models::{Attribute, CreateTaskRequest, TaskPriority, UpdateTaskRequest};
     use rusqlite::{Connection, Result as SqliteResult};
 
     fn create_test_db() -> SqliteResult<Connection> {
@@ -188,13 +1060,64 @@ mod tests {
             "CREATE TABLE tasks (
                 id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
                 title TEXT NOT NULL,
+                notes TEXT,
                 completed BOOLEAN NOT NULL DEFAULT 0,
                 created_at INTEGER NOT NULL,
-                updated_at INTEGER NOT NULL
+                updated_at INTEGER NOT NULL,
+                priority TEXT NOT NULL DEFAULT 'Medium',
+                attribute TEXT,
+                due_at INTEGER,
+                scheduled_for TEXT,
+                recurrence_id INTEGER,
+                project_id INTEGER,
+                parent_id INTEGER,
+                goal_id INTEGER,
+                inbox BOOLEAN NOT NULL DEFAULT 0,
+                estimate_minutes INTEGER,
+                deleted_at INTEGER
             )",
             [],
         )?;
 
+        // Utwórz tabele tagów
+        conn.execute_batch(
+            "CREATE TABLE tags (
+                id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                created_at INTEGER NOT NULL
+            );
+            CREATE TABLE task_tags (
+                task_id INTEGER NOT NULL,
+                tag_id INTEGER NOT NULL,
+                PRIMARY KEY (task_id, tag_id)
+            );
+            CREATE TABLE task_recurrences (
+                id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                rule TEXT NOT NULL,
+                active BOOLEAN NOT NULL DEFAULT 1,
+                occurrences INTEGER NOT NULL DEFAULT 1,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            );
+            CREATE TABLE projects (
+                id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                color TEXT NOT NULL DEFAULT '#6366f1',
+                attribute TEXT,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            );
+            CREATE TABLE settings (
+                key TEXT PRIMARY KEY NOT NULL,
+                value TEXT NOT NULL,
+                updated_at INTEGER NOT NULL
+            );
+            CREATE TABLE inbox_zero_days (
+                day TEXT PRIMARY KEY NOT NULL,
+                reached_at INTEGER NOT NULL
+            );",
+        )?;
+
         Ok(conn)
     }
 
@@ -203,6 +1126,7 @@ mod tests {
         let conn = create_test_db().unwrap();
         let request = CreateTaskRequest {
             title: "Test task".to_string(),
+            ..Default::default()
         };
 
         let task = add_task(&conn, request).unwrap();
@@ -219,9 +1143,11 @@ mod tests {
         // Dodaj kilka zadań
         let request1 = CreateTaskRequest {
             title: "Task 1".to_string(),
+            ..Default::default()
         };
         let request2 = CreateTaskRequest {
             title: "Task 2".to_string(),
+            ..Default::default()
         };
 
         add_task(&conn, request1).unwrap();
@@ -244,11 +1170,561 @@ mod tests {
         assert!(tasks[0].created_at >= tasks[1].created_at);
     }
 
+    #[test]
+    fn test_query_tasks_filters_and_pages() {
+        let conn = create_test_db().unwrap();
+
+        for title in ["Raport", "Zakupy", "Raport roczny", "Siłownia", "Czytanie"] {
+            add_task(
+                &conn,
+                CreateTaskRequest {
+                    title: title.to_string(),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        }
+        conn.execute("UPDATE tasks SET completed = 1 WHERE title = 'Zakupy'", [])
+            .unwrap();
+
+        let found = query_tasks(
+            &conn,
+            TaskQuery {
+                search: Some("raport".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(found.items.len(), 2);
+        assert!(found.next_cursor.is_none());
+
+        // Stronicowanie po tytule, po 2 nieukończone zadania na stronę
+        let mut titles = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = query_tasks(
+                &conn,
+                TaskQuery {
+                    completed: Some(false),
+                    sort: Some(TaskSortField::Title),
+                    order: Some(crate::models::SortOrder::Asc),
+                    limit: Some(2),
+                    cursor: cursor.clone(),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            titles.extend(page.items.into_iter().map(|task| task.title));
+
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        assert_eq!(
+            titles,
+            vec!["Czytanie", "Raport", "Raport roczny", "Siłownia"]
+        );
+    }
+
+    #[test]
+    fn test_add_task_with_tags_and_priority() {
+        let conn = create_test_db().unwrap();
+
+        let task = add_task(
+            &conn,
+            CreateTaskRequest {
+                title: "Senior review".to_string(),
+                priority: TaskPriority::High,
+                attribute: None,
+                tags: vec![
+                    "#Praca".to_string(),
+                    "praca".to_string(),
+                    "code".to_string(),
+                ],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(task.tags, vec!["code", "praca"]);
+
+        let loaded = get_task_by_id(&conn, task.id).unwrap();
+        assert_eq!(loaded.priority, TaskPriority::High);
+        assert_eq!(loaded.attribute, None);
+        assert_eq!(loaded.tags, vec!["code", "praca"]);
+
+        add_task(
+            &conn,
+            CreateTaskRequest {
+                title: "Zakupy".to_string(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let tagged = query_tasks(
+            &conn,
+            TaskQuery {
+                tag: Some("PRACA".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(tagged.items.len(), 1);
+        assert_eq!(tagged.items[0].title, "Senior review");
+
+        let high = query_tasks(
+            &conn,
+            TaskQuery {
+                priority: Some(TaskPriority::High),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(high.items.len(), 1);
+    }
+
+    #[test]
+    fn test_update_task() {
+        let conn = create_test_db().unwrap();
+        let task = add_task(
+            &conn,
+            CreateTaskRequest {
+                title: "Trening".to_string(),
+                attribute: Some(Attribute::Strength),
+                tags: vec!["sport".to_string()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let updated = update_task(
+            &conn,
+            task.id,
+            UpdateTaskRequest {
+                priority: Some(TaskPriority::Urgent),
+                tags: Some(vec!["zdrowie".to_string()]),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(updated.title, "Trening");
+        assert_eq!(updated.priority, TaskPriority::Urgent);
+        assert_eq!(updated.attribute, Some(Attribute::Strength));
+        assert_eq!(updated.tags, vec!["zdrowie"]);
+
+        // Jawne `null` usuwa atrybut
+        let request: UpdateTaskRequest = serde_json::from_str(r#"{"attribute": null}"#).unwrap();
+        let cleared = update_task(&conn, task.id, request).unwrap();
+        assert_eq!(cleared.attribute, None);
+        assert_eq!(
+            get_task_by_id(&conn, task.id).unwrap().tags,
+            vec!["zdrowie"]
+        );
+
+        assert!(update_task(&conn, 999, UpdateTaskRequest::default()).is_err());
+    }
+
+    #[test]
+    fn test_task_dates_and_views() {
+        let conn = create_test_db().unwrap();
+        let today = settings_service::today(&conn).unwrap();
+        let now = Utc::now().timestamp();
+
+        let add = |title: &str, due_at: Option<i64>, scheduled_for: Option<NaiveDate>| {
+            add_task(
+                &conn,
+                CreateTaskRequest {
+                    title: title.to_string(),
+                    due_at,
+                    scheduled_for: scheduled_for.map(|date| date.format("%Y-%m-%d").to_string()),
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+        };
+
+        let planned = add("Zaplanowane na dziś", None, Some(today));
+        let overdue = add("Zaległe", Some(now - 3 * 86_400), None);
+        let later = add("Za miesiąc", None, Some(today + Duration::days(30)));
+        let inbox = add("Do przejrzenia", None, None);
+
+        let titles = |tasks: Vec<Task>| -> Vec<String> {
+            tasks.into_iter().map(|task| task.title).collect()
+        };
+
+        assert_eq!(
+            titles(get_tasks_for_today(&conn).unwrap()),
+            vec![planned.title.clone()]
+        );
+        assert!(titles(get_tasks_for_week(&conn).unwrap()).contains(&planned.title));
+        assert!(!titles(get_tasks_for_week(&conn).unwrap()).contains(&later.title));
+        assert_eq!(
+            titles(get_overdue_tasks(&conn).unwrap()),
+            vec![overdue.title.clone()]
+        );
+        assert_eq!(
+            titles(get_unscheduled_tasks(&conn).unwrap()),
+            vec![inbox.title.clone()]
+        );
+
+        // Ukończone zadanie przestaje być zaległe
+        toggle_task_status(&conn, overdue.id).unwrap();
+        assert!(get_overdue_tasks(&conn).unwrap().is_empty());
+
+        // Jawne `null` zdejmuje termin - zadanie wraca do inboxu
+        let request: UpdateTaskRequest =
+            serde_json::from_str(r#"{"scheduled_for": null}"#).unwrap();
+        let unplanned = update_task(&conn, later.id, request).unwrap();
+        assert_eq!(unplanned.scheduled_for, None);
+        assert_eq!(get_unscheduled_tasks(&conn).unwrap().len(), 2);
+
+        let invalid = add_task(
+            &conn,
+            CreateTaskRequest {
+                title: "Zła data".to_string(),
+                scheduled_for: Some("17.10.2026".to_string()),
+                ..Default::default()
+            },
+        );
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn test_recurring_task_spawns_next_occurrence() {
+        let conn = create_test_db().unwrap();
+        let today = settings_service::today(&conn).unwrap();
+
+        let task = add_task(
+            &conn,
+            CreateTaskRequest {
+                title: "Faktury".to_string(),
+                tags: vec!["praca".to_string()],
+                recurrence: Some("freq=daily;count=2".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(task.recurrence.as_deref(), Some("FREQ=DAILY;COUNT=2"));
+        // Bez terminu zadanie powtarzalne jest planowane na dziś
+        assert_eq!(
+            task.scheduled_for,
+            Some(today.format("%Y-%m-%d").to_string())
+        );
+
+        toggle_task_status(&conn, task.id).unwrap();
+        let tomorrow = get_all_tasks(&conn)
+            .unwrap()
+            .into_iter()
+            .find(|t| !t.completed)
+            .unwrap();
+        assert_eq!(tomorrow.title, "Faktury");
+        assert_eq!(tomorrow.tags, vec!["praca"]);
+        assert_eq!(tomorrow.recurrence_id, task.recurrence_id);
+        assert_eq!(
+            tomorrow.scheduled_for,
+            Some((today + Duration::days(1)).format("%Y-%m-%d").to_string())
+        );
+
+        // Ponowne ukończenie tego samego wystąpienia nie dubluje kolejnego
+        toggle_task_status(&conn, task.id).unwrap();
+        toggle_task_status(&conn, task.id).unwrap();
+        assert_eq!(get_all_tasks(&conn).unwrap().len(), 2);
+
+        // COUNT=2 - ostatnie wystąpienie kończy serię
+        let last = toggle_task_status(&conn, tomorrow.id).unwrap();
+        assert_eq!(get_all_tasks(&conn).unwrap().len(), 2);
+        assert_eq!(get_task_by_id(&conn, last.id).unwrap().recurrence, None);
+    }
+
+    #[test]
+    fn test_edit_and_stop_recurrence() {
+        let conn = create_test_db().unwrap();
+        let task = add_task(
+            &conn,
+            CreateTaskRequest {
+                title: "Przegląd tygodnia".to_string(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let request: UpdateTaskRequest =
+            serde_json::from_str(r#"{"recurrence": "FREQ=WEEKLY;BYDAY=FR"}"#).unwrap();
+        let recurring = update_task(&conn, task.id, request).unwrap();
+        assert_eq!(
+            recurring.recurrence.as_deref(),
+            Some("FREQ=WEEKLY;BYDAY=FR")
+        );
+        assert!(recurring.scheduled_for.is_some());
+
+        let request: UpdateTaskRequest =
+            serde_json::from_str(r#"{"recurrence": "FREQ=WEEKLY;BYDAY=XX"}"#).unwrap();
+        assert!(update_task(&conn, task.id, request).is_err());
+
+        // `null` zatrzymuje serię - ukończenie nie tworzy kolejnego wystąpienia
+        let request: UpdateTaskRequest = serde_json::from_str(r#"{"recurrence": null}"#).unwrap();
+        let stopped = update_task(&conn, task.id, request).unwrap();
+        assert_eq!(stopped.recurrence, None);
+        assert_eq!(stopped.recurrence_id, recurring.recurrence_id);
+
+        toggle_task_status(&conn, task.id).unwrap();
+        assert_eq!(get_all_tasks(&conn).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_next_occurrence_shifts_due_date() {
+        let conn = create_test_db().unwrap();
+        let due_at = NaiveDate::from_ymd_opt(2026, 10, 12)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp();
+        let mut task = add_task(
+            &conn,
+            CreateTaskRequest {
+                title: "Raport".to_string(),
+                due_at: Some(due_at),
+                recurrence: Some("FREQ=WEEKLY".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(task.scheduled_for, None);
+        task.completed = true;
+
+        let next =
+            spawn_next_occurrence(&conn, &task, NaiveDate::from_ymd_opt(2026, 10, 12).unwrap())
+                .unwrap()
+                .unwrap();
+        assert_eq!(next.due_at, Some(due_at + 7 * 86_400));
+        assert_eq!(next.scheduled_for, None);
+    }
+
+    #[test]
+    fn test_failed_completion_is_rolled_back() {
+        let conn = create_test_db().unwrap();
+        let task = add_task(
+            &conn,
+            CreateTaskRequest {
+                title: "Raport".to_string(),
+                recurrence: Some("FREQ=WEEKLY".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        // Brak reguły powtarzania sprawia, że utworzenie kolejnego wystąpienia się nie uda
+        conn.execute("DELETE FROM task_recurrences", []).unwrap();
+        assert!(toggle_task_status(&conn, task.id).is_err());
+        assert!(!get_task_by_id(&conn, task.id).unwrap().completed);
+
+        // Po naprawie reguły ukończenie przechodzi razem z kolejnym wystąpieniem
+        conn.execute(
+            "INSERT INTO task_recurrences (id, rule, created_at, updated_at)
+             VALUES (?1, 'FREQ=WEEKLY', 0, 0)",
+            [task.recurrence_id],
+        )
+        .unwrap();
+        assert!(toggle_task_status(&conn, task.id).unwrap().completed);
+        let series: i32 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM tasks WHERE recurrence_id = ?1",
+                [task.recurrence_id],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(series, 2);
+    }
+
+    #[test]
+    fn test_task_days_follow_time_zone() {
+        let conn = create_test_db().unwrap();
+        settings_service::set_time_zone(&conn, "Europe/Warsaw").unwrap();
+        let utc = |d: u32, h: u32| {
+            NaiveDate::from_ymd_opt(2026, 10, d)
+                .unwrap()
+                .and_hms_opt(h, 0, 0)
+                .unwrap()
+                .and_utc()
+                .timestamp()
+        };
+        let day = |d: u32| NaiveDate::from_ymd_opt(2026, 10, d).unwrap();
+
+        // 22:00 UTC to już północ następnego dnia w Warszawie
+        add_task(
+            &conn,
+            CreateTaskRequest {
+                title: "Po północy".to_string(),
+                due_at: Some(utc(12, 22)),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(tasks_between(&conn, day(12), day(13)).unwrap().is_empty());
+        assert_eq!(tasks_between(&conn, day(13), day(14)).unwrap().len(), 1);
+
+        // 09:00 czasu lokalnego zostaje 09:00 także po zmianie czasu 25 października
+        let mut task = add_task(
+            &conn,
+            CreateTaskRequest {
+                title: "Raport".to_string(),
+                due_at: Some(utc(20, 7)),
+                recurrence: Some("FREQ=WEEKLY".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        task.completed = true;
+
+        let next = spawn_next_occurrence(&conn, &task, day(20))
+            .unwrap()
+            .unwrap();
+        assert_eq!(next.due_at, Some(utc(27, 8)));
+    }
+
+    fn add_subtask(conn: &Connection, title: &str, parent_id: i32) -> Task {
+        add_task(
+            conn,
+            CreateTaskRequest {
+                title: title.to_string(),
+                parent_id: Some(parent_id),
+                ..Default::default()
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_subtask_tree_and_progress() {
+        let conn = create_test_db().unwrap();
+        conn.execute(
+            "INSERT INTO projects (name, created_at, updated_at) VALUES ('Przeprowadzka', 0, 0)",
+            [],
+        )
+        .unwrap();
+
+        let parent = add_task(
+            &conn,
+            CreateTaskRequest {
+                title: "Spakować mieszkanie".to_string(),
+                project_id: Some(1),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let kitchen = add_subtask(&conn, "Kuchnia", parent.id);
+        let books = add_subtask(&conn, "Książki", kitchen.id);
+        add_subtask(&conn, "Naczynia", kitchen.id);
+        add_subtask(&conn, "Sypialnia", parent.id);
+
+        // Podzadanie dziedziczy projekt rodzica
+        assert_eq!(books.project_id, Some(1));
+
+        settings_service::set_auto_complete_parents(&conn, false).unwrap();
+        toggle_task_status(&conn, books.id).unwrap();
+
+        let tree = get_task_tree(&conn, parent.id).unwrap();
+        assert_eq!(tree.task.subtask_count, 2);
+        assert_eq!(tree.subtasks[0].task.title, "Kuchnia");
+        assert_eq!(tree.subtasks[0].progress, 50.0);
+        assert_eq!(tree.subtasks[1].progress, 0.0);
+        assert_eq!(tree.progress, 25.0);
+
+        let roots = get_project_tree(&conn, 1).unwrap();
+        assert_eq!(roots.len(), 1);
+        assert_eq!(count_tree(&roots), (5, 1));
+        assert!(get_project_tree(&conn, 99).is_err());
+
+        let children = query_tasks(
+            &conn,
+            TaskQuery {
+                parent_id: Some(parent.id),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(children.items.len(), 2);
+    }
+
+    #[test]
+    fn test_last_subtask_completes_parent() {
+        let conn = create_test_db().unwrap();
+        let parent = add_task(
+            &conn,
+            CreateTaskRequest {
+                title: "Wydanie wersji".to_string(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let tests = add_subtask(&conn, "Testy", parent.id);
+        let notes = add_subtask(&conn, "Notatki", parent.id);
+
+        toggle_task_status(&conn, tests.id).unwrap();
+        assert!(!get_task_by_id(&conn, parent.id).unwrap().completed);
+
+        toggle_task_status(&conn, notes.id).unwrap();
+        assert!(get_task_by_id(&conn, parent.id).unwrap().completed);
+        assert_eq!(get_task_tree(&conn, parent.id).unwrap().progress, 100.0);
+    }
+
+    #[test]
+    fn test_move_task_in_tree() {
+        let conn = create_test_db().unwrap();
+        conn.execute_batch(
+            "INSERT INTO projects (name, created_at, updated_at) VALUES ('Dom', 0, 0), ('Praca', 0, 0);",
+        )
+        .unwrap();
+        let parent = add_task(
+            &conn,
+            CreateTaskRequest {
+                title: "Remont".to_string(),
+                project_id: Some(1),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let child = add_subtask(&conn, "Malowanie", parent.id);
+
+        // Zadanie nie może trafić pod własne podzadanie
+        let request: UpdateTaskRequest =
+            serde_json::from_str(&format!(r#"{{"parent_id": {}}}"#, child.id)).unwrap();
+        assert!(update_task(&conn, parent.id, request).is_err());
+
+        // Podzadanie musi należeć do projektu rodzica
+        let request: UpdateTaskRequest = serde_json::from_str(r#"{"project_id": 2}"#).unwrap();
+        assert!(update_task(&conn, child.id, request).is_err());
+
+        // Przeniesienie rodzica przenosi podzadania
+        let request: UpdateTaskRequest = serde_json::from_str(r#"{"project_id": 2}"#).unwrap();
+        update_task(&conn, parent.id, request).unwrap();
+        assert_eq!(get_task_by_id(&conn, child.id).unwrap().project_id, Some(2));
+
+        let request: UpdateTaskRequest = serde_json::from_str(r#"{"parent_id": null}"#).unwrap();
+        let detached = update_task(&conn, child.id, request).unwrap();
+        assert_eq!(detached.parent_id, None);
+        assert_eq!(detached.project_id, Some(2));
+
+        assert!(add_task(
+            &conn,
+            CreateTaskRequest {
+                title: "Sierota".to_string(),
+                project_id: Some(99),
+                ..Default::default()
+            },
+        )
+        .is_err());
+    }
+
     #[test]
     fn test_toggle_task_status() {
         let conn = create_test_db().unwrap();
         let request = CreateTaskRequest {
             title: "Toggle test".to_string(),
+            ..Default::default()
         };
 
         let task = add_task(&conn, request).unwrap();
@@ -266,6 +1742,7 @@ mod tests {
         let conn = create_test_db().unwrap();
         let request = CreateTaskRequest {
             title: "Delete test".to_string(),
+            ..Default::default()
         };
 
         let task = add_task(&conn, request).unwrap();
@@ -281,4 +1758,16 @@ mod tests {
         let result = get_task_by_id(&conn, task.id);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_delete_missing_task_is_not_found() {
+        let conn = create_test_db().unwrap();
+
+        let err = delete_task(&conn, 42).unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<error::ServiceError>(),
+            Some(error::ServiceError::NotFound(_))
+        ));
+    }
 }
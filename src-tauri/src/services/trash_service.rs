@@ -0,0 +1,489 @@
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension};
+use std::collections::HashMap;
+
+use crate::models::{Habit, Task, TrashItem, TrashKind};
+use crate::services::{error, habit_service, settings_service, task_service};
+
+/// Liczba sekund w dniu
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Okres przechowywania elementów w koszu w sekundach
+fn retention_seconds(conn: &Connection) -> Result<i64> {
+    Ok(i64::from(settings_service::get_trash_retention_days(conn)?) * SECONDS_PER_DAY)
+}
+
+/// Usunięte zadania jako elementy kosza
+///
+/// Podzadania usunięte razem z rodzicem (ten sam `deleted_at`) nie są osobnymi
+/// elementami - wliczają się do `item_count` rodzica i wracają razem z nim.
+fn trashed_tasks(conn: &Connection, retention: i64) -> Result<Vec<TrashItem>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, parent_id, title, deleted_at FROM tasks WHERE deleted_at IS NOT NULL",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i32>(0)?,
+                row.get::<_, Option<i32>>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, i64>(3)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let parents: HashMap<i32, (Option<i32>, i64)> = rows
+        .iter()
+        .map(|(id, parent_id, _, deleted_at)| (*id, (*parent_id, *deleted_at)))
+        .collect();
+    let root_of = |id: i32| {
+        let mut current = id;
+        while let Some((Some(parent_id), deleted_at)) = parents.get(&current) {
+            match parents.get(parent_id) {
+                Some((_, parent_deleted_at)) if parent_deleted_at == deleted_at => {
+                    current = *parent_id
+                }
+                _ => break,
+            }
+        }
+        current
+    };
+
+    let mut subtask_counts: HashMap<i32, i32> = HashMap::new();
+    for (id, _, _, _) in &rows {
+        let root = root_of(*id);
+        if root != *id {
+            *subtask_counts.entry(root).or_default() += 1;
+        }
+    }
+
+    Ok(rows
+        .into_iter()
+        .filter(|(id, _, _, _)| root_of(*id) == *id)
+        .map(|(id, _, title, deleted_at)| TrashItem {
+            kind: TrashKind::Task,
+            id,
+            title,
+            item_count: subtask_counts.get(&id).copied().unwrap_or(0),
+            deleted_at,
+            purge_at: deleted_at + retention,
+        })
+        .collect())
+}
+
+/// Usunięte nawyki jako elementy kosza (z liczbą zachowanych wpisów)
+fn trashed_habits(conn: &Connection, retention: i64) -> Result<Vec<TrashItem>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, title, deleted_at,
+                (SELECT COUNT(*) FROM habit_entries WHERE habit_entries.habit_id = habits.id)
+         FROM habits WHERE deleted_at IS NOT NULL",
+    )?;
+    let items = stmt
+        .query_map([], |row| {
+            let deleted_at: i64 = row.get(2)?;
+            Ok(TrashItem {
+                kind: TrashKind::Habit,
+                id: row.get(0)?,
+                title: row.get(1)?,
+                item_count: row.get(3)?,
+                deleted_at,
+                purge_at: deleted_at + retention,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(items)
+}
+
+/// Pobiera zawartość kosza
+///
+/// Najpierw trwale usuwa elementy, których okres przechowywania minął (patrz [`purge_expired`]).
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+///
+/// # Returns
+/// * `Result<Vec<TrashItem>>` - Usunięte zadania i nawyki (ostatnio usunięte pierwsze) lub błąd
+pub fn list_trash(conn: &Connection) -> Result<Vec<TrashItem>> {
+    purge_expired(conn)?;
+
+    let retention = retention_seconds(conn)?;
+    let mut items = trashed_tasks(conn, retention)?;
+    items.extend(trashed_habits(conn, retention)?);
+    items.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at).then(b.id.cmp(&a.id)));
+
+    Ok(items)
+}
+
+/// Przywraca zadanie z kosza razem z podzadaniami usuniętymi w tym samym momencie
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `task_id` - ID zadania w koszu
+///
+/// # Returns
+/// * `Result<Task>` - Przywrócone zadanie, błąd not found gdy zadania nie ma w koszu
+///   lub conflict gdy w koszu jest wciąż jego zadanie nadrzędne
+pub fn restore_task(conn: &Connection, task_id: i32) -> Result<Task> {
+    let (parent_id, deleted_at): (Option<i32>, i64) = conn
+        .query_row(
+            "SELECT parent_id, deleted_at FROM tasks WHERE id = ?1 AND deleted_at IS NOT NULL",
+            [task_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?
+        .ok_or_else(|| error::not_found(format!("Task with id {} is not in the trash", task_id)))?;
+
+    if let Some(parent_id) = parent_id {
+        let parent_trashed: bool = conn.query_row(
+            "SELECT deleted_at IS NOT NULL FROM tasks WHERE id = ?1",
+            [parent_id],
+            |row| row.get(0),
+        )?;
+        if parent_trashed {
+            return Err(error::conflict(format!(
+                "Parent task {} is in the trash, restore it first",
+                parent_id
+            )));
+        }
+    }
+
+    let sql = format!(
+        "UPDATE tasks SET deleted_at = NULL WHERE id IN ({}) AND deleted_at = ?",
+        task_service::SUBTREE_IDS
+    );
+    conn.execute(&sql, (task_id, deleted_at))?;
+
+    task_service::get_task_by_id(conn, task_id)
+}
+
+/// Przywraca nawyk z kosza razem z wpisami
+///
+/// Streak jest przeliczany, bo mógł się przerwać, gdy nawyk był w koszu.
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `habit_id` - ID nawyku w koszu
+///
+/// # Returns
+/// * `Result<Habit>` - Przywrócony nawyk lub błąd not found, gdy nawyku nie ma w koszu
+pub fn restore_habit(conn: &Connection, habit_id: i32) -> Result<Habit> {
+    let rows_affected = conn.execute(
+        "UPDATE habits SET deleted_at = NULL WHERE id = ?1 AND deleted_at IS NOT NULL",
+        [habit_id],
+    )?;
+    if rows_affected == 0 {
+        return Err(error::not_found(format!(
+            "Habit with id {} is not in the trash",
+            habit_id
+        )));
+    }
+
+    let streak = habit_service::calculate_streak(conn, habit_id)?;
+    habit_service::update_habit_streak(conn, habit_id, streak)?;
+
+    habit_service::get_habit_by_id(conn, habit_id)
+}
+
+/// Trwale usuwa zadanie z kosza (razem z podzadaniami i tagami)
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `task_id` - ID zadania w koszu
+///
+/// # Returns
+/// * `Result<()>` - Sukces lub błąd not found, gdy zadania nie ma w koszu
+pub fn purge_task(conn: &Connection, task_id: i32) -> Result<()> {
+    let rows_affected = conn.execute(
+        "DELETE FROM tasks WHERE id = ?1 AND deleted_at IS NOT NULL",
+        [task_id],
+    )?;
+    if rows_affected == 0 {
+        return Err(error::not_found(format!(
+            "Task with id {} is not in the trash",
+            task_id
+        )));
+    }
+
+    Ok(())
+}
+
+/// Trwale usuwa nawyk z kosza razem z wpisami i powiązanymi questami
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `habit_id` - ID nawyku w koszu
+///
+/// # Returns
+/// * `Result<()>` - Sukces lub błąd not found, gdy nawyku nie ma w koszu
+pub fn purge_habit(conn: &Connection, habit_id: i32) -> Result<()> {
+    let trashed: Option<i32> = conn
+        .query_row(
+            "SELECT id FROM habits WHERE id = ?1 AND deleted_at IS NOT NULL",
+            [habit_id],
+            |row| row.get(0),
+        )
+        .optional()?;
+    if trashed.is_none() {
+        return Err(error::not_found(format!(
+            "Habit with id {} is not in the trash",
+            habit_id
+        )));
+    }
+
+    conn.execute("DELETE FROM habit_entries WHERE habit_id = ?1", [habit_id])?;
+    conn.execute("DELETE FROM habits WHERE id = ?1", [habit_id])?;
+
+    Ok(())
+}
+
+/// Trwale usuwa elementy przeniesione do kosza przed `cutoff`
+///
+/// # Returns
+/// * `Result<usize>` - Liczba usuniętych zadań (bez podzadań) i nawyków
+fn purge_deleted_before(conn: &Connection, cutoff: i64) -> Result<usize> {
+    // Podzadania i tagi znikają kaskadowo razem z zadaniem
+    let tasks = conn.execute(
+        "DELETE FROM tasks
+         WHERE deleted_at < ?1
+           AND (parent_id IS NULL
+                OR parent_id NOT IN (SELECT id FROM tasks WHERE deleted_at < ?1))",
+        [cutoff],
+    )?;
+    conn.execute(
+        "DELETE FROM habit_entries
+         WHERE habit_id IN (SELECT id FROM habits WHERE deleted_at < ?1)",
+        [cutoff],
+    )?;
+    let habits = conn.execute("DELETE FROM habits WHERE deleted_at < ?1", [cutoff])?;
+
+    Ok(tasks + habits)
+}
+
+/// Trwale usuwa elementy, które leżą w koszu dłużej niż okres przechowywania
+///
+/// Okres ustawia `settings_service::set_trash_retention_days` (domyślnie 30 dni).
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+///
+/// # Returns
+/// * `Result<usize>` - Liczba trwale usuniętych zadań i nawyków
+pub fn purge_expired(conn: &Connection) -> Result<usize> {
+    let cutoff = Utc::now().timestamp() - retention_seconds(conn)?;
+    purge_deleted_before(conn, cutoff)
+}
+
+/// Opróżnia kosz
+///
+/// # Returns
+/// * `Result<usize>` - Liczba trwale usuniętych zadań i nawyków
+pub fn empty_trash(conn: &Connection) -> Result<usize> {
+    purge_deleted_before(conn, i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use crate::models::{
+        CreateHabitEntryRequest, CreateHabitRequest, CreateTaskRequest, HabitType, SearchQuery,
+    };
+    use crate::services::search_service;
+
+    fn create_test_db() -> Database {
+        Database::new(":memory:".into()).unwrap()
+    }
+
+    fn add_task(conn: &Connection, title: &str, parent_id: Option<i32>) -> i32 {
+        task_service::add_task(
+            conn,
+            CreateTaskRequest {
+                title: title.to_string(),
+                parent_id,
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .id
+    }
+
+    fn add_habit(conn: &Connection, title: &str) -> i32 {
+        habit_service::add_habit(
+            conn,
+            CreateHabitRequest {
+                title: title.to_string(),
+                habit_type: HabitType::Boolean,
+                target_value: None,
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .id
+    }
+
+    fn search_count(conn: &Connection, q: &str) -> usize {
+        search_service::search(
+            conn,
+            SearchQuery {
+                q: q.to_string(),
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .len()
+    }
+
+    #[test]
+    fn test_deleted_task_goes_to_trash_with_subtasks() {
+        let db = create_test_db();
+        let conn = db.connection();
+        let parent = add_task(conn, "Przeprowadzka", None);
+        add_task(conn, "Spakować książki", Some(parent));
+        let other = add_task(conn, "Zakupy", None);
+
+        task_service::delete_task(conn, parent).unwrap();
+
+        let remaining = task_service::get_all_tasks(conn).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, other);
+        assert!(task_service::get_task_by_id(conn, parent).is_err());
+        assert_eq!(search_count(conn, "ksiazki"), 0);
+
+        let trash = list_trash(conn).unwrap();
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].kind, TrashKind::Task);
+        assert_eq!(trash[0].id, parent);
+        assert_eq!(trash[0].item_count, 1);
+        assert_eq!(
+            trash[0].purge_at - trash[0].deleted_at,
+            30 * SECONDS_PER_DAY
+        );
+
+        let restored = restore_task(conn, parent).unwrap();
+        assert_eq!(restored.subtask_count, 1);
+        assert_eq!(task_service::get_all_tasks(conn).unwrap().len(), 3);
+        assert_eq!(search_count(conn, "ksiazki"), 1);
+        assert!(list_trash(conn).unwrap().is_empty());
+
+        // Zadania spoza kosza nie da się przywrócić
+        assert!(restore_task(conn, parent).is_err());
+    }
+
+    #[test]
+    fn test_subtask_waits_for_trashed_parent() {
+        let db = create_test_db();
+        let conn = db.connection();
+        let parent = add_task(conn, "Remont", None);
+        let child = add_task(conn, "Kupić farbę", Some(parent));
+
+        task_service::delete_task(conn, child).unwrap();
+        conn.execute(
+            "UPDATE tasks SET deleted_at = deleted_at - 60 WHERE id = ?1",
+            [child],
+        )
+        .unwrap();
+        task_service::delete_task(conn, parent).unwrap();
+        assert_eq!(list_trash(conn).unwrap().len(), 2);
+
+        let err = restore_task(conn, child).unwrap_err();
+        assert!(err.to_string().contains("restore it first"));
+
+        // Rodzic wraca bez podzadania usuniętego wcześniej
+        assert_eq!(restore_task(conn, parent).unwrap().subtask_count, 0);
+        restore_task(conn, child).unwrap();
+        assert_eq!(
+            task_service::get_task_by_id(conn, parent)
+                .unwrap()
+                .subtask_count,
+            1
+        );
+    }
+
+    #[test]
+    fn test_trashed_habit_keeps_entries_until_purged() {
+        let db = create_test_db();
+        let conn = db.connection();
+        let habit_id = add_habit(conn, "Medytacja");
+        let today = Utc::now().format("%Y-%m-%d").to_string();
+        habit_service::add_habit_entry(
+            conn,
+            CreateHabitEntryRequest {
+                habit_id,
+                date: today.clone(),
+                completed: Some(true),
+                value: None,
+            },
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO quests (title, description, quest_type, target_value, habit_id,
+                                 reward_exp, week, created_at, updated_at)
+             VALUES ('Mistrz Konsekwencji', 'Medytuj', 'Habit', 7, ?1, 75, '2025-03', 0, 0)",
+            [habit_id],
+        )
+        .unwrap();
+
+        habit_service::delete_habit(conn, habit_id).unwrap();
+        assert!(habit_service::get_all_habits(conn).unwrap().is_empty());
+        assert!(habit_service::get_habit_entries_for_date(conn, &today)
+            .unwrap()
+            .is_empty());
+        assert!(habit_service::delete_habit(conn, habit_id).is_err());
+
+        let trash = list_trash(conn).unwrap();
+        assert_eq!(trash[0].kind, TrashKind::Habit);
+        assert_eq!(trash[0].item_count, 1);
+
+        let restored = restore_habit(conn, habit_id).unwrap();
+        assert_eq!(restored.current_streak, 1);
+
+        habit_service::delete_habit(conn, habit_id).unwrap();
+        purge_habit(conn, habit_id).unwrap();
+        let leftovers: i32 = conn
+            .query_row(
+                "SELECT (SELECT COUNT(*) FROM habit_entries) + (SELECT COUNT(*) FROM quests)",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(leftovers, 0);
+        assert!(restore_habit(conn, habit_id).is_err());
+    }
+
+    #[test]
+    fn test_purge_expired_respects_retention() {
+        let db = create_test_db();
+        let conn = db.connection();
+        let old = add_task(conn, "Stare", None);
+        add_task(conn, "Podzadanie", Some(old));
+        let recent = add_task(conn, "Nowe", None);
+        let habit_id = add_habit(conn, "Stary nawyk");
+
+        task_service::delete_task(conn, old).unwrap();
+        task_service::delete_task(conn, recent).unwrap();
+        habit_service::delete_habit(conn, habit_id).unwrap();
+        settings_service::set_trash_retention_days(conn, 7).unwrap();
+        let eight_days_ago = Utc::now().timestamp() - 8 * SECONDS_PER_DAY;
+        conn.execute(
+            "UPDATE tasks SET deleted_at = ?1 WHERE id != ?2",
+            (eight_days_ago, recent),
+        )
+        .unwrap();
+        conn.execute("UPDATE habits SET deleted_at = ?1", [eight_days_ago])
+            .unwrap();
+
+        assert_eq!(purge_expired(conn).unwrap(), 2);
+        let trash = list_trash(conn).unwrap();
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].id, recent);
+        let task_count: i32 = conn
+            .query_row("SELECT COUNT(*) FROM tasks", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(task_count, 1);
+
+        assert_eq!(empty_trash(conn).unwrap(), 1);
+        assert!(list_trash(conn).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,176 @@
+use std::time::Duration;
+
+use serde_json::json;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::database::SharedDatabase;
+use crate::models::{DeliveryStatus, DomainEvent};
+use crate::services::{events, webhook_service};
+
+/// Maksymalna liczba prób dostarczenia jednego zdarzenia
+const MAX_ATTEMPTS: u32 = 5;
+/// Opóźnienie przed pierwszą ponowną próbą; każda kolejna czeka dwa razy dłużej
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+/// Limit czasu na odpowiedź odbiorcy
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Wynik pojedynczej próby wysłania webhooka
+enum AttemptOutcome {
+    Delivered(u16),
+    /// Błąd przejściowy (sieć, 5xx, 429) - warto spróbować ponownie
+    Retry(Option<u16>, String),
+    /// Błąd trwały (pozostałe 4xx) - ponawianie nic nie da
+    Fail(u16, String),
+}
+
+/// Nasłuchuje zdarzeń domenowych i rozsyła je do zarejestrowanych webhooków
+///
+/// Każde dostarczenie działa w osobnym zadaniu, więc wolny odbiorca
+/// nie blokuje pozostałych.
+pub async fn run(db: SharedDatabase) {
+    let client = match reqwest::Client::builder().timeout(REQUEST_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("❌ Failed to create webhook HTTP client: {}", e);
+            return;
+        }
+    };
+    let mut receiver = events::subscribe();
+
+    loop {
+        let event = match receiver.recv().await {
+            Ok(event) => event,
+            Err(RecvError::Lagged(skipped)) => {
+                eprintln!("⚠️ Webhook dispatcher skipped {} events", skipped);
+                continue;
+            }
+            Err(RecvError::Closed) => return,
+        };
+
+        if let Err(e) = dispatch(&db, &client, event) {
+            eprintln!("❌ Failed to dispatch webhooks: {}", e);
+        }
+    }
+}
+
+/// Zapisuje dostarczenia w dzienniku i uruchamia ich wysyłkę
+fn dispatch(
+    db: &SharedDatabase,
+    client: &reqwest::Client,
+    event: DomainEvent,
+) -> anyhow::Result<()> {
+    let event_type = event.name();
+    let db_guard = db
+        .lock()
+        .map_err(|e| anyhow::anyhow!("Database lock error: {}", e))?;
+    let conn = db_guard.connection();
+
+    for target in webhook_service::targets_for_event(conn, event_type)? {
+        // ID dostarczenia trafia do treści, więc najpierw tworzymy wpis, a potem go uzupełniamy
+        let delivery_id = webhook_service::create_delivery(conn, target.id, event_type, "")?;
+        let payload = json!({
+            "id": delivery_id,
+            "event": event_type,
+            "timestamp": chrono::Utc::now().timestamp(),
+            "data": event,
+        })
+        .to_string();
+        webhook_service::set_delivery_payload(conn, delivery_id, &payload)?;
+
+        tokio::spawn(deliver(
+            db.clone(),
+            client.clone(),
+            target,
+            delivery_id,
+            event_type,
+            payload,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Wysyła jedno zdarzenie, ponawiając próby z wykładniczym opóźnieniem
+async fn deliver(
+    db: SharedDatabase,
+    client: reqwest::Client,
+    target: webhook_service::WebhookTarget,
+    delivery_id: i32,
+    event_type: &'static str,
+    payload: String,
+) {
+    let signature = target
+        .secret
+        .as_deref()
+        .map(|secret| format!("sha256={}", webhook_service::sign_payload(secret, &payload)));
+    let mut backoff = INITIAL_BACKOFF;
+
+    for attempt in 1..=MAX_ATTEMPTS {
+        let mut request = client
+            .post(&target.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header("X-PDRPG-Event", event_type)
+            .header("X-PDRPG-Delivery", delivery_id.to_string())
+            .body(payload.clone());
+        if let Some(signature) = &signature {
+            request = request.header("X-PDRPG-Signature", signature);
+        }
+
+        let outcome = match request.send().await {
+            Ok(response) => {
+                let status = response.status();
+                if status.is_success() {
+                    AttemptOutcome::Delivered(status.as_u16())
+                } else if status.is_server_error()
+                    || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+                {
+                    AttemptOutcome::Retry(Some(status.as_u16()), format!("HTTP {}", status))
+                } else {
+                    AttemptOutcome::Fail(status.as_u16(), format!("HTTP {}", status))
+                }
+            }
+            Err(e) => AttemptOutcome::Retry(None, e.to_string()),
+        };
+
+        let (status, response_status, error, done) = match &outcome {
+            AttemptOutcome::Delivered(code) => (DeliveryStatus::Delivered, Some(*code), None, true),
+            AttemptOutcome::Fail(code, error) => (
+                DeliveryStatus::Failed,
+                Some(*code),
+                Some(error.as_str()),
+                true,
+            ),
+            AttemptOutcome::Retry(code, error) if attempt == MAX_ATTEMPTS => {
+                (DeliveryStatus::Failed, *code, Some(error.as_str()), true)
+            }
+            AttemptOutcome::Retry(code, error) => {
+                (DeliveryStatus::Pending, *code, Some(error.as_str()), false)
+            }
+        };
+
+        match db.lock() {
+            Ok(db) => {
+                if let Err(e) = webhook_service::record_attempt(
+                    db.connection(),
+                    delivery_id,
+                    status,
+                    response_status,
+                    error,
+                ) {
+                    eprintln!(
+                        "❌ Failed to record webhook delivery {}: {}",
+                        delivery_id, e
+                    );
+                }
+            }
+            Err(e) => eprintln!("❌ Database lock error: {}", e),
+        }
+
+        if done {
+            return;
+        }
+
+        tokio::time::sleep(backoff).await;
+        backoff *= 2;
+    }
+}
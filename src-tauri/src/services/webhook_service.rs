@@ -0,0 +1,468 @@
+use anyhow::Result;
+use hmac::{Hmac, Mac};
+use rusqlite::{Connection, Row};
+use sha2::Sha256;
+
+use crate::models::{CreateWebhookRequest, DeliveryStatus, DomainEvent, Webhook, WebhookDelivery};
+use crate::services::error;
+
+/// Maksymalna liczba wpisów dziennika dostarczeń zwracana naraz
+const MAX_DELIVERY_LOG: u32 = 200;
+
+/// Webhook z sekretem - używany wyłącznie przy wysyłaniu zdarzeń
+pub struct WebhookTarget {
+    pub id: i32,
+    pub url: String,
+    pub secret: Option<String>,
+}
+
+/// Konwertuje wiersz bazy danych na obiekt Webhook
+fn row_to_webhook(row: &Row) -> Result<Webhook, rusqlite::Error> {
+    let events_str: String = row.get("events")?;
+    let secret: Option<String> = row.get("secret")?;
+
+    Ok(Webhook {
+        id: row.get("id")?,
+        url: row.get("url")?,
+        events: split_events(&events_str),
+        has_secret: secret.is_some(),
+        active: row.get("active")?,
+        created_at: row.get("created_at")?,
+        updated_at: row.get("updated_at")?,
+    })
+}
+
+/// Konwertuje wiersz bazy danych na obiekt WebhookDelivery
+fn row_to_delivery(row: &Row) -> Result<WebhookDelivery, rusqlite::Error> {
+    let status_str: String = row.get("status")?;
+    let status = match status_str.as_str() {
+        "Delivered" => DeliveryStatus::Delivered,
+        "Failed" => DeliveryStatus::Failed,
+        _ => DeliveryStatus::Pending,
+    };
+
+    Ok(WebhookDelivery {
+        id: row.get("id")?,
+        webhook_id: row.get("webhook_id")?,
+        event_type: row.get("event_type")?,
+        payload: row.get("payload")?,
+        status,
+        attempts: row.get("attempts")?,
+        response_status: row.get("response_status")?,
+        error: row.get("error")?,
+        created_at: row.get("created_at")?,
+        last_attempt_at: row.get("last_attempt_at")?,
+    })
+}
+
+fn split_events(events: &str) -> Vec<String> {
+    events
+        .split(',')
+        .map(str::trim)
+        .filter(|event| !event.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn delivery_status_str(status: DeliveryStatus) -> &'static str {
+    match status {
+        DeliveryStatus::Pending => "Pending",
+        DeliveryStatus::Delivered => "Delivered",
+        DeliveryStatus::Failed => "Failed",
+    }
+}
+
+/// Rejestruje nowy webhook
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `request` - Adres, filtr zdarzeń i opcjonalny sekret
+///
+/// # Returns
+/// * `Result<Webhook>` - Zapisany webhook lub błąd walidacji
+pub fn create_webhook(conn: &Connection, request: CreateWebhookRequest) -> Result<Webhook> {
+    let url = request.url.trim().to_string();
+    if reqwest::Url::parse(&url)
+        .map(|parsed| !matches!(parsed.scheme(), "http" | "https"))
+        .unwrap_or(true)
+    {
+        return Err(error::validation(format!(
+            "Invalid webhook URL '{}' (expected http:// or https://)",
+            url
+        )));
+    }
+
+    let mut events = Vec::new();
+    for event in request.events {
+        let event = event.trim().to_string();
+        if !DomainEvent::NAMES.contains(&event.as_str()) {
+            return Err(error::validation(format!(
+                "Unknown event type '{}' (expected one of: {})",
+                event,
+                DomainEvent::NAMES.join(", ")
+            )));
+        }
+        if !events.contains(&event) {
+            events.push(event);
+        }
+    }
+
+    let secret = request
+        .secret
+        .map(|secret| secret.trim().to_string())
+        .filter(|secret| !secret.is_empty());
+
+    let now = chrono::Utc::now().timestamp();
+    conn.execute(
+        "INSERT INTO webhooks (url, events, secret, active, created_at, updated_at)
+         VALUES (?1, ?2, ?3, 1, ?4, ?4)",
+        (&url, events.join(","), &secret, now),
+    )?;
+
+    Ok(Webhook {
+        id: conn.last_insert_rowid() as i32,
+        url,
+        events,
+        has_secret: secret.is_some(),
+        active: true,
+        created_at: now,
+        updated_at: now,
+    })
+}
+
+/// Pobiera wszystkie webhooki
+pub fn list_webhooks(conn: &Connection) -> Result<Vec<Webhook>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, url, events, secret, active, created_at, updated_at
+         FROM webhooks ORDER BY created_at DESC, id DESC",
+    )?;
+
+    let webhook_iter = stmt.query_map([], row_to_webhook)?;
+
+    let mut webhooks = Vec::new();
+    for webhook in webhook_iter {
+        webhooks.push(webhook?);
+    }
+
+    Ok(webhooks)
+}
+
+/// Włącza lub wyłącza webhook
+pub fn set_webhook_active(conn: &Connection, webhook_id: i32, active: bool) -> Result<Webhook> {
+    let now = chrono::Utc::now().timestamp();
+    let rows_affected = conn.execute(
+        "UPDATE webhooks SET active = ?1, updated_at = ?2 WHERE id = ?3",
+        (active, now, webhook_id),
+    )?;
+
+    if rows_affected == 0 {
+        return Err(error::not_found(format!(
+            "Webhook with id {} not found",
+            webhook_id
+        )));
+    }
+
+    let webhook = conn.query_row(
+        "SELECT id, url, events, secret, active, created_at, updated_at FROM webhooks WHERE id = ?1",
+        [webhook_id],
+        row_to_webhook,
+    )?;
+
+    Ok(webhook)
+}
+
+/// Usuwa webhook wraz z jego dziennikiem dostarczeń
+pub fn delete_webhook(conn: &Connection, webhook_id: i32) -> Result<()> {
+    conn.execute(
+        "DELETE FROM webhook_deliveries WHERE webhook_id = ?1",
+        [webhook_id],
+    )?;
+    let rows_affected = conn.execute("DELETE FROM webhooks WHERE id = ?1", [webhook_id])?;
+
+    if rows_affected == 0 {
+        return Err(error::not_found(format!(
+            "Webhook with id {} not found",
+            webhook_id
+        )));
+    }
+
+    Ok(())
+}
+
+/// Pobiera aktywne webhooki zainteresowane danym typem zdarzenia
+pub fn targets_for_event(conn: &Connection, event_type: &str) -> Result<Vec<WebhookTarget>> {
+    let mut stmt = conn.prepare("SELECT id, url, events, secret FROM webhooks WHERE active = 1")?;
+
+    let target_iter = stmt.query_map([], |row| {
+        let events: String = row.get("events")?;
+        Ok((
+            events,
+            WebhookTarget {
+                id: row.get("id")?,
+                url: row.get("url")?,
+                secret: row.get("secret")?,
+            },
+        ))
+    })?;
+
+    let mut targets = Vec::new();
+    for target in target_iter {
+        let (events, target) = target?;
+        let events = split_events(&events);
+        if events.is_empty() || events.iter().any(|event| event == event_type) {
+            targets.push(target);
+        }
+    }
+
+    Ok(targets)
+}
+
+/// Tworzy wpis dziennika dla nowego dostarczenia (status `Pending`)
+///
+/// # Returns
+/// * `Result<i32>` - ID wpisu dziennika
+pub fn create_delivery(
+    conn: &Connection,
+    webhook_id: i32,
+    event_type: &str,
+    payload: &str,
+) -> Result<i32> {
+    let now = chrono::Utc::now().timestamp();
+    conn.execute(
+        "INSERT INTO webhook_deliveries (webhook_id, event_type, payload, status, attempts, created_at)
+         VALUES (?1, ?2, ?3, 'Pending', 0, ?4)",
+        (webhook_id, event_type, payload, now),
+    )?;
+
+    Ok(conn.last_insert_rowid() as i32)
+}
+
+/// Uzupełnia treść wysyłanego zdarzenia w dzienniku dostarczeń
+pub fn set_delivery_payload(conn: &Connection, delivery_id: i32, payload: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE webhook_deliveries SET payload = ?1 WHERE id = ?2",
+        (payload, delivery_id),
+    )?;
+
+    Ok(())
+}
+
+/// Zapisuje wynik próby dostarczenia
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `delivery_id` - ID wpisu dziennika
+/// * `status` - Nowy status (`Pending` gdy zaplanowano kolejną próbę)
+/// * `response_status` - Kod HTTP odpowiedzi, jeśli została odebrana
+/// * `error` - Opis błędu, jeśli próba się nie powiodła
+pub fn record_attempt(
+    conn: &Connection,
+    delivery_id: i32,
+    status: DeliveryStatus,
+    response_status: Option<u16>,
+    error: Option<&str>,
+) -> Result<()> {
+    let now = chrono::Utc::now().timestamp();
+    conn.execute(
+        "UPDATE webhook_deliveries
+         SET status = ?1, attempts = attempts + 1, response_status = ?2, error = ?3, last_attempt_at = ?4
+         WHERE id = ?5",
+        (
+            delivery_status_str(status),
+            response_status,
+            error,
+            now,
+            delivery_id,
+        ),
+    )?;
+
+    Ok(())
+}
+
+/// Pobiera najnowsze wpisy dziennika dostarczeń
+///
+/// # Arguments
+/// * `conn` - Referencja do połączenia z bazą danych
+/// * `webhook_id` - Opcjonalne ograniczenie do jednego webhooka
+/// * `limit` - Maksymalna liczba wpisów (domyślnie i maksymalnie 200)
+pub fn list_deliveries(
+    conn: &Connection,
+    webhook_id: Option<i32>,
+    limit: Option<u32>,
+) -> Result<Vec<WebhookDelivery>> {
+    let limit = limit.unwrap_or(MAX_DELIVERY_LOG).clamp(1, MAX_DELIVERY_LOG);
+    let mut stmt = conn.prepare(
+        "SELECT id, webhook_id, event_type, payload, status, attempts, response_status, error,
+                created_at, last_attempt_at
+         FROM webhook_deliveries
+         WHERE ?1 IS NULL OR webhook_id = ?1
+         ORDER BY created_at DESC, id DESC
+         LIMIT ?2",
+    )?;
+
+    let delivery_iter = stmt.query_map((webhook_id, limit), row_to_delivery)?;
+
+    let mut deliveries = Vec::new();
+    for delivery in delivery_iter {
+        deliveries.push(delivery?);
+    }
+
+    Ok(deliveries)
+}
+
+/// Podpisuje ciało żądania sekretem webhooka (HMAC-SHA256, zapis szesnastkowy)
+///
+/// Odbiorca powinien porównać wynik z nagłówkiem `X-PDRPG-Signature: sha256=<hex>`.
+pub fn sign_payload(secret: &str, payload: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(payload.as_bytes());
+
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    fn create_test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+
+        conn.execute_batch(
+            "CREATE TABLE webhooks (
+                id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                url TEXT NOT NULL,
+                events TEXT NOT NULL DEFAULT '',
+                secret TEXT,
+                active BOOLEAN NOT NULL DEFAULT 1,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL
+            );
+            CREATE TABLE webhook_deliveries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                webhook_id INTEGER NOT NULL,
+                event_type TEXT NOT NULL,
+                payload TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'Pending',
+                attempts INTEGER NOT NULL DEFAULT 0,
+                response_status INTEGER,
+                error TEXT,
+                created_at INTEGER NOT NULL,
+                last_attempt_at INTEGER
+            );",
+        )
+        .unwrap();
+
+        conn
+    }
+
+    #[test]
+    fn test_create_webhook_and_match_events() {
+        let conn = create_test_db();
+
+        let level_only = create_webhook(
+            &conn,
+            CreateWebhookRequest {
+                url: "http://localhost:8080/hook".to_string(),
+                events: vec!["LevelUp".to_string()],
+                secret: Some("s3cret".to_string()),
+            },
+        )
+        .unwrap();
+        assert!(level_only.has_secret);
+
+        create_webhook(
+            &conn,
+            CreateWebhookRequest {
+                url: "https://example.com/all".to_string(),
+                events: vec![],
+                secret: None,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(targets_for_event(&conn, "LevelUp").unwrap().len(), 2);
+        assert_eq!(targets_for_event(&conn, "TaskCompleted").unwrap().len(), 1);
+
+        set_webhook_active(&conn, level_only.id, false).unwrap();
+        assert_eq!(targets_for_event(&conn, "LevelUp").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_create_webhook_validation() {
+        let conn = create_test_db();
+
+        let bad_url = create_webhook(
+            &conn,
+            CreateWebhookRequest {
+                url: "ftp://localhost/hook".to_string(),
+                events: vec![],
+                secret: None,
+            },
+        );
+        assert!(bad_url.is_err());
+
+        let bad_event = create_webhook(
+            &conn,
+            CreateWebhookRequest {
+                url: "http://localhost/hook".to_string(),
+                events: vec!["Nope".to_string()],
+                secret: None,
+            },
+        );
+        assert!(bad_event.is_err());
+    }
+
+    #[test]
+    fn test_delivery_log() {
+        let conn = create_test_db();
+        let webhook = create_webhook(
+            &conn,
+            CreateWebhookRequest {
+                url: "http://localhost:8080/hook".to_string(),
+                events: vec![],
+                secret: None,
+            },
+        )
+        .unwrap();
+
+        let delivery_id = create_delivery(&conn, webhook.id, "LevelUp", "{}").unwrap();
+        record_attempt(
+            &conn,
+            delivery_id,
+            DeliveryStatus::Pending,
+            Some(500),
+            Some("HTTP 500"),
+        )
+        .unwrap();
+        record_attempt(
+            &conn,
+            delivery_id,
+            DeliveryStatus::Delivered,
+            Some(200),
+            None,
+        )
+        .unwrap();
+
+        let deliveries = list_deliveries(&conn, Some(webhook.id), None).unwrap();
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(deliveries[0].status, DeliveryStatus::Delivered);
+        assert_eq!(deliveries[0].attempts, 2);
+        assert_eq!(deliveries[0].response_status, Some(200));
+        assert_eq!(deliveries[0].error, None);
+    }
+
+    #[test]
+    fn test_sign_payload() {
+        // Wektor testowy RFC 4231 (przypadek 2)
+        assert_eq!(
+            sign_payload("Jefe", "what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
@@ -74,7 +74,9 @@ export const taskActions = {
 		taskStore.update(state => ({ ...state, loading: 'loading', error: null }));
 
 		try {
-			const newTask: Task = await invoke('add_task', { title: title.trim() });
+			const newTask: Task = await invoke('add_task', {
+				request: { title: title.trim() }
+			});
 			taskStore.update(state => ({
 				...state,
 				tasks: [newTask, ...state.tasks], // Dodaj na początku (najnowsze pierwsze)
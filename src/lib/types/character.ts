@@ -25,6 +25,7 @@ export interface Character {
     experience: number;                 // Aktualny experience points
     character_class: CharacterClass;   // Klasa postaci
     attributes: CharacterAttributes;   // Atrybuty postaci
+    streak_freezes: number;             // Żetony zamrożenia streaka (maks. 3)
     created_at: number;                 // Timestamp utworzenia postaci
     updated_at: number;                 // Timestamp ostatniej modyfikacji
 }
@@ -0,0 +1,105 @@
+/**
+ * Poziom celu w drzewie Wizja → Roczny → Kwartalny → Miesięczny
+ */
+export type GoalLevel = 'Vision' | 'Yearly' | 'Quarterly' | 'Monthly';
+
+/**
+ * Status celu
+ */
+export type GoalStatus = 'Active' | 'Completed' | 'Abandoned';
+
+/**
+ * Kluczowy rezultat - mierzalna wartość docelowa celu (OKR)
+ * Zgodny z modelem KeyResult z backendu Rust
+ */
+export interface KeyResult {
+	/** Unikalny identyfikator kluczowego rezultatu */
+	id: number;
+	/** Cel, do którego należy */
+	goal_id: number;
+	/** Tytuł kluczowego rezultatu */
+	title: string;
+	/** Wartość docelowa */
+	target_value: number;
+	/** Wartość bieżąca */
+	current_value: number;
+	/** Jednostka wartości (np. km) */
+	unit: string | null;
+	/** Procent realizacji (0-100) */
+	progress: number;
+	/** Timestamp utworzenia (Unix timestamp) */
+	created_at: number;
+	/** Timestamp ostatniej modyfikacji (Unix timestamp) */
+	updated_at: number;
+}
+
+/**
+ * Interfejs reprezentujący cel
+ * Zgodny z modelem Goal z backendu Rust
+ */
+export interface Goal {
+	/** Unikalny identyfikator celu */
+	id: number;
+	/** Tytuł celu */
+	title: string;
+	/** Opis celu */
+	description: string | null;
+	/** Poziom celu */
+	level: GoalLevel;
+	/** Cel nadrzędny (wyższego poziomu) */
+	parent_id: number | null;
+	/** Termin realizacji (Unix timestamp) */
+	deadline: number | null;
+	/** Status celu */
+	status: GoalStatus;
+	/** Kluczowe rezultaty celu */
+	key_results: KeyResult[];
+	/** Liczba zadań powiązanych z celem */
+	task_count: number;
+	/** Liczba ukończonych zadań powiązanych z celem */
+	completed_task_count: number;
+	/** Procent ukończenia (0-100) */
+	progress: number;
+	/** Timestamp utworzenia (Unix timestamp) */
+	created_at: number;
+	/** Timestamp ostatniej modyfikacji (Unix timestamp) */
+	updated_at: number;
+}
+
+/**
+ * Cel wraz z drzewem podcelów
+ */
+export interface GoalNode extends Goal {
+	/** Podcele niższego poziomu */
+	children: GoalNode[];
+}
+
+/**
+ * Interfejs dla danych tworzenia nowego celu
+ */
+export interface CreateGoalRequest {
+	/** Tytuł celu */
+	title: string;
+	/** Opis celu */
+	description?: string;
+	/** Poziom celu */
+	level: GoalLevel;
+	/** Cel nadrzędny - musi mieć wyższy poziom */
+	parent_id?: number;
+	/** Termin realizacji (Unix timestamp) */
+	deadline?: number;
+}
+
+/**
+ * Interfejs dla danych dodania kluczowego rezultatu
+ */
+export interface CreateKeyResultRequest {
+	/** Tytuł kluczowego rezultatu */
+	title: string;
+	/** Wartość docelowa (większa od zera) */
+	target_value: number;
+	/** Wartość początkowa (domyślnie 0) */
+	current_value?: number;
+	/** Jednostka wartości */
+	unit?: string;
+}
@@ -3,6 +3,16 @@
  */
 export type HabitType = 'Boolean' | 'Counter';
 
+/**
+ * Harmonogram nawyku - w które dni oczekiwane jest jego wykonanie
+ * (dni tygodnia: 1 = poniedziałek ... 7 = niedziela)
+ */
+export type HabitSchedule =
+	| { type: 'Daily' }
+	| { type: 'Weekdays'; days: number[] }
+	| { type: 'TimesPerWeek'; times: number }
+	| { type: 'EveryNDays'; interval: number };
+
 /**
  * Interfejs reprezentujący nawyk w aplikacji PDRPG
  * Zgodny z modelem Habit z backendu Rust
@@ -16,8 +26,14 @@ export interface Habit {
 	habit_type: HabitType;
 	/** Wartość docelowa (dla typu Counter, np. 8 szklanek wody) */
 	target_value?: number;
-	/** Aktualny ciąg dni (streak) */
+	/** Harmonogram nawyku */
+	schedule: HabitSchedule;
+	/** Aktualny streak (kolejne terminy z harmonogramu) */
 	current_streak: number;
+	/** Najdłuższy osiągnięty streak */
+	best_streak: number;
+	/** Łączna liczba dni z wykonanym nawykiem */
+	total_completions: number;
 	/** Timestamp utworzenia nawyku (Unix timestamp) */
 	created_at: number;
 	/** Timestamp ostatniej modyfikacji (Unix timestamp) */
@@ -38,6 +54,8 @@ export interface HabitEntry {
 	completed: boolean;
 	/** Wartość dla nawyków typu Counter */
 	value: number;
+	/** Czy dzień został pokryty żetonem zamrożenia streaka (zamiast wykonania) */
+	frozen: boolean;
 	/** Timestamp utworzenia wpisu */
 	created_at: number;
 }
@@ -52,6 +70,8 @@ export interface CreateHabitRequest {
 	habit_type: HabitType;
 	/** Wartość docelowa (opcjonalna, dla typu Counter) */
 	target_value?: number;
+	/** Harmonogram (opcjonalny, domyślnie codziennie) */
+	schedule?: HabitSchedule;
 }
 
 /**
@@ -62,6 +82,8 @@ export interface UpdateHabitRequest {
 	title?: string;
 	/** Nowa wartość docelowa */
 	target_value?: number;
+	/** Nowy harmonogram (przelicza streak) */
+	schedule?: HabitSchedule;
 }
 
 /**
@@ -78,6 +100,104 @@ export interface CreateHabitEntryRequest {
 	value?: number;
 }
 
+/**
+ * Ustawienia wyznaczające "dzisiaj" dla nawyków, streaków i questów
+ */
+export interface TimeSettings {
+	/** Strefa czasowa IANA (np. "Europe/Warsaw") */
+	time_zone: string;
+	/** Godzina (0-23), o której zaczyna się nowy dzień */
+	day_rollover_hour: number;
+}
+
+/**
+ * Wykonanie nawyku w ostatnich N dniach
+ */
+export interface HabitPeriodStats {
+	/** Długość okresu w dniach (7, 30 lub 90) */
+	days: number;
+	/** Liczba wykonań oczekiwana według harmonogramu */
+	expected: number;
+	/** Liczba wykonań zaliczonych do harmonogramu */
+	completed: number;
+	/** Odsetek wykonania (0.0 - 1.0) */
+	completion_rate: number;
+	/** Średnia dzienna wartość (tylko dla typu Counter) */
+	average_value: number | null;
+}
+
+/**
+ * Wykonanie nawyku w danym dniu tygodnia
+ */
+export interface WeekdayStats {
+	/** Dzień tygodnia (1 = poniedziałek ... 7 = niedziela) */
+	weekday: number;
+	/** Liczba takich dni od początku historii nawyku */
+	days: number;
+	/** Liczba dni z wykonanym nawykiem */
+	completed: number;
+	/** Odsetek wykonania (0.0 - 1.0) */
+	completion_rate: number;
+}
+
+/**
+ * Jedna seria (streak) z historii nawyku
+ */
+export interface StreakRun {
+	/** Dzień pierwszego wykonania w serii (YYYY-MM-DD) */
+	start: string;
+	/** Dzień ostatniego wykonania w serii (YYYY-MM-DD) */
+	end: string;
+	/** Długość serii w jednostkach harmonogramu */
+	length: number;
+	/** Czy seria wciąż trwa */
+	active: boolean;
+}
+
+/**
+ * Statystyki nawyku (komenda get_habit_stats)
+ */
+export interface HabitStats {
+	habit_id: number;
+	current_streak: number;
+	best_streak: number;
+	total_completions: number;
+	/** Wykonanie w ostatnich 7, 30 i 90 dniach */
+	periods: HabitPeriodStats[];
+	/** Wykonanie w poszczególne dni tygodnia (od poniedziałku) */
+	weekdays: WeekdayStats[];
+	/** Historia serii od najstarszej */
+	streak_history: StreakRun[];
+}
+
+/**
+ * Parametry kalendarza wypełnienia (komenda get_habit_heatmap)
+ */
+export interface HeatmapQuery {
+	/** Pierwszy dzień (YYYY-MM-DD), domyślnie 364 dni przed `to` */
+	from?: string;
+	/** Ostatni dzień (YYYY-MM-DD), domyślnie dzisiaj */
+	to?: string;
+	/** Tylko ten nawyk (domyślnie wszystkie nawyki razem) */
+	habit_id?: number;
+}
+
+/**
+ * Jeden dzień kalendarza wypełnienia
+ */
+export interface HeatmapDay {
+	/** Dzień (YYYY-MM-DD) */
+	date: string;
+	/** Średni stopień wykonania nawyków tego dnia (0.0 - 1.0) */
+	intensity: number;
+	/** Liczba nawyków wykonanych tego dnia */
+	completed: number;
+	/** Liczba dni pokrytych żetonem zamrożenia */
+	frozen: number;
+	/** Liczba nawyków branych pod uwagę (istniejących tego dnia) */
+	total: number;
+}
+
 /**
  * Typy stanów dla zarządzania operacjami asynchronicznymi
  */
@@ -0,0 +1,38 @@
+import type { Attribute } from './task';
+
+/**
+ * Interfejs reprezentujący projekt - nazwany kontener zadań
+ * Zgodny z modelem Project z backendu Rust
+ */
+export interface Project {
+	/** Unikalny identyfikator projektu */
+	id: number;
+	/** Nazwa projektu (unikalna) */
+	name: string;
+	/** Kolor w formacie #RRGGBB */
+	color: string;
+	/** Atrybut rozwijany przez zadania projektu, które nie mają własnego */
+	attribute: Attribute | null;
+	/** Liczba zadań projektu (razem z podzadaniami) */
+	task_count: number;
+	/** Liczba ukończonych zadań projektu */
+	completed_count: number;
+	/** Procent ukończenia (0-100) */
+	progress: number;
+	/** Timestamp utworzenia (Unix timestamp) */
+	created_at: number;
+	/** Timestamp ostatniej modyfikacji (Unix timestamp) */
+	updated_at: number;
+}
+
+/**
+ * Interfejs dla danych tworzenia nowego projektu
+ */
+export interface CreateProjectRequest {
+	/** Nazwa projektu */
+	name: string;
+	/** Kolor #RRGGBB (domyślnie #6366f1) */
+	color?: string;
+	/** Atrybut postaci rozwijany przez zadania projektu */
+	attribute?: Attribute;
+}
@@ -1,7 +1,7 @@
 // Quest and Achievement types for PDRPG frontend
 
 export type QuestStatus = 'Active' | 'Completed' | 'Expired';
-export type QuestType = 'Task' | 'Habit' | 'Character';
+export type QuestType = 'Task' | 'Habit' | 'Character' | 'Goal' | 'Overdue';
 
 export interface Quest {
     id: number;
@@ -12,6 +12,7 @@ export interface Quest {
     current_progress: number;
     category?: string;
     habit_id?: number;
+    goal_id?: number; // Cel kwartalny dla questów typu Goal
     status: QuestStatus;
     reward_exp: number;
     deadline?: number; // Unix timestamp
@@ -21,7 +22,7 @@ export interface Quest {
 }
 
 export type AchievementStatus = 'Locked' | 'Available' | 'Earned';
-export type AchievementType = 'HabitStreak' | 'TaskCount' | 'CharacterLevel' | 'QuestCount';
+export type AchievementType = 'HabitStreak' | 'TaskCount' | 'CharacterLevel' | 'QuestCount' | 'InboxZero';
 
 export interface Achievement {
     id: number;
@@ -100,6 +101,10 @@ export function getQuestTypeIcon(type: QuestType): string {
             return '🔄';
         case 'Character':
             return '⚔️';
+        case 'Goal':
+            return '🏔️';
+        case 'Overdue':
+            return '⏳';
         default:
             return '📝';
     }
@@ -0,0 +1,33 @@
+/**
+ * Rodzaj elementu zwracanego przez wyszukiwanie
+ */
+export type SearchKind = 'task' | 'habit' | 'quest';
+
+/**
+ * Parametry wyszukiwania pełnotekstowego
+ */
+export interface SearchQuery {
+	/** Szukany tekst (wielkość liter i polskie znaki są ignorowane) */
+	q: string;
+	/** Tylko wyniki danego rodzaju */
+	kind?: SearchKind;
+	/** Maksymalna liczba wyników (1-100, domyślnie 20) */
+	limit?: number;
+}
+
+/**
+ * Pojedynczy wynik wyszukiwania
+ */
+export interface SearchResult {
+	kind: SearchKind;
+	/** ID zadania, nawyku lub questa */
+	id: number;
+	/** Oryginalny tytuł */
+	title: string;
+	/** Tytuł z dopasowaniami w znacznikach <mark> */
+	title_highlight: string;
+	/** Fragment notatek lub opisu z dopasowaniem (null, gdy pasuje tylko tytuł) */
+	snippet: string | null;
+	/** Trafność BM25 (mniejsza wartość = lepsze dopasowanie) */
+	rank: number;
+}
@@ -1,3 +1,15 @@
+/** Priorytet zadania (wpływa na nagrodę EXP) */
+export type TaskPriority = 'Low' | 'Medium' | 'High' | 'Urgent';
+
+/** Atrybut postaci rozwijany przez zadanie */
+export type Attribute =
+	| 'strength'
+	| 'intelligence'
+	| 'charisma'
+	| 'dexterity'
+	| 'wisdom'
+	| 'constitution';
+
 /**
  * Interfejs reprezentujący zadanie w aplikacji PDRPG
  * Zgodny z modelem Task z backendu Rust
@@ -7,8 +19,36 @@ export interface Task {
 	id: number;
 	/** Tytuł/nazwa zadania */
 	title: string;
+	/** Notatki do zadania (przeszukiwane razem z tytułem) */
+	notes: string | null;
 	/** Status ukończenia zadania */
 	completed: boolean;
+	/** Priorytet zadania */
+	priority: TaskPriority;
+	/** Atrybut postaci rozwijany przez zadanie */
+	attribute: Attribute | null;
+	/** Tagi zadania (małymi literami) */
+	tags: string[];
+	/** Termin wykonania (Unix timestamp) */
+	due_at: number | null;
+	/** Dzień, na który zaplanowano zadanie (YYYY-MM-DD) */
+	scheduled_for: string | null;
+	/** ID serii zadania powtarzalnego */
+	recurrence_id: number | null;
+	/** Reguła powtarzania aktywnej serii (RRULE, np. FREQ=WEEKLY;BYDAY=MO) */
+	recurrence: string | null;
+	/** Projekt, do którego należy zadanie */
+	project_id: number | null;
+	/** Zadanie nadrzędne (dla podzadań) */
+	parent_id: number | null;
+	/** Cel, do którego przybliża zadanie (ukończenie daje więcej EXP) */
+	goal_id: number | null;
+	/** Zadanie czeka w skrzynce na przegląd */
+	inbox: boolean;
+	/** Szacowany czas wykonania w minutach */
+	estimate_minutes: number | null;
+	/** Liczba bezpośrednich podzadań */
+	subtask_count: number;
 	/** Timestamp utworzenia zadania (Unix timestamp) */
 	created_at: number;
 	/** Timestamp ostatniej modyfikacji (Unix timestamp) */
@@ -21,6 +61,95 @@ export interface Task {
 export interface CreateTaskRequest {
 	/** Tytuł nowego zadania */
 	title: string;
+	/** Notatki do zadania */
+	notes?: string;
+	/** Priorytet (domyślnie Medium) */
+	priority?: TaskPriority;
+	/** Atrybut postaci rozwijany przez zadanie */
+	attribute?: Attribute;
+	/** Tagi zadania */
+	tags?: string[];
+	/** Termin wykonania (Unix timestamp) */
+	due_at?: number;
+	/** Dzień, na który zaplanowano zadanie (YYYY-MM-DD) */
+	scheduled_for?: string;
+	/** Reguła powtarzania (RRULE) */
+	recurrence?: string;
+	/** Projekt zadania (podzadania dziedziczą projekt rodzica) */
+	project_id?: number;
+	/** Zadanie nadrzędne - tworzy podzadanie */
+	parent_id?: number;
+	/** Cel, do którego przybliża zadanie */
+	goal_id?: number;
+	/** Czy zadanie trafia do skrzynki (domyślnie tak, gdy nie podano kontekstu) */
+	inbox?: boolean;
+	/** Szacowany czas wykonania w minutach */
+	estimate_minutes?: number;
+	/** Parsuje składnię szybkiego dodawania w tytule (jutro 18:00 #tag !high +Projekt ~30m) */
+	quick_add?: boolean;
+}
+
+/**
+ * Pola rozpoznane w tekście szybkiego dodawania (nierozpoznany tekst zostaje w tytule)
+ */
+export interface ParsedTask {
+	/** Tytuł po usunięciu rozpoznanych fragmentów */
+	title: string;
+	priority: TaskPriority | null;
+	tags: string[];
+	/** Termin, gdy podano godzinę (Unix timestamp) */
+	due_at: number | null;
+	/** Dzień, gdy podano datę bez godziny (YYYY-MM-DD) */
+	scheduled_for: string | null;
+	project_id: number | null;
+	/** Nazwa rozpoznanego projektu */
+	project: string | null;
+	estimate_minutes: number | null;
+}
+
+/**
+ * Zadanie utworzone przez szybkie dodawanie
+ */
+export interface QuickAddResult {
+	task: Task;
+	parsed: ParsedTask;
+}
+
+/**
+ * Dane przeglądu zadania ze skrzynki (zadanie zawsze opuszcza skrzynkę)
+ */
+export interface TriageTaskRequest {
+	project_id?: number;
+	goal_id?: number;
+	/** Dzień, na który zaplanowano zadanie (YYYY-MM-DD) */
+	scheduled_for?: string;
+	/** Termin wykonania (Unix timestamp) */
+	due_at?: number;
+	/** Zastępuje tagi zadania */
+	tags?: string[];
+	priority?: TaskPriority;
+}
+
+/**
+ * Stan skrzynki zadań
+ */
+export interface InboxStatus {
+	/** Liczba nieukończonych zadań w skrzynce */
+	count: number;
+	/** Liczba dni, w których skrzynka została opróżniona */
+	inbox_zero_count: number;
+	/** Ostatni dzień z pustą skrzynką (YYYY-MM-DD) */
+	last_inbox_zero: string | null;
+}
+
+/**
+ * Zadanie wraz z drzewem podzadań i zagregowanym postępem
+ */
+export interface TaskNode extends Task {
+	/** Procent ukończenia (0-100) */
+	progress: number;
+	/** Podzadania w kolejności utworzenia */
+	subtasks: TaskNode[];
 }
 
 /**
@@ -0,0 +1,20 @@
+/**
+ * Rodzaj elementu w koszu
+ */
+export type TrashKind = 'task' | 'habit';
+
+/**
+ * Usunięte zadanie lub nawyk czekający w koszu
+ */
+export interface TrashItem {
+	kind: TrashKind;
+	/** ID zadania lub nawyku (do przywrócenia lub trwałego usunięcia) */
+	id: number;
+	title: string;
+	/** Liczba podzadań usuniętych razem z zadaniem (dla nawyków - liczba wpisów) */
+	item_count: number;
+	/** Timestamp przeniesienia do kosza (Unix timestamp) */
+	deleted_at: number;
+	/** Timestamp trwałego usunięcia (Unix timestamp) */
+	purge_at: number;
+}